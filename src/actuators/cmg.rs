@@ -0,0 +1,259 @@
+use altai_rs::meta::types::{Generic1D, Generic2D, Vector3};
+use ndarray::Array2;
+
+use crate::actuators::types::ActuatorError;
+
+/// Single-gimbal control moment gyro with a constant-speed rotor. The rotor
+/// and gimbal inertias are part of the vehicle inertia tensor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ControlMomentGyro {
+    pub gimbal_axis_body: [f64; 3], // Unit gimbal axis (Body) [-]
+    pub spin_axis_body: [f64; 3],   // Unit rotor momentum direction at zero gimbal angle (Body) [-]
+    pub rotor_momentum: f64,        // [N m s]
+    pub max_gimbal_rate: f64,       // [rad/s]
+    pub gimbal_time_constant: f64,  // First-order gimbal rate loop; 0 for ideal [s]
+    pub initial_angle: f64,         // [rad]
+}
+impl Default for ControlMomentGyro {
+    fn default() -> Self {
+        // Small agile-imager class CMG
+        Self {
+            gimbal_axis_body: [0., 0., 1.],
+            spin_axis_body: [1., 0., 0.],
+            rotor_momentum: 1.,
+            max_gimbal_rate: 1.,
+            gimbal_time_constant: 0.05,
+            initial_angle: 0.,
+        }
+    }
+}
+
+/// Gimbal state after a step.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GimbalStep {
+    pub angle: f64,      // [rad]
+    pub rate: f64,       // [rad/s]
+    pub saturated: bool, // Rate command beyond the limit
+}
+
+impl ControlMomentGyro {
+    pub fn validate(&self, index: usize) -> Result<(), ActuatorError> {
+        for axis in [self.gimbal_axis_body, self.spin_axis_body] {
+            let norm = axis.iter().map(|x| x * x).sum::<f64>().sqrt();
+            if (norm - 1.).abs() > 1e-6 {
+                return Err(ActuatorError::AxisNotUnit {
+                    device: "control moment gyro",
+                    index,
+                    norm,
+                });
+            }
+        }
+        let g = self.gimbal_axis_body;
+        let s = self.spin_axis_body;
+        let cos_gs = g[0] * s[0] + g[1] * s[1] + g[2] * s[2];
+        if cos_gs.abs() > 1e-6 {
+            return Err(ActuatorError::AxesNotOrthogonal {
+                device: "control moment gyro",
+                index,
+                cos: cos_gs,
+            });
+        }
+        for (field, value) in [
+            ("rotor_momentum", self.rotor_momentum),
+            ("max_gimbal_rate", self.max_gimbal_rate),
+        ] {
+            ActuatorError::check_positive("control moment gyro", index, field, value)?;
+        }
+        ActuatorError::check_non_negative(
+            "control moment gyro",
+            index,
+            "gimbal_time_constant",
+            self.gimbal_time_constant,
+        )
+    }
+
+    /// Unit rotor momentum direction at a gimbal angle (Body) [-].
+    pub fn spin_direction(&self, angle: f64) -> [f64; 3] {
+        let t = self.transverse_axis();
+        let (s, c) = angle.sin_cos();
+        [0, 1, 2].map(|k| self.spin_axis_body[k] * c + t[k] * s)
+    }
+
+    /// Direction of the output torque per unit gimbal rate, d(spin)/d(angle) (Body) [-].
+    pub fn torque_direction(&self, angle: f64) -> [f64; 3] {
+        let t = self.transverse_axis();
+        let (s, c) = angle.sin_cos();
+        [0, 1, 2].map(|k| -self.spin_axis_body[k] * s + t[k] * c)
+    }
+
+    fn transverse_axis(&self) -> [f64; 3] {
+        let (g, s) = (self.gimbal_axis_body, self.spin_axis_body);
+        [
+            g[1] * s[2] - g[2] * s[1],
+            g[2] * s[0] - g[0] * s[2],
+            g[0] * s[1] - g[1] * s[0],
+        ]
+    }
+
+    /// Advance the gimbal over `dt` holding the rate command.
+    pub fn step(&self, angle: f64, rate: f64, rate_cmd: f64, dt: f64) -> GimbalStep {
+        let saturated = rate_cmd.abs() > self.max_gimbal_rate;
+        let cmd = rate_cmd.clamp(-self.max_gimbal_rate, self.max_gimbal_rate);
+        // Rate loop: rate' = (cmd - rate) / tau, integrated exactly
+        let (next_rate, travel) = if self.gimbal_time_constant > 0. {
+            let decay = (-dt / self.gimbal_time_constant).exp();
+            (
+                cmd + (rate - cmd) * decay,
+                cmd * dt + (rate - cmd) * self.gimbal_time_constant * (1. - decay),
+            )
+        } else {
+            (cmd, cmd * dt)
+        };
+        GimbalStep {
+            angle: angle + travel,
+            rate: next_rate,
+            saturated,
+        }
+    }
+}
+
+/// Control moment gyro cluster configuration.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ControlMomentGyroCluster {
+    pub gyros: Vec<ControlMomentGyro>,
+}
+impl ControlMomentGyroCluster {
+    pub fn validate(&self) -> Result<(), ActuatorError> {
+        self.gyros
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, gyro)| gyro.validate(index))
+    }
+
+    pub fn len(&self) -> usize {
+        self.gyros.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.gyros.is_empty()
+    }
+
+    pub fn initial_angle(&self) -> Generic1D {
+        self.gyros.iter().map(|g| g.initial_angle).collect()
+    }
+
+    /// Rotor momentum relative to the body (Body) [N m s].
+    pub fn momentum_body(&self, angles: &Generic1D) -> Vector3 {
+        let mut h = Vector3::zeros((3, 1));
+        for (gyro, &angle) in self.gyros.iter().zip(angles) {
+            let dir = gyro.spin_direction(angle);
+            for k in 0..3 {
+                h[[k, 0]] += gyro.rotor_momentum * dir[k];
+            }
+        }
+        h
+    }
+
+    /// Momentum Jacobian dh/d(angles) (Body) [N m s/rad], 3xN.
+    pub fn jacobian(&self, angles: &Generic1D) -> Generic2D {
+        let dirs: Vec<[f64; 3]> = self
+            .gyros
+            .iter()
+            .zip(angles)
+            .map(|(g, &a)| g.torque_direction(a).map(|x| x * g.rotor_momentum))
+            .collect();
+        Array2::from_shape_fn((3, self.len()), |(r, c)| dirs[c][r])
+    }
+
+    /// Singularity measure det(C C^T) of the Jacobian with unit rotor
+    /// momenta; zero at a singular gimbal set [-].
+    pub fn singularity_measure(&self, angles: &Generic1D) -> f64 {
+        let c = Array2::from_shape_fn((3, self.len()), |(r, i)| {
+            self.gyros[i].torque_direction(angles[i])[r]
+        });
+        let m = c.dot(&c.t());
+        m[[0, 0]] * (m[[1, 1]] * m[[2, 2]] - m[[1, 2]] * m[[2, 1]])
+            - m[[0, 1]] * (m[[1, 0]] * m[[2, 2]] - m[[1, 2]] * m[[2, 0]])
+            + m[[0, 2]] * (m[[1, 0]] * m[[2, 1]] - m[[1, 1]] * m[[2, 0]])
+    }
+}
+
+/// Gimbal states and cluster momentum at the end of the step.
+#[derive(Debug, Clone)]
+pub struct ControlMomentGyroSignal {
+    pub gimbal_angle: Generic1D, // [rad]
+    pub gimbal_rate: Generic1D,  // [rad/s]
+    pub saturated: Vec<bool>,
+    pub momentum_start: Vector3, // Cluster momentum at the start of the step (Body) [N m s]
+    pub momentum_body: Vector3,  // Cluster momentum relative to the body (Body) [N m s]
+    pub momentum_rate: Vector3,  // Step-averaged dh/dt (Body) [N m]
+    pub singularity: f64,        // det(C C^T) [-]
+}
+impl Default for ControlMomentGyroSignal {
+    fn default() -> Self {
+        Self {
+            gimbal_angle: Generic1D::zeros(0),
+            gimbal_rate: Generic1D::zeros(0),
+            saturated: Vec::new(),
+            momentum_start: Vector3::zeros((3, 1)),
+            momentum_body: Vector3::zeros((3, 1)),
+            momentum_rate: Vector3::zeros((3, 1)),
+            singularity: 0.,
+        }
+    }
+}
+impl ControlMomentGyroSignal {
+    pub fn initialize(cluster: &ControlMomentGyroCluster) -> Self {
+        let n = cluster.len();
+        let gimbal_angle = cluster.initial_angle();
+        let momentum_body = cluster.momentum_body(&gimbal_angle);
+        Self {
+            momentum_start: momentum_body.clone(),
+            momentum_body,
+            singularity: cluster.singularity_measure(&gimbal_angle),
+            gimbal_angle,
+            gimbal_rate: Generic1D::zeros(n),
+            saturated: vec![false; n],
+            momentum_rate: Vector3::zeros((3, 1)),
+        }
+    }
+
+    /// Gimbals advanced from the previous step's state; missing rate
+    /// commands are taken as zero.
+    pub fn step(
+        &mut self,
+        cluster: &ControlMomentGyroCluster,
+        rate_cmd: &Generic1D,
+        prev: &Self,
+        dt: f64,
+    ) {
+        let steps: Vec<GimbalStep> = cluster
+            .gyros
+            .iter()
+            .enumerate()
+            .map(|(i, gyro)| {
+                let cmd = rate_cmd.get(i).copied().unwrap_or(0.);
+                gyro.step(prev.gimbal_angle[i], prev.gimbal_rate[i], cmd, dt)
+            })
+            .collect();
+        self.gimbal_angle = steps.iter().map(|s| s.angle).collect();
+        self.gimbal_rate = steps.iter().map(|s| s.rate).collect();
+        self.saturated = steps.iter().map(|s| s.saturated).collect();
+        self.momentum_start.clone_from(&prev.momentum_body);
+        self.momentum_body = cluster.momentum_body(&self.gimbal_angle);
+        self.momentum_rate = (&self.momentum_body - &self.momentum_start) / dt;
+        self.singularity = cluster.singularity_measure(&self.gimbal_angle);
+    }
+
+    /// Output torque on the spacecraft, minus the momentum rate (Body) [N m].
+    /// The gyroscopic w x h term is carried by the attitude dynamics.
+    pub fn output_torque(&self) -> Vector3 {
+        -&self.momentum_rate
+    }
+
+    /// Cluster momentum `elapsed` seconds into the step, linear between its
+    /// ends (Body) [N m s].
+    pub fn momentum_at(&self, elapsed: f64) -> Vector3 {
+        &self.momentum_start + &(&self.momentum_rate * elapsed)
+    }
+}
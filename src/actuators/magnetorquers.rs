@@ -0,0 +1,136 @@
+use altai_rs::meta::types::{Generic1D, Vector3};
+use altai_rs::veclib::fcross;
+use ndarray::Array2;
+
+use crate::actuators::types::ActuatorError;
+
+/// How the magnetorquer command vector is read.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum MagnetorquerCommand {
+    // Dipole per rod [A m2]
+    #[default]
+    Dipole,
+    // Signed PWM duty cycle per rod in [-1, 1]; the dipole is the period average
+    DutyCycle,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Magnetorquer {
+    pub axis_body: [f64; 3],  // Unit dipole axis (Body) [-]
+    pub max_dipole: f64,      // Dipole at full drive [A m2]
+    pub levels: u32,          // Drive steps per polarity at full scale; 0 for continuous
+    pub residual_dipole: f64, // Remanent dipole along the axis, present when off [A m2]
+}
+impl Default for Magnetorquer {
+    fn default() -> Self {
+        // Small-satellite torque rod with an 8-bit signed driver
+        Self {
+            axis_body: [1., 0., 0.],
+            max_dipole: 0.2,
+            levels: 127,
+            residual_dipole: 0.,
+        }
+    }
+}
+impl Magnetorquer {
+    pub fn validate(&self, index: usize) -> Result<(), ActuatorError> {
+        let norm = self.axis_body.iter().map(|x| x * x).sum::<f64>().sqrt();
+        if (norm - 1.).abs() > 1e-6 {
+            return Err(ActuatorError::AxisNotUnit {
+                device: "magnetorquer",
+                index,
+                norm,
+            });
+        }
+        ActuatorError::check_positive("magnetorquer", index, "max_dipole", self.max_dipole)
+    }
+
+    /// Delivered dipole [A m2] and whether the command was clipped.
+    pub fn dipole(&self, command: f64, mode: MagnetorquerCommand) -> (f64, bool) {
+        let drive = match mode {
+            MagnetorquerCommand::Dipole => command / self.max_dipole,
+            MagnetorquerCommand::DutyCycle => command,
+        };
+        let saturated = drive.abs() > 1.;
+        let mut drive = drive.clamp(-1., 1.);
+        if self.levels > 0 {
+            let levels = self.levels as f64;
+            drive = (drive * levels).round() / levels;
+        }
+        (drive * self.max_dipole + self.residual_dipole, saturated)
+    }
+}
+
+/// Magnetorquer assembly configuration.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MagnetorquerAssembly {
+    pub rods: Vec<Magnetorquer>,
+    pub command: MagnetorquerCommand,
+}
+impl MagnetorquerAssembly {
+    pub fn validate(&self) -> Result<(), ActuatorError> {
+        self.rods
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, rod)| rod.validate(index))
+    }
+
+    pub fn len(&self) -> usize {
+        self.rods.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rods.is_empty()
+    }
+
+    /// Dipole axes as columns (Body) [-], 3xN.
+    pub fn axes(&self) -> Array2<f64> {
+        Array2::from_shape_fn((3, self.len()), |(r, c)| self.rods[c].axis_body[r])
+    }
+}
+
+/// Magnetorquer dipoles, held over the step.
+#[derive(Debug, Clone)]
+pub struct MagnetorquerSignal {
+    pub dipole: Generic1D, // Per rod, residual included [A m2]
+    pub m_body: Vector3,   // Net dipole (Body) [A m2]
+    pub saturated: Vec<bool>,
+}
+impl Default for MagnetorquerSignal {
+    fn default() -> Self {
+        Self {
+            dipole: Generic1D::zeros(0),
+            m_body: Vector3::zeros((3, 1)),
+            saturated: Vec::new(),
+        }
+    }
+}
+impl MagnetorquerSignal {
+    pub fn initialize(assembly: &MagnetorquerAssembly) -> Self {
+        let mut signal = Self::default();
+        signal.step(assembly, &Generic1D::zeros(0));
+        signal
+    }
+
+    /// Dipoles for the step; missing commands are taken as zero.
+    pub fn step(&mut self, assembly: &MagnetorquerAssembly, command: &Generic1D) {
+        let (dipole, saturated): (Vec<f64>, Vec<bool>) = assembly
+            .rods
+            .iter()
+            .enumerate()
+            .map(|(i, rod)| rod.dipole(command.get(i).copied().unwrap_or(0.), assembly.command))
+            .unzip();
+        self.dipole = Generic1D::from(dipole);
+        self.saturated = saturated;
+        self.m_body = assembly
+            .axes()
+            .dot(&self.dipole)
+            .into_shape_with_order((3, 1))
+            .unwrap();
+    }
+
+    /// Torque m x B (Body) [N m] for a field (Body) [T].
+    pub fn torque(&self, b_body: &Vector3) -> Vector3 {
+        fcross(&self.m_body, b_body)
+    }
+}
@@ -1 +1,5 @@
+pub mod cmg;
+pub mod magnetorquers;
+pub mod reaction_wheels;
+pub mod thrusters;
 pub mod types;
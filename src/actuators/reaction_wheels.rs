@@ -0,0 +1,239 @@
+use altai_rs::meta::types::{Generic1D, Vector3};
+use ndarray::Array2;
+
+use crate::actuators::types::ActuatorError;
+
+/// Bearing and lubricant drag on the rotor; Olsson et al. (1998) Stribeck form.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WheelFriction {
+    pub coulomb: f64,        // Kinetic friction [N m]
+    pub stribeck: f64,       // Static friction in excess of Coulomb [N m]
+    pub stribeck_speed: f64, // Decay speed of the static excess [rad/s]
+    pub viscous: f64,        // [N m s/rad]
+}
+impl WheelFriction {
+    /// Friction torque on the rotor at a wheel speed [N m].
+    pub fn torque(&self, speed: f64) -> f64 {
+        if speed == 0. {
+            return 0.;
+        }
+        let stribeck = if self.stribeck_speed > 0. {
+            self.stribeck * (-(speed / self.stribeck_speed).powi(2)).exp()
+        } else {
+            0.
+        };
+        -(self.coulomb + stribeck).copysign(speed) - self.viscous * speed
+    }
+
+    /// Motor torque needed to start a wheel at rest [N m].
+    pub fn breakaway(&self) -> f64 {
+        self.coulomb + self.stribeck
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReactionWheel {
+    pub spin_axis_body: [f64; 3], // Unit spin axis (Body) [-]
+    pub inertia: f64,             // Rotor inertia about the spin axis [kg m2]
+    pub max_torque: f64,          // Motor torque limit [N m]
+    pub max_speed: f64,           // Over-speed cutoff [rad/s]
+    pub motor_constant: f64,      // Torque constant [N m/A]
+    pub max_current: f64,         // Drive current limit [A]
+    pub friction: WheelFriction,
+    pub initial_speed: f64, // [rad/s]
+}
+impl Default for ReactionWheel {
+    fn default() -> Self {
+        // Small-satellite class wheel, ~0.1 N m s at 6000 rpm
+        Self {
+            spin_axis_body: [1., 0., 0.],
+            inertia: 1.6e-4,
+            max_torque: 5e-3,
+            max_speed: 628.,
+            motor_constant: 5e-3,
+            max_current: 1.,
+            friction: WheelFriction::default(),
+            initial_speed: 0.,
+        }
+    }
+}
+
+/// Wheel state after a step.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WheelStep {
+    pub speed: f64,           // [rad/s]
+    pub motor_torque: f64,    // Delivered motor torque on the rotor [N m]
+    pub friction_torque: f64, // [N m]
+    pub saturated: bool,      // Command not met: torque, current or speed limit
+}
+
+impl ReactionWheel {
+    pub fn validate(&self, index: usize) -> Result<(), ActuatorError> {
+        let norm = self
+            .spin_axis_body
+            .iter()
+            .map(|x| x * x)
+            .sum::<f64>()
+            .sqrt();
+        if (norm - 1.).abs() > 1e-6 {
+            return Err(ActuatorError::AxisNotUnit {
+                device: "reaction wheel",
+                index,
+                norm,
+            });
+        }
+        for (field, value) in [
+            ("inertia", self.inertia),
+            ("max_torque", self.max_torque),
+            ("max_speed", self.max_speed),
+            ("motor_constant", self.motor_constant),
+            ("max_current", self.max_current),
+        ] {
+            ActuatorError::check_positive("reaction wheel", index, field, value)?;
+        }
+        Ok(())
+    }
+
+    /// Deliverable motor torque: the lower of the torque and current limits [N m].
+    pub fn torque_limit(&self) -> f64 {
+        self.max_torque.min(self.motor_constant * self.max_current)
+    }
+
+    /// Advance the wheel speed over `dt` holding the command.
+    pub fn step(&self, speed: f64, torque_cmd: f64, dt: f64) -> WheelStep {
+        let limit = self.torque_limit();
+        let mut motor = torque_cmd.clamp(-limit, limit);
+        let mut saturated = torque_cmd.abs() > limit;
+        let mut friction = self.friction.torque(speed);
+        let mut next = speed + (motor + friction) / self.inertia * dt;
+
+        // Over-speed protection: drive torque only up to the cutoff speed
+        if next.abs() > self.max_speed && motor * next > 0. {
+            let cutoff = self.max_speed.copysign(next);
+            motor = ((cutoff - speed) * self.inertia / dt - friction).clamp(-limit, limit);
+            if motor * cutoff < 0. {
+                motor = 0.;
+            }
+            next = speed + (motor + friction) / self.inertia * dt;
+            saturated = true;
+        }
+
+        // Friction stops the rotor but cannot reverse it; static friction
+        // holds it until the motor exceeds breakaway
+        if next * speed <= 0. && motor.abs() <= self.friction.breakaway() {
+            friction = -motor - speed * self.inertia / dt;
+            next = 0.;
+        }
+
+        WheelStep {
+            speed: next,
+            motor_torque: motor,
+            friction_torque: friction,
+            saturated,
+        }
+    }
+}
+
+/// Reaction wheel assembly configuration.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReactionWheelAssembly {
+    pub wheels: Vec<ReactionWheel>,
+}
+impl ReactionWheelAssembly {
+    pub fn validate(&self) -> Result<(), ActuatorError> {
+        self.wheels
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, wheel)| wheel.validate(index))
+    }
+
+    pub fn len(&self) -> usize {
+        self.wheels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.wheels.is_empty()
+    }
+
+    /// Spin axes as columns (Body) [-], 3xN.
+    pub fn spin_axes(&self) -> Array2<f64> {
+        Array2::from_shape_fn((3, self.len()), |(r, c)| self.wheels[c].spin_axis_body[r])
+    }
+
+    /// Rotor inertias about the spin axes [kg m2].
+    pub fn spin_inertia(&self) -> Generic1D {
+        self.wheels.iter().map(|w| w.inertia).collect()
+    }
+
+    pub fn initial_speed(&self) -> Generic1D {
+        self.wheels.iter().map(|w| w.initial_speed).collect()
+    }
+
+    /// Wheel momentum about the spin axes [N m s].
+    pub fn axial_momentum(&self, speeds: &Generic1D) -> Generic1D {
+        Generic1D::from_iter(self.wheels.iter().zip(speeds).map(|(w, s)| w.inertia * s))
+    }
+
+    /// Stored momentum (Body) [N m s].
+    pub fn momentum_body(&self, speeds: &Generic1D) -> Vector3 {
+        self.spin_axes()
+            .dot(&self.axial_momentum(speeds))
+            .into_shape_with_order((3, 1))
+            .unwrap()
+    }
+}
+
+/// Reaction wheel assembly torques, held over the step. Wheel speeds are
+/// part of the attitude state.
+#[derive(Debug, Clone, Default)]
+pub struct ReactionWheelSignal {
+    pub motor_torque: Generic1D,    // [N m]
+    pub friction_torque: Generic1D, // [N m]
+    pub saturated: Vec<bool>,
+}
+impl ReactionWheelSignal {
+    pub fn initialize(assembly: &ReactionWheelAssembly) -> Self {
+        let n = assembly.len();
+        Self {
+            motor_torque: Generic1D::zeros(n),
+            friction_torque: Generic1D::zeros(n),
+            saturated: vec![false; n],
+        }
+    }
+
+    /// Torques for every wheel from the speeds at the start of the step;
+    /// missing commands are taken as zero.
+    pub fn step(
+        &mut self,
+        assembly: &ReactionWheelAssembly,
+        torque_cmd: &Generic1D,
+        speed: &Generic1D,
+        dt: f64,
+    ) {
+        let steps: Vec<WheelStep> = assembly
+            .wheels
+            .iter()
+            .enumerate()
+            .map(|(i, wheel)| {
+                let cmd = torque_cmd.get(i).copied().unwrap_or(0.);
+                wheel.step(speed[i], cmd, dt)
+            })
+            .collect();
+        self.motor_torque = steps.iter().map(|s| s.motor_torque).collect();
+        self.friction_torque = steps.iter().map(|s| s.friction_torque).collect();
+        self.saturated = steps.iter().map(|s| s.saturated).collect();
+    }
+
+    /// Net axial torque on each rotor [N m].
+    pub fn axial_torque(&self) -> Generic1D {
+        &self.motor_torque + &self.friction_torque
+    }
+
+    /// Reaction torque on the spacecraft (Body) [N m]; minus the rate of
+    /// change of stored momentum.
+    pub fn reaction_torque(&self, assembly: &ReactionWheelAssembly) -> Vector3 {
+        (-assembly.spin_axes().dot(&self.axial_torque()))
+            .into_shape_with_order((3, 1))
+            .unwrap()
+    }
+}
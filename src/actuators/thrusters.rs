@@ -0,0 +1,279 @@
+use altai_rs::meta::types::{Generic1D, Generic2D, Vector3};
+use altai_rs::veclib::fcross;
+use ndarray::Array2;
+
+use crate::actuators::types::ActuatorError;
+use crate::sc_types::SpacecraftMassArchitecture;
+
+// Standard gravity for Isp [m/s2]
+const G0: f64 = 9.80665;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Thruster {
+    pub position_body: [f64; 3],  // Nozzle exit center (Body) [m]
+    pub direction_body: [f64; 3], // Unit thrust direction, opposite the plume (Body) [-]
+    pub thrust: f64,              // Nominal steady-state thrust [N]
+    pub isp: f64,                 // Specific impulse [s]
+    pub min_impulse_bit: f64,     // Smallest deliverable impulse [N s]
+    pub rise_time: f64,           // First-order time constant at valve open [s]
+    pub fall_time: f64,           // First-order time constant at valve close [s]
+    pub misalignment: [f64; 3],   // Rotation vector applied to the direction (Body) [rad]
+    pub thrust_error: f64,        // Fractional thrust magnitude error [-]
+}
+impl Default for Thruster {
+    fn default() -> Self {
+        // 1 N hydrazine monopropellant thruster
+        Self {
+            position_body: [0., 0., 0.],
+            direction_body: [0., 0., 1.],
+            thrust: 1.,
+            isp: 220.,
+            min_impulse_bit: 0.01,
+            rise_time: 0.01,
+            fall_time: 0.02,
+            misalignment: [0., 0., 0.],
+            thrust_error: 0.,
+        }
+    }
+}
+
+/// Thruster state after a step.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ThrusterPulse {
+    pub level: f64,     // Thrust fraction at the end of the step [-]
+    pub burn_time: f64, // Time-integral of the thrust fraction over the step [s]
+}
+
+impl Thruster {
+    pub fn validate(&self, index: usize) -> Result<(), ActuatorError> {
+        let norm = self
+            .direction_body
+            .iter()
+            .map(|x| x * x)
+            .sum::<f64>()
+            .sqrt();
+        if (norm - 1.).abs() > 1e-6 {
+            return Err(ActuatorError::AxisNotUnit {
+                device: "thruster",
+                index,
+                norm,
+            });
+        }
+        for (field, value) in [("thrust", self.thrust), ("isp", self.isp)] {
+            ActuatorError::check_positive("thruster", index, field, value)?;
+        }
+        for (field, value) in [
+            ("min_impulse_bit", self.min_impulse_bit),
+            ("rise_time", self.rise_time),
+            ("fall_time", self.fall_time),
+        ] {
+            ActuatorError::check_non_negative("thruster", index, field, value)?;
+        }
+        Ok(())
+    }
+
+    /// Delivered steady-state thrust, magnitude error included [N].
+    pub fn actual_thrust(&self) -> f64 {
+        self.thrust * (1. + self.thrust_error)
+    }
+
+    /// Thrust direction after the misalignment rotation (Body) [-].
+    pub fn actual_direction(&self) -> [f64; 3] {
+        let d = self.direction_body;
+        let theta = self.misalignment;
+        let angle = theta.iter().map(|x| x * x).sum::<f64>().sqrt();
+        if angle == 0. {
+            return d;
+        }
+        // Rodrigues rotation about k = theta / |theta|
+        let k = theta.map(|x| x / angle);
+        let kxd = [
+            k[1] * d[2] - k[2] * d[1],
+            k[2] * d[0] - k[0] * d[2],
+            k[0] * d[1] - k[1] * d[0],
+        ];
+        let kd = k[0] * d[0] + k[1] * d[1] + k[2] * d[2];
+        let (s, c) = angle.sin_cos();
+        [0, 1, 2].map(|i| d[i] * c + kxd[i] * s + k[i] * kd * (1. - c))
+    }
+
+    /// Valve open for `on_time` from the start of the step, closed for the
+    /// rest of `dt`; thrust follows the valve with first-order lags.
+    pub fn step(&self, level: f64, on_time: f64, dt: f64) -> ThrusterPulse {
+        // Pulses shorter than the minimum impulse bit are not fired
+        let on_time = if on_time * self.thrust < self.min_impulse_bit {
+            0.
+        } else {
+            on_time
+        };
+        let off_time = dt - on_time;
+
+        let (mut burn_time, mut level) = (0., level);
+        if on_time > 0. {
+            if self.rise_time > 0. {
+                let decay = (-on_time / self.rise_time).exp();
+                burn_time += on_time - (1. - level) * self.rise_time * (1. - decay);
+                level = 1. - (1. - level) * decay;
+            } else {
+                burn_time += on_time;
+                level = 1.;
+            }
+        }
+        if off_time > 0. {
+            if self.fall_time > 0. {
+                let decay = (-off_time / self.fall_time).exp();
+                burn_time += level * self.fall_time * (1. - decay);
+                level *= decay;
+            } else {
+                level = 0.;
+            }
+        }
+        ThrusterPulse { level, burn_time }
+    }
+}
+
+/// Thruster assembly configuration. Propellant is included in the configured
+/// vehicle mass and drawn from a point-mass tank.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ThrusterAssembly {
+    pub thrusters: Vec<Thruster>,
+    pub propellant_mass: f64,         // Loaded propellant [kg]
+    pub tank_position_body: [f64; 3], // Propellant center of mass (Body) [m]
+}
+impl ThrusterAssembly {
+    /// `mass` is the wet spacecraft mass the propellant is drawn from [kg].
+    pub fn validate(&self, mass: f64) -> Result<(), ActuatorError> {
+        ActuatorError::check_non_negative(
+            "propellant tank",
+            0,
+            "propellant_mass",
+            self.propellant_mass,
+        )?;
+        if self.propellant_mass >= mass {
+            return Err(ActuatorError::PropellantExceedsMass {
+                propellant_mass: self.propellant_mass,
+                mass,
+            });
+        }
+        self.thrusters
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, thruster)| thruster.validate(index))
+    }
+
+    pub fn len(&self) -> usize {
+        self.thrusters.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.thrusters.is_empty()
+    }
+
+    /// Delivered thrust directions as columns (Body) [-], 3xN.
+    pub fn directions(&self) -> Array2<f64> {
+        let dirs: Vec<[f64; 3]> = self
+            .thrusters
+            .iter()
+            .map(|t| t.actual_direction())
+            .collect();
+        Array2::from_shape_fn((3, self.len()), |(r, c)| dirs[c][r])
+    }
+
+    /// Mass properties with `consumed` propellant removed from the tank.
+    pub fn mass_properties(
+        &self,
+        wet: &SpacecraftMassArchitecture,
+        consumed: f64,
+    ) -> SpacecraftMassArchitecture {
+        if consumed <= 0. {
+            return wet.clone();
+        }
+        let tank = Vector3::from_shape_fn((3, 1), |(k, _)| self.tank_position_body[k]);
+        // Parallel axis theorem through the body origin
+        let point_inertia = |m: f64, r: &Vector3| -> Generic2D {
+            m * (r.iter().map(|x| x * x).sum::<f64>() * Generic2D::eye(3) - r.dot(&r.t()))
+        };
+        let mass = wet.mass - consumed;
+        let r_cm_body = (wet.mass * &wet.r_cm_body - consumed * &tank) / mass;
+        let j_origin =
+            &wet.j_sc + &point_inertia(wet.mass, &wet.r_cm_body) - point_inertia(consumed, &tank);
+        SpacecraftMassArchitecture {
+            mass,
+            j_sc: j_origin - point_inertia(mass, &r_cm_body),
+            r_cm_body,
+        }
+    }
+}
+
+/// Step-averaged thrusts and remaining propellant.
+#[derive(Debug, Clone, Default)]
+pub struct ThrusterSignal {
+    pub level: Generic1D,     // Thrust fraction at the end of the step [-]
+    pub thrust: Generic1D,    // Step-averaged thrust [N]
+    pub saturated: Vec<bool>, // On-time command exceeded the step
+    pub propellant_mass: f64, // Remaining [kg]
+    pub propellant_used: f64, // Consumed over the step [kg]
+}
+impl ThrusterSignal {
+    pub fn initialize(assembly: &ThrusterAssembly) -> Self {
+        let n = assembly.len();
+        Self {
+            level: Generic1D::zeros(n),
+            thrust: Generic1D::zeros(n),
+            saturated: vec![false; n],
+            propellant_mass: assembly.propellant_mass,
+            propellant_used: 0.,
+        }
+    }
+
+    /// Thrusts for on-time commands [s] fired from the start of the step;
+    /// missing commands are taken as zero.
+    pub fn step(&mut self, assembly: &ThrusterAssembly, on_time_cmd: &Generic1D, dt: f64) {
+        let mut impulse = Generic1D::zeros(assembly.len());
+        for (i, thruster) in assembly.thrusters.iter().enumerate() {
+            let cmd = on_time_cmd.get(i).copied().unwrap_or(0.);
+            self.saturated[i] = cmd > dt;
+            let pulse = thruster.step(self.level[i], cmd.clamp(0., dt), dt);
+            self.level[i] = pulse.level;
+            impulse[i] = thruster.actual_thrust() * pulse.burn_time;
+        }
+
+        // Propellant flow m_dot = F / (Isp g0); thrust stops when the tank runs dry
+        let demand: f64 = assembly
+            .thrusters
+            .iter()
+            .zip(impulse.iter())
+            .map(|(t, j)| j / (t.isp * G0))
+            .sum();
+        if demand > self.propellant_mass {
+            impulse *= self.propellant_mass / demand;
+            self.level.fill(0.);
+        }
+        self.propellant_used = demand.min(self.propellant_mass);
+        self.propellant_mass -= self.propellant_used;
+        self.thrust = impulse / dt;
+    }
+
+    /// Net thrust force (Body) [N].
+    pub fn force_body(&self, assembly: &ThrusterAssembly) -> Vector3 {
+        assembly
+            .directions()
+            .dot(&self.thrust)
+            .into_shape_with_order((3, 1))
+            .unwrap()
+    }
+
+    /// Thrust torque about the center of mass (Body) [N m].
+    pub fn torque_body(&self, assembly: &ThrusterAssembly, r_cm_body: &Vector3) -> Vector3 {
+        let dirs = assembly.directions();
+        let mut torque = Vector3::zeros((3, 1));
+        for (i, thruster) in assembly.thrusters.iter().enumerate() {
+            let arm = Vector3::from_shape_fn((3, 1), |(k, _)| {
+                thruster.position_body[k] - r_cm_body[[k, 0]]
+            });
+            let force = Vector3::from_shape_fn((3, 1), |(k, _)| dirs[[k, i]] * self.thrust[i]);
+            torque += &fcross(&arm, &force);
+        }
+        torque
+    }
+}
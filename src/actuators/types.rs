@@ -1,11 +1,155 @@
-use altai_rs::meta::types::Vector3;
+use std::fmt;
+
+use altai_rs::meta::types::{Generic1D, Vector3};
 use ndarray::array;
 use polaris_fsw::actuators::types::ActuatorBus;
 
+use crate::actuators::cmg::{ControlMomentGyroCluster, ControlMomentGyroSignal};
+use crate::actuators::magnetorquers::{MagnetorquerAssembly, MagnetorquerSignal};
+use crate::actuators::reaction_wheels::{ReactionWheelAssembly, ReactionWheelSignal};
+use crate::actuators::thrusters::{ThrusterAssembly, ThrusterSignal};
+use crate::attitude::types::TruthAttitudeSignal;
+use crate::ephemeris::types::TruthEphemerisSignal;
+use crate::sc_types::{SpacecraftActuatorArchitecture, SpacecraftMassArchitecture};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActuatorError {
+    AxisNotUnit {
+        device: &'static str,
+        index: usize,
+        norm: f64,
+    },
+    NonPositive {
+        device: &'static str,
+        index: usize,
+        field: &'static str,
+        value: f64,
+    },
+    AxesNotOrthogonal {
+        device: &'static str,
+        index: usize,
+        cos: f64,
+    },
+    Negative {
+        device: &'static str,
+        index: usize,
+        field: &'static str,
+        value: f64,
+    },
+    PropellantExceedsMass {
+        propellant_mass: f64,
+        mass: f64,
+    },
+}
+impl fmt::Display for ActuatorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AxisNotUnit {
+                device,
+                index,
+                norm,
+            } => write!(f, "{device} {index}: axis norm {norm} is not unity"),
+            Self::NonPositive {
+                device,
+                index,
+                field,
+                value,
+            } => write!(f, "{device} {index}: `{field}` = {value} must be positive"),
+            Self::AxesNotOrthogonal { device, index, cos } => {
+                write!(f, "{device} {index}: axes are not orthogonal (cos = {cos})")
+            }
+            Self::Negative {
+                device,
+                index,
+                field,
+                value,
+            } => write!(
+                f,
+                "{device} {index}: `{field}` = {value} must not be negative"
+            ),
+            Self::PropellantExceedsMass {
+                propellant_mass,
+                mass,
+            } => write!(
+                f,
+                "propellant mass {propellant_mass} kg must be less than the spacecraft mass {mass} kg"
+            ),
+        }
+    }
+}
+impl std::error::Error for ActuatorError {}
+
+impl ActuatorError {
+    /// Rejects zero, negative and NaN values.
+    pub(crate) fn check_positive(
+        device: &'static str,
+        index: usize,
+        field: &'static str,
+        value: f64,
+    ) -> Result<(), Self> {
+        if value.is_nan() || value <= 0. {
+            return Err(Self::NonPositive {
+                device,
+                index,
+                field,
+                value,
+            });
+        }
+        Ok(())
+    }
+
+    /// Rejects negative and NaN values.
+    pub(crate) fn check_non_negative(
+        device: &'static str,
+        index: usize,
+        field: &'static str,
+        value: f64,
+    ) -> Result<(), Self> {
+        if value.is_nan() || value < 0. {
+            return Err(Self::Negative {
+                device,
+                index,
+                field,
+                value,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Plant-side actuator commands, one entry per device in configuration order.
+/// Missing entries are taken as zero.
+#[derive(Clone, Debug, Default)]
+pub struct ActuatorCommands {
+    pub rw_torque_cmd: Generic1D,   // Wheel motor torque [N m]
+    pub mtq_cmd: Generic1D,         // Dipole or duty cycle, see `MagnetorquerCommand`
+    pub thr_on_time_cmd: Generic1D, // On-time from the start of the step [s]
+    pub cmg_rate_cmd: Generic1D,    // Gimbal rate [rad/s]
+}
+impl From<&ActuatorBus> for ActuatorCommands {
+    fn from(actuator_bus: &ActuatorBus) -> Self {
+        Self {
+            rw_torque_cmd: actuator_bus.rw_torque_cmd.clone(),
+            mtq_cmd: actuator_bus.mtq_cmd.clone(),
+            thr_on_time_cmd: actuator_bus.thr_on_time_cmd.clone(),
+            cmg_rate_cmd: actuator_bus.cmg_rate_cmd.clone(),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TruthActuatorBus {
     pub net_forces: Vector3,
     pub net_torques: Vector3,
+    pub reaction_wheels: ReactionWheelSignal,
+    pub magnetorquers: MagnetorquerSignal,
+    pub thrusters: ThrusterSignal,
+    pub control_moment_gyros: ControlMomentGyroSignal,
+    rwa: ReactionWheelAssembly,
+    mtqa: MagnetorquerAssembly,
+    tha: ThrusterAssembly,
+    cmgs: ControlMomentGyroCluster,
+    ts: f64,
 }
 
 impl Default for TruthActuatorBus {
@@ -13,12 +157,105 @@ impl Default for TruthActuatorBus {
         Self {
             net_forces: array![0., 0., 0.].into_shape_with_order((3, 1)).unwrap(),
             net_torques: array![0., 0., 0.].into_shape_with_order((3, 1)).unwrap(),
+            reaction_wheels: ReactionWheelSignal::default(),
+            magnetorquers: MagnetorquerSignal::default(),
+            thrusters: ThrusterSignal::default(),
+            control_moment_gyros: ControlMomentGyroSignal::default(),
+            rwa: ReactionWheelAssembly::default(),
+            mtqa: MagnetorquerAssembly::default(),
+            tha: ThrusterAssembly::default(),
+            cmgs: ControlMomentGyroCluster::default(),
+            ts: 0.1,
         }
     }
 }
 
 impl TruthActuatorBus {
-    pub fn process(actuator_cmd: &ActuatorBus, prev_actuator: &Self) -> Self {
-        Self::default()
+    pub fn initialize(sc_ts: f64, actuator_params: SpacecraftActuatorArchitecture) -> Self {
+        let rwa = actuator_params.reaction_wheels;
+        let mtqa = actuator_params.magnetorquers;
+        let tha = actuator_params.thrusters;
+        let cmgs = actuator_params.control_moment_gyros;
+        Self {
+            reaction_wheels: ReactionWheelSignal::initialize(&rwa),
+            magnetorquers: MagnetorquerSignal::initialize(&mtqa),
+            thrusters: ThrusterSignal::initialize(&tha),
+            control_moment_gyros: ControlMomentGyroSignal::initialize(&cmgs),
+            rwa,
+            mtqa,
+            tha,
+            cmgs,
+            ts: sc_ts,
+            ..Default::default()
+        }
+    }
+
+    pub fn reaction_wheel_assembly(&self) -> &ReactionWheelAssembly {
+        &self.rwa
+    }
+
+    pub fn magnetorquer_assembly(&self) -> &MagnetorquerAssembly {
+        &self.mtqa
+    }
+
+    pub fn thruster_assembly(&self) -> &ThrusterAssembly {
+        &self.tha
+    }
+
+    pub fn control_moment_gyro_cluster(&self) -> &ControlMomentGyroCluster {
+        &self.cmgs
+    }
+
+    /// Vehicle mass properties less the propellant used so far.
+    pub fn mass_properties(&self, wet: &SpacecraftMassArchitecture) -> SpacecraftMassArchitecture {
+        self.tha.mass_properties(
+            wet,
+            self.tha.propellant_mass - self.thrusters.propellant_mass,
+        )
+    }
+
+    pub fn process(
+        &mut self,
+        actuator_cmd: &ActuatorCommands,
+        mass_props: &SpacecraftMassArchitecture,
+        attitude: &TruthAttitudeSignal,
+        ephemeris: &TruthEphemerisSignal,
+        prev_actuator: &Self,
+    ) {
+        self.rwa.clone_from(&prev_actuator.rwa);
+        self.mtqa.clone_from(&prev_actuator.mtqa);
+        self.tha.clone_from(&prev_actuator.tha);
+        self.cmgs.clone_from(&prev_actuator.cmgs);
+        self.ts = prev_actuator.ts;
+
+        // Wheel torques from the speeds at the start of the step, command held
+        self.reaction_wheels.step(
+            &self.rwa,
+            &actuator_cmd.rw_torque_cmd,
+            &attitude.wheel_speed,
+            self.ts,
+        );
+
+        // Dipoles held over the step against the field at its start
+        self.magnetorquers.step(&self.mtqa, &actuator_cmd.mtq_cmd);
+
+        // Thruster lag and propellant carry over from the previous step
+        self.thrusters.clone_from(&prev_actuator.thrusters);
+        self.thrusters
+            .step(&self.tha, &actuator_cmd.thr_on_time_cmd, self.ts);
+
+        // Gimbals follow the rate command through the rate loop
+        self.control_moment_gyros.step(
+            &self.cmgs,
+            &actuator_cmd.cmg_rate_cmd,
+            &prev_actuator.control_moment_gyros,
+            self.ts,
+        );
+
+        self.net_forces = self.thrusters.force_body(&self.tha);
+        self.net_torques = self.reaction_wheels.reaction_torque(&self.rwa)
+            + self.magnetorquers.torque(&ephemeris.b_body)
+            + self.thrusters.torque_body(&self.tha, &mass_props.r_cm_body)
+            + self.control_moment_gyros.output_torque();
     }
 }
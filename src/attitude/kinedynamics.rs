@@ -1,5 +1,5 @@
 use altai_rs as lib;
-use altai_rs::meta::types::{Generic1D, Generic2D};
+use altai_rs::meta::types::{Generic1D, Generic2D, Quaternion4};
 use altai_rs::quatlib::psi_q;
 use altai_rs::veclib::{fcross, fdot};
 use ndarray::{array, concatenate, s, Axis};
@@ -18,6 +18,65 @@ fn inv_3x3(j: &Generic2D) -> Generic2D {
     concatenate![Axis(1), ijx, ijy, ijz].t().to_owned() / detj[0]
 }
 
+pub fn quat_to_dcm(q: &Quaternion4) -> Generic2D {
+    // Attitude matrix (inertial -> body) of a scalar-last quaternion; Markley 2.125
+    let (q1, q2, q3, q4) = (q[[0, 0]], q[[1, 0]], q[[2, 0]], q[[3, 0]]);
+    array![
+        [
+            q1 * q1 - q2 * q2 - q3 * q3 + q4 * q4,
+            2. * (q1 * q2 + q3 * q4),
+            2. * (q1 * q3 - q2 * q4)
+        ],
+        [
+            2. * (q1 * q2 - q3 * q4),
+            -q1 * q1 + q2 * q2 - q3 * q3 + q4 * q4,
+            2. * (q2 * q3 + q1 * q4)
+        ],
+        [
+            2. * (q1 * q3 + q2 * q4),
+            2. * (q2 * q3 - q1 * q4),
+            -q1 * q1 - q2 * q2 + q3 * q3 + q4 * q4
+        ]
+    ]
+}
+
+pub fn quat_multiply(q: &Quaternion4, p: &Quaternion4) -> Quaternion4 {
+    // Scalar-last product with A(q * p) = A(q) A(p); Markley 2.82a
+    let (qv, q4) = ([q[[0, 0]], q[[1, 0]], q[[2, 0]]], q[[3, 0]]);
+    let (pv, p4) = ([p[[0, 0]], p[[1, 0]], p[[2, 0]]], p[[3, 0]]);
+    let qxp = [
+        qv[1] * pv[2] - qv[2] * pv[1],
+        qv[2] * pv[0] - qv[0] * pv[2],
+        qv[0] * pv[1] - qv[1] * pv[0],
+    ];
+    array![
+        [p4 * qv[0] + q4 * pv[0] - qxp[0]],
+        [p4 * qv[1] + q4 * pv[1] - qxp[1]],
+        [p4 * qv[2] + q4 * pv[2] - qxp[2]],
+        [q4 * p4 - qv[0] * pv[0] - qv[1] * pv[1] - qv[2] * pv[2]]
+    ]
+}
+
+pub fn sym_eigenvalues_3x3(j: &Generic2D) -> [f64; 3] {
+    // Closed-form eigenvalues of a real symmetric 3x3; Smith (1961)
+    // Returned in descending order
+    let p1 = j[[0, 1]].powi(2) + j[[0, 2]].powi(2) + j[[1, 2]].powi(2);
+    let q = (j[[0, 0]] + j[[1, 1]] + j[[2, 2]]) / 3.;
+    let p2 = (j[[0, 0]] - q).powi(2) + (j[[1, 1]] - q).powi(2) + (j[[2, 2]] - q).powi(2) + 2. * p1;
+    if p2 == 0. {
+        return [q, q, q];
+    }
+    let p = (p2 / 6.).sqrt();
+    let b = (j - q * Generic2D::eye(3)) / p;
+    let detb = b[[0, 0]] * (b[[1, 1]] * b[[2, 2]] - b[[1, 2]] * b[[2, 1]])
+        - b[[0, 1]] * (b[[1, 0]] * b[[2, 2]] - b[[1, 2]] * b[[2, 0]])
+        + b[[0, 2]] * (b[[1, 0]] * b[[2, 1]] - b[[1, 1]] * b[[2, 0]]);
+    let phi = (detb / 2.).clamp(-1., 1.).acos() / 3.;
+    let e1 = q + 2. * p * phi.cos();
+    let e3 = q + 2. * p * (phi + 2. * std::f64::consts::PI / 3.).cos();
+    [e1, 3. * q - e1 - e3, e3]
+}
+
 pub fn rigid_body_dynamics(_t: f64, state0: &Generic1D, inpt: &Generic2D) -> Generic1D {
     /*
     Inputs:
@@ -53,3 +112,64 @@ pub fn rigid_body_dynamics(_t: f64, state0: &Generic1D, inpt: &Generic2D) -> Gen
 
     concatenate![Axis(0), qdot, wdot]
 }
+
+pub fn gyrostat_dynamics(
+    _t: f64,
+    state0: &Generic1D,
+    inpt: &Generic2D,
+    spin_axes: &Generic2D,
+    wheel_inertia: &Generic1D,
+    axial_torque: &Generic1D,
+    h_internal: &Generic1D,
+) -> Generic1D {
+    /*
+    Rigid body carrying N axisymmetric rotors
+
+    Inputs:
+    0-3: Quaternion at Time
+    4-6: Angular Rate at Time
+    7-(6+N): Rotor speeds relative to the body at Time [rad/s]
+
+    inpt[0..3, 0]: Net torque on the body, rotor reactions included (Body) [N m]
+    inpt[0..3, 1..4]: Inertia tensor, rotors included (Body) [kg m2]
+    spin_axes: Rotor spin axes as columns (Body), 3xN [-]
+    wheel_inertia: Rotor spin inertias [kg m2]
+    axial_torque: Motor plus friction torque on each rotor [N m]
+    h_internal: Other internal momentum relative to the body, e.g. gimballed
+        rotors, whose exchange torque is in inpt (Body) [N m s]
+
+    Outputs:
+    0-3: dQuaternion at Time
+    4-6: dOmega at Time
+    7-(6+N): dRotor speeds at Time
+    */
+
+    let q = state0.slice(s![0..4]);
+    let w = state0.slice(s![4..7]);
+    let speed = state0.slice(s![7..]);
+    let tq = inpt.slice(s![0..3, 0]);
+    let j_mat = inpt.slice(s![0..3, 1..4]).to_owned();
+
+    // quaternion dot; Markley 3.79
+    let wquat = concatenate![Axis(0), w.to_owned(), array![0.]]
+        .into_shape_with_order((4, 1))
+        .unwrap();
+    let wpsi = psi_q(wquat.to_owned()).remove_axis(Axis(2));
+    let wcross = concatenate![Axis(1), wpsi, wquat];
+    let qdot = 0.5 * wcross.dot(&q);
+
+    // Inertia less the rotor spin inertias, and absolute rotor momenta
+    let j_s = &j_mat - &(spin_axes * wheel_inertia).dot(&spin_axes.t());
+    let h_axial = wheel_inertia * &(&speed + &spin_axes.t().dot(&w));
+
+    // angular rate dot; Euler's equation for a gyrostat
+    // wdot = inv(Js) * (T - w cross (Js * w + A * h))
+    let h_body = j_s.dot(&w) + spin_axes.dot(&h_axial) + h_internal;
+    let wxh = lib::veclib::mfcross(&w, &h_body.view());
+    let wdot = inv_3x3(&j_s).dot(&(-1. * wxh + tq));
+
+    // Rotor speed relative to the body
+    let speed_dot = axial_torque / wheel_inertia - spin_axes.t().dot(&wdot);
+
+    concatenate![Axis(0), qdot, wdot, speed_dot]
+}
@@ -1,17 +1,21 @@
-use std::f64::consts::PI;
-
 use crate::attitude::kinedynamics;
-use crate::ode::{self, RK2, RK5};
-use crate::sc_types::SpacecraftAttitudeArchitecture;
-use crate::{actuators::types::TruthActuatorBus, ode::Integrator};
+use crate::ode::{self, RK5};
+use crate::sc_types::{
+    SpacecraftActuatorArchitecture, SpacecraftAttitudeArchitecture, SpacecraftMassArchitecture,
+};
+use crate::{
+    actuators::types::TruthActuatorBus,
+    ode::{Integrator, IntegratorStats},
+};
 
-use altai_rs::meta::types::{Generic1D, Generic2D, Quaternion4, Vector3};
+use altai_rs::meta::types::{Generic1D, Quaternion4, Vector3};
 use ndarray::{array, concatenate, s, Axis};
 
 #[derive(Clone, Debug)]
 pub struct TruthAttitudeSignal {
     pub q_sc_eci: Quaternion4,
     pub omega_sc: Vector3,
+    pub wheel_speed: Generic1D, // Reaction wheel speeds relative to the body [rad/s]
 }
 impl Default for TruthAttitudeSignal {
     fn default() -> Self {
@@ -20,6 +24,7 @@ impl Default for TruthAttitudeSignal {
                 .into_shape_with_order((4, 1))
                 .unwrap(),
             omega_sc: (array![0., 0., 0.,]).into_shape_with_order((3, 1)).unwrap(),
+            wheel_speed: Generic1D::zeros(0),
         }
     }
 }
@@ -29,6 +34,7 @@ impl TruthAttitudeSignal {
         Self {
             q_sc_eci: q_sc_eci0,
             omega_sc: omega_sc0,
+            wheel_speed: Generic1D::zeros(0),
         }
     }
 
@@ -39,7 +45,8 @@ impl TruthAttitudeSignal {
                 .remove_axis(Axis(1))
                 .iter()
                 .chain(self.omega_sc.to_owned().remove_axis(Axis(1)).iter())
-                .map(|&x| x),
+                .chain(self.wheel_speed.iter())
+                .copied(),
         )
     }
 
@@ -50,58 +57,166 @@ impl TruthAttitudeSignal {
         state_vec
             .slice(s![4..7])
             .assign_to(self.omega_sc.slice_mut(s![0..3, 0]));
+        self.wheel_speed = state_vec.slice(s![7..]).to_owned();
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct TruthAttitudeBus {
     pub signal: TruthAttitudeSignal,
-    integrator: ode::RK5,
+    integrator: ode::Solver,
 }
 
 impl Default for TruthAttitudeBus {
     fn default() -> Self {
         Self {
             signal: TruthAttitudeSignal::default(),
-            integrator: RK5(0.1),
+            integrator: ode::Solver::RK5(RK5(0.1)),
         }
     }
 }
 
 impl TruthAttitudeBus {
-    pub fn initialize(SC_Ts: f64, attitude_params: SpacecraftAttitudeArchitecture) -> Self {
+    pub fn initialize(
+        sc_ts: f64,
+        attitude_params: SpacecraftAttitudeArchitecture,
+        actuator_params: &SpacecraftActuatorArchitecture,
+    ) -> Self {
         Self {
-            signal: TruthAttitudeSignal::initialize(
-                attitude_params.q_sc_eci,
-                attitude_params.omega_sc,
-            ),
-            integrator: ode::RK5(SC_Ts),
+            signal: TruthAttitudeSignal {
+                wheel_speed: actuator_params.reaction_wheels.initial_speed(),
+                ..TruthAttitudeSignal::initialize(
+                    attitude_params.q_sc_eci,
+                    attitude_params.omega_sc,
+                )
+            },
+            integrator: ode::Solver::initialize(&attitude_params.integrator, sc_ts),
         }
     }
-    pub fn process(&mut self, actuator_dynamics: &TruthActuatorBus, prev_attitude: &Self) {
-        self.propagate(actuator_dynamics, &prev_attitude.signal);
+
+    pub fn integrator_stats(&self) -> Option<IntegratorStats> {
+        self.integrator.stats()
+    }
+
+    pub fn process(
+        &mut self,
+        time: f64,
+        mass_props: &SpacecraftMassArchitecture,
+        actuator_dynamics: &TruthActuatorBus,
+        env_torques: &Vector3,
+        prev_attitude: &Self,
+    ) {
+        // Carry integrator step-size and statistics forward across the swap
+        self.integrator.clone_from(&prev_attitude.integrator);
+        self.propagate(
+            time,
+            mass_props,
+            actuator_dynamics,
+            env_torques,
+            &prev_attitude.signal,
+        );
     }
 
     fn propagate(
         &mut self,
+        time: f64,
+        mass_props: &SpacecraftMassArchitecture,
         actuator_dynamics: &TruthActuatorBus,
+        env_torques: &Vector3,
         prev_attitude: &TruthAttitudeSignal,
     ) {
         let state0 = prev_attitude.to_state_vector();
-        let Jsc =
-            Generic2D::from_shape_vec((3, 3), vec![10., 0., 0., 0., 20., 0., 0., 0., 30.]).unwrap();
-        let inpts = concatenate![Axis(1), actuator_dynamics.net_torques.to_owned(), Jsc];
-        let nstate =
+        let inpts = concatenate![
+            Axis(1),
+            &actuator_dynamics.net_torques + env_torques,
+            mass_props.j_sc.to_owned()
+        ];
+        let rwa = actuator_dynamics.reaction_wheel_assembly();
+        let cmgs = actuator_dynamics.control_moment_gyro_cluster();
+        let nstate = if rwa.is_empty() && cmgs.is_empty() {
             self.integrator
-                .integrate(&kinedynamics::rigid_body_dynamics, 0., &state0, &inpts);
+                .integrate(&kinedynamics::rigid_body_dynamics, time, &state0, &inpts)
+        } else {
+            // Rotor torques held over the step; gimballed momentum linear over it
+            let spin_axes = rwa.spin_axes();
+            let wheel_inertia = rwa.spin_inertia();
+            let axial_torque = actuator_dynamics.reaction_wheels.axial_torque();
+            let cmg = &actuator_dynamics.control_moment_gyros;
+            self.integrator.integrate(
+                &|t, state, inpt| {
+                    let h_cmg = cmg.momentum_at(t - time).remove_axis(Axis(1));
+                    kinedynamics::gyrostat_dynamics(
+                        t,
+                        state,
+                        inpt,
+                        &spin_axes,
+                        &wheel_inertia,
+                        &axial_torque,
+                        &h_cmg,
+                    )
+                },
+                time,
+                &state0,
+                &inpts,
+            )
+        };
         self.signal.from_state_vector(nstate);
     }
 }
 
-#[derive(Default, Clone, Debug)]
-pub struct TruthMultibodyBus {}
+/// Internal angular momentum carried by the rotors.
+#[derive(Clone, Debug)]
+pub struct TruthMultibodyBus {
+    pub h_wheels: Vector3,    // Rotor momentum relative to the body (Body) [N m s]
+    pub h_cmg: Vector3,       // Gimballed rotor momentum relative to the body (Body) [N m s]
+    pub h_total: Vector3,     // System angular momentum about the CoM (Body) [N m s]
+    pub h_total_eci: Vector3, // System angular momentum about the CoM (ECI) [N m s]
+}
+impl Default for TruthMultibodyBus {
+    fn default() -> Self {
+        Self {
+            h_wheels: Vector3::zeros((3, 1)),
+            h_cmg: Vector3::zeros((3, 1)),
+            h_total: Vector3::zeros((3, 1)),
+            h_total_eci: Vector3::zeros((3, 1)),
+        }
+    }
+}
 impl TruthMultibodyBus {
-    pub fn process(actuator_dynamics: &TruthActuatorBus, prev_multibody: &Self) -> Self {
-        Self::default()
+    pub fn initialize(
+        mass_props: &SpacecraftMassArchitecture,
+        actuator_dynamics: &TruthActuatorBus,
+        attitude: &TruthAttitudeSignal,
+    ) -> Self {
+        let mut bus = Self::default();
+        bus.update(mass_props, actuator_dynamics, attitude);
+        bus
+    }
+
+    pub fn process(
+        &mut self,
+        mass_props: &SpacecraftMassArchitecture,
+        actuator_dynamics: &TruthActuatorBus,
+        attitude: &TruthAttitudeSignal,
+    ) {
+        self.update(mass_props, actuator_dynamics, attitude);
+    }
+
+    fn update(
+        &mut self,
+        mass_props: &SpacecraftMassArchitecture,
+        actuator_dynamics: &TruthActuatorBus,
+        attitude: &TruthAttitudeSignal,
+    ) {
+        // H = J w + A Iw Omega + h_cmg, with J including the rotors
+        self.h_wheels = actuator_dynamics
+            .reaction_wheel_assembly()
+            .momentum_body(&attitude.wheel_speed);
+        self.h_cmg
+            .clone_from(&actuator_dynamics.control_moment_gyros.momentum_body);
+        self.h_total = mass_props.j_sc.dot(&attitude.omega_sc) + &self.h_wheels + &self.h_cmg;
+        self.h_total_eci = kinedynamics::quat_to_dcm(&attitude.q_sc_eci)
+            .t()
+            .dot(&self.h_total);
     }
 }
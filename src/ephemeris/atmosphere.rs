@@ -0,0 +1,289 @@
+use std::f64::consts::PI;
+use std::fmt::Debug;
+
+use ndarray::ArrayView1;
+
+use crate::ephemeris::celestial::sun_position_eci;
+use crate::ephemeris::consts::RE;
+use crate::ephemeris::types::ForceModelError;
+
+// Upper end of the Kp scale [-]
+const KP_MAX: f64 = 9.;
+
+/// Solar and geomagnetic activity inputs to the density models.
+#[derive(Debug, Clone)]
+pub struct SpaceWeather {
+    pub f107: f64,     // Daily 10.7 cm solar flux [sfu]
+    pub f107_avg: f64, // 81-day centered average 10.7 cm flux [sfu]
+    pub kp: f64,       // 3-hourly planetary geomagnetic index [-]
+}
+impl SpaceWeather {
+    pub fn validate(&self) -> Result<(), ForceModelError> {
+        ForceModelError::check_positive("space weather", "f107", self.f107)?;
+        ForceModelError::check_positive("space weather", "f107_avg", self.f107_avg)?;
+        ForceModelError::check_range("space weather", "kp", self.kp, 0., KP_MAX)
+    }
+}
+impl Default for SpaceWeather {
+    fn default() -> Self {
+        // Moderate solar activity
+        Self {
+            f107: 150.,
+            f107_avg: 150.,
+            kp: 3.,
+        }
+    }
+}
+
+pub trait AtmosphereModel: Debug + Send + Sync {
+    /// Neutral mass density [kg/m3] at an ECI position [m] and time [s past J2000].
+    fn density(&self, time: f64, r_eci: &ArrayView1<f64>, space_weather: &SpaceWeather) -> f64;
+}
+
+fn altitude(r_eci: &ArrayView1<f64>) -> f64 {
+    r_eci.dot(r_eci).sqrt() - RE
+}
+
+/// Piecewise exponential model; Vallado Table 8-4.
+#[derive(Debug, Clone, Default)]
+pub struct ExponentialAtmosphere;
+
+// Base altitude [km], nominal density [kg/m3], scale height [km]
+const EXPONENTIAL_TABLE: [(f64, f64, f64); 28] = [
+    (0., 1.225, 7.249),
+    (25., 3.899e-2, 6.349),
+    (30., 1.774e-2, 6.682),
+    (40., 3.972e-3, 7.554),
+    (50., 1.057e-3, 8.382),
+    (60., 3.206e-4, 7.714),
+    (70., 8.770e-5, 6.549),
+    (80., 1.905e-5, 5.799),
+    (90., 3.396e-6, 5.382),
+    (100., 5.297e-7, 5.877),
+    (110., 9.661e-8, 7.263),
+    (120., 2.438e-8, 9.473),
+    (130., 8.484e-9, 12.636),
+    (140., 3.845e-9, 16.149),
+    (150., 2.070e-9, 22.523),
+    (180., 5.464e-10, 29.740),
+    (200., 2.789e-10, 37.105),
+    (250., 7.248e-11, 45.546),
+    (300., 2.418e-11, 53.628),
+    (350., 9.518e-12, 53.298),
+    (400., 3.725e-12, 58.515),
+    (450., 1.585e-12, 60.828),
+    (500., 6.967e-13, 63.822),
+    (600., 1.454e-13, 71.835),
+    (700., 3.614e-14, 88.667),
+    (800., 1.170e-14, 124.64),
+    (900., 5.245e-15, 181.05),
+    (1000., 3.019e-15, 268.00),
+];
+
+impl AtmosphereModel for ExponentialAtmosphere {
+    fn density(&self, _time: f64, r_eci: &ArrayView1<f64>, _space_weather: &SpaceWeather) -> f64 {
+        let h_km = altitude(r_eci) / 1e3;
+        if h_km < 0. {
+            return EXPONENTIAL_TABLE[0].1;
+        }
+        let (h0, rho0, scale) = EXPONENTIAL_TABLE
+            .iter()
+            .rev()
+            .find(|(h0, _, _)| h_km >= *h0)
+            .copied()
+            .unwrap_or(EXPONENTIAL_TABLE[0]);
+        rho0 * (-(h_km - h0) / scale).exp()
+    }
+}
+
+/// Harris-Priester model with diurnal bulge; Montenbruck & Gill 3.5.2.
+/// Table values are for mean solar activity.
+#[derive(Debug, Clone)]
+pub struct HarrisPriesterAtmosphere {
+    // Exponent of the bulge cosine: 2 for polar, up to 6 for equatorial orbits
+    pub n_prm: f64,
+}
+impl Default for HarrisPriesterAtmosphere {
+    fn default() -> Self {
+        Self { n_prm: 6. }
+    }
+}
+
+// Altitude [km], minimum and maximum density [g/km3]
+const HARRIS_PRIESTER_TABLE: [(f64, f64, f64); 50] = [
+    (100., 497400.0, 497400.0),
+    (120., 24900.0, 24900.0),
+    (130., 8377.0, 8710.0),
+    (140., 3899.0, 4059.0),
+    (150., 2122.0, 2215.0),
+    (160., 1263.0, 1344.0),
+    (170., 800.8, 875.8),
+    (180., 528.3, 601.0),
+    (190., 361.7, 429.7),
+    (200., 255.7, 316.2),
+    (210., 183.9, 239.6),
+    (220., 134.1, 185.3),
+    (230., 99.49, 145.5),
+    (240., 74.88, 115.7),
+    (250., 57.09, 93.08),
+    (260., 44.03, 75.55),
+    (270., 34.30, 61.82),
+    (280., 26.97, 50.95),
+    (290., 21.39, 42.26),
+    (300., 17.08, 35.26),
+    (320., 10.99, 25.11),
+    (340., 7.214, 18.19),
+    (360., 4.824, 13.37),
+    (380., 3.274, 9.955),
+    (400., 2.249, 7.492),
+    (420., 1.558, 5.684),
+    (440., 1.091, 4.355),
+    (460., 0.7701, 3.362),
+    (480., 0.5474, 2.612),
+    (500., 0.3916, 2.042),
+    (520., 0.2819, 1.605),
+    (540., 0.2042, 1.267),
+    (560., 0.1488, 1.005),
+    (580., 0.1092, 0.7997),
+    (600., 0.08070, 0.6390),
+    (620., 0.06012, 0.5123),
+    (640., 0.04519, 0.4121),
+    (660., 0.03430, 0.3325),
+    (680., 0.02632, 0.2691),
+    (700., 0.02043, 0.2185),
+    (720., 0.01607, 0.1779),
+    (740., 0.01281, 0.1452),
+    (760., 0.01036, 0.1190),
+    (780., 0.008496, 0.09776),
+    (800., 0.007069, 0.08059),
+    (840., 0.004680, 0.05741),
+    (880., 0.003200, 0.04210),
+    (920., 0.002210, 0.03130),
+    (960., 0.001560, 0.02360),
+    (1000., 0.001150, 0.01810),
+];
+
+impl AtmosphereModel for HarrisPriesterAtmosphere {
+    fn density(&self, time: f64, r_eci: &ArrayView1<f64>, _space_weather: &SpaceWeather) -> f64 {
+        let h_km = altitude(r_eci) / 1e3;
+        let table = &HARRIS_PRIESTER_TABLE;
+        if h_km < table[0].0 || h_km >= table[table.len() - 1].0 {
+            return 0.;
+        }
+
+        // Bulge apex lags the Sun by 30 deg in right ascension
+        let r_sun = sun_position_eci(time);
+        let ra_sun = r_sun[1].atan2(r_sun[0]);
+        let dec_sun = (r_sun[2] / r_sun.dot(&r_sun).sqrt()).asin();
+        let ra_bulge = ra_sun + 30f64.to_radians();
+        let u_bulge = [
+            dec_sun.cos() * ra_bulge.cos(),
+            dec_sun.cos() * ra_bulge.sin(),
+            dec_sun.sin(),
+        ];
+        let rmag = r_eci.dot(r_eci).sqrt();
+        let cos_psi =
+            (r_eci[0] * u_bulge[0] + r_eci[1] * u_bulge[1] + r_eci[2] * u_bulge[2]) / rmag;
+        let c_psi2 = 0.5 + 0.5 * cos_psi;
+
+        // Exponential interpolation between bracketing table entries
+        let idx = table.iter().rposition(|row| h_km >= row.0).unwrap();
+        let (h0, min0, max0) = table[idx];
+        let (h1, min1, max1) = table[idx + 1];
+        let h_min = (h0 - h1) / (min1 / min0).ln();
+        let h_max = (h0 - h1) / (max1 / max0).ln();
+        let d_min = min0 * ((h0 - h_km) / h_min).exp();
+        let d_max = max0 * ((h0 - h_km) / h_max).exp();
+
+        // [g/km3] -> [kg/m3]
+        (d_min + (d_max - d_min) * c_psi2.powf(self.n_prm / 2.)) * 1e-12
+    }
+}
+
+/// Bates-profile thermosphere driven by the Jacchia (1971) exospheric
+/// temperature, with diffusive equilibrium of each constituent above 120 km.
+/// The 120 km boundary densities are fixed representative values, so only the
+/// temperature follows J71; the density is not the J71 tabulation.
+#[derive(Debug, Clone, Default)]
+pub struct BatesAtmosphere;
+
+const BATES_T120: f64 = 380.; // Boundary temperature at 120 km [K]
+const GAS_CONSTANT: f64 = 8.31446; // [J/(mol K)]
+const AVOGADRO: f64 = 6.02214076e23; // [1/mol]
+
+// Molar mass [kg/mol], number density at 120 km [1/m3], thermal diffusion factor
+const BATES_SPECIES: [(f64, f64, f64); 5] = [
+    (0.028013, 4.0e17, 0.),     // N2
+    (0.031999, 7.5e16, 0.),     // O2
+    (0.015999, 7.6e16, 0.),     // O
+    (0.039948, 5.0e15, 0.),     // Ar
+    (0.0040026, 3.4e13, -0.38), // He
+];
+
+impl BatesAtmosphere {
+    /// J71 exospheric temperature [K] with diurnal and geomagnetic terms.
+    pub fn exospheric_temperature(
+        &self,
+        time: f64,
+        r_eci: &ArrayView1<f64>,
+        space_weather: &SpaceWeather,
+    ) -> f64 {
+        // Nighttime minimum global exospheric temperature
+        let t_c = 379.
+            + 3.24 * space_weather.f107_avg
+            + 1.3 * (space_weather.f107 - space_weather.f107_avg);
+
+        // Diurnal variation
+        let r_sun = sun_position_eci(time);
+        let ra_sun = r_sun[1].atan2(r_sun[0]);
+        let dec_sun = (r_sun[2] / r_sun.dot(&r_sun).sqrt()).asin();
+        let rmag = r_eci.dot(r_eci).sqrt();
+        let lat = (r_eci[2] / rmag).asin();
+        let hour_angle = (r_eci[1].atan2(r_eci[0]) - ra_sun + PI).rem_euclid(2. * PI) - PI;
+
+        let eta = 0.5 * (lat - dec_sun).abs();
+        let theta = 0.5 * (lat + dec_sun).abs();
+        let tau = hour_angle - 37f64.to_radians()
+            + 6f64.to_radians() * (hour_angle + 43f64.to_radians()).sin();
+        let s_theta = theta.sin().powf(2.2);
+        let c_eta = eta.cos().powf(2.2);
+        let t_l =
+            t_c * (1. + 0.3 * (s_theta + (c_eta - s_theta) * (0.5 * tau).cos().abs().powi(3)));
+
+        // Geomagnetic heating
+        t_l + 28. * space_weather.kp + 0.03 * space_weather.kp.exp()
+    }
+}
+
+impl AtmosphereModel for BatesAtmosphere {
+    fn density(&self, time: f64, r_eci: &ArrayView1<f64>, space_weather: &SpaceWeather) -> f64 {
+        let h = altitude(r_eci);
+        if h < 120e3 {
+            // Below the diffusion boundary fall back on the standard atmosphere
+            return ExponentialAtmosphere.density(time, r_eci, space_weather);
+        }
+
+        let t_inf = self.exospheric_temperature(time, r_eci, space_weather);
+
+        // Bates profile shape parameter; Jacchia 1977
+        let x = (t_inf - 800.) / (750. + 1.722e-4 * (t_inf - 800.).powi(2));
+        let sigma = 0.0291e-3 * (-0.5 * x * x).exp();
+
+        // Geopotential distance above the boundary
+        let z120 = RE + 120e3;
+        let zeta = (h - 120e3) * z120 / (RE + h);
+        let t_z = t_inf - (t_inf - BATES_T120) * (-sigma * zeta).exp();
+        let g120 = 9.80665 * (RE / z120).powi(2);
+
+        BATES_SPECIES
+            .iter()
+            .map(|&(molar_mass, n120, alpha)| {
+                let gamma = molar_mass * g120 / (sigma * GAS_CONSTANT * t_inf);
+                let n = n120
+                    * (BATES_T120 / t_z).powf(1. + alpha + gamma)
+                    * (-sigma * gamma * zeta).exp();
+                n * molar_mass / AVOGADRO
+            })
+            .sum()
+    }
+}
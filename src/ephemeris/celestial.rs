@@ -0,0 +1,224 @@
+use std::f64::consts::PI;
+
+use altai_rs::meta::types::Generic1D;
+use ndarray::{array, ArrayView1};
+
+use crate::ephemeris::consts::RE;
+use crate::frames::rotations::{gcrf_to_mod, SEC_PER_CENTURY};
+
+// Astronomical unit [m]
+pub const AU: f64 = 149597870.7e3;
+
+// Gravitational parameters [m3/s2]
+pub const MU_SUN: f64 = 1.32712440018e20;
+pub const MU_MOON: f64 = 4.9048695e12;
+
+const ARCSEC_PER_RAD: f64 = 206264.806247;
+
+pub fn sun_position_eci(time: f64) -> Generic1D {
+    /*
+    Low-precision Sun position; Vallado Alg. 29, ~0.01 deg accuracy
+
+    Inputs:
+    time: Seconds past J2000
+
+    Outputs:
+    0-2: Sun R-vector (ECI, mean equator and equinox of J2000) [m]
+    */
+    let t = time / SEC_PER_CENTURY;
+    let lambda_m = (280.460 + 36000.771 * t).to_radians();
+    let m_sun = (357.5291092 + 35999.05034 * t).to_radians();
+    let lambda_ecl =
+        lambda_m + (1.914666471 * m_sun.sin() + 0.019994643 * (2. * m_sun).sin()).to_radians();
+    let rmag = AU * (1.000140612 - 0.016708617 * m_sun.cos() - 0.000139589 * (2. * m_sun).cos());
+    let eps = (23.439291 - 0.0130042 * t).to_radians();
+
+    // Alg. 29 is referred to the mean equinox of date; precess back to J2000
+    // to share the frame of the Moon and the dynamics
+    let r_mod = rmag
+        * array![
+            lambda_ecl.cos(),
+            eps.cos() * lambda_ecl.sin(),
+            eps.sin() * lambda_ecl.sin()
+        ];
+    gcrf_to_mod(t).t().dot(&r_mod)
+}
+
+pub fn moon_position_eci(time: f64) -> Generic1D {
+    /*
+    Low-precision Moon position; Montenbruck & Gill 3.3.2, ~several arcmin
+
+    Inputs:
+    time: Seconds past J2000
+
+    Outputs:
+    0-2: Moon R-vector (ECI, mean equator and equinox of J2000) [m]
+    */
+    let t = time / SEC_PER_CENTURY;
+    let frac = |x: f64| x.rem_euclid(1.);
+
+    // Mean arguments
+    let l0 = frac(0.606433 + 1336.851344 * t);
+    let l = 2. * PI * frac(0.374897 + 1325.552410 * t);
+    let lp = 2. * PI * frac(0.993133 + 99.997361 * t);
+    let d = 2. * PI * frac(0.827361 + 1236.853086 * t);
+    let f = 2. * PI * frac(0.259086 + 1342.227825 * t);
+
+    // Perturbations in longitude [arcsec]
+    let dl = 22640. * l.sin() - 4586. * (l - 2. * d).sin()
+        + 2370. * (2. * d).sin()
+        + 769. * (2. * l).sin()
+        - 668. * lp.sin()
+        - 412. * (2. * f).sin()
+        - 212. * (2. * l - 2. * d).sin()
+        - 206. * (l + lp - 2. * d).sin()
+        + 192. * (l + 2. * d).sin()
+        - 165. * (lp - 2. * d).sin()
+        - 125. * d.sin()
+        - 110. * (l + lp).sin()
+        + 148. * (l - lp).sin()
+        - 55. * (2. * f - 2. * d).sin();
+
+    // Ecliptic longitude, latitude and distance
+    let lon = 2. * PI * frac(l0 + dl / 1296.0e3);
+    let s = f + (dl + 412. * (2. * f).sin() + 541. * lp.sin()) / ARCSEC_PER_RAD;
+    let h = f - 2. * d;
+    let n = -526. * h.sin() + 44. * (l + h).sin() - 31. * (-l + h).sin() - 23. * (lp + h).sin()
+        + 11. * (-lp + h).sin()
+        - 25. * (-2. * l + f).sin()
+        + 21. * (-l + f).sin();
+    let lat = (18520. * s.sin() + n) / ARCSEC_PER_RAD;
+    let rmag = 1e3
+        * (385000.
+            - 20905. * l.cos()
+            - 3699. * (2. * d - l).cos()
+            - 2956. * (2. * d).cos()
+            - 570. * (2. * l).cos()
+            + 246. * (2. * l - 2. * d).cos()
+            - 205. * (lp - 2. * d).cos()
+            - 171. * (l + 2. * d).cos()
+            - 152. * (l + lp - 2. * d).cos());
+
+    // Ecliptic -> equatorial
+    let eps = 23.43929111f64.to_radians();
+    let (x_ecl, y_ecl, z_ecl) = (
+        rmag * lon.cos() * lat.cos(),
+        rmag * lon.sin() * lat.cos(),
+        rmag * lat.sin(),
+    );
+    array![
+        x_ecl,
+        eps.cos() * y_ecl - eps.sin() * z_ecl,
+        eps.sin() * y_ecl + eps.cos() * z_ecl
+    ]
+}
+
+pub fn third_body_acceleration(
+    rsc: &ArrayView1<f64>,
+    r_body: &ArrayView1<f64>,
+    mu_body: f64,
+) -> Generic1D {
+    /*
+    Third-body perturbation in Battin's form, avoiding the cancellation of
+    the direct and indirect terms; Battin Eq. 8.60-8.61
+
+    Inputs:
+    rsc: Spacecraft R-vector (ECI) [m]
+    r_body: Perturbing body R-vector (ECI) [m]
+    mu_body: Perturbing body gravitational parameter [m3/s2]
+
+    Outputs:
+    0-2: Perturbing A-Vector (ECI) [m/s2]
+    */
+    let d = rsc.to_owned() - r_body;
+    let dmag = d.dot(&d).sqrt();
+    let q = rsc.dot(&(rsc.to_owned() - 2. * r_body.to_owned())) / r_body.dot(r_body);
+    let f_q = q * (3. + 3. * q + q * q) / (1. + (1. + q).powf(1.5));
+    -mu_body / dmag.powi(3) * (rsc.to_owned() + f_q * r_body.to_owned())
+}
+
+// Solar radius [m]
+pub const R_SUN: f64 = 696000e3;
+
+/// Earth shadow geometry used for eclipse state.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ShadowModel {
+    Cylindrical,
+    #[default]
+    Conical,
+}
+
+pub fn shadow_fraction(model: ShadowModel, r_sc: &ArrayView1<f64>, r_sun: &ArrayView1<f64>) -> f64 {
+    /*
+    Fraction of the solar disk visible from the spacecraft
+
+    Inputs:
+    r_sc: Spacecraft R-vector (ECI) [m]
+    r_sun: Sun R-vector (ECI) [m]
+
+    Outputs:
+    1: Full sunlight, 0: Umbra, (0, 1): Penumbra / annular
+    */
+    match model {
+        ShadowModel::Cylindrical => {
+            let sun_hat = r_sun.to_owned() / r_sun.dot(r_sun).sqrt();
+            let proj = r_sc.dot(&sun_hat);
+            let perp = r_sc.to_owned() - proj * &sun_hat;
+            if proj < 0. && perp.dot(&perp).sqrt() < RE {
+                0.
+            } else {
+                1.
+            }
+        }
+        ShadowModel::Conical => {
+            // Montenbruck & Gill 3.4.2
+            let d_sun = r_sun.to_owned() - r_sc;
+            let rmag = r_sc.dot(r_sc).sqrt();
+            let dmag = d_sun.dot(&d_sun).sqrt();
+            if rmag <= RE {
+                return 0.;
+            }
+            let a = (R_SUN / dmag).asin(); // Apparent solar radius
+            let b = (RE / rmag).asin(); // Apparent Earth radius
+            let c = (-r_sc.dot(&d_sun) / (rmag * dmag)).clamp(-1., 1.).acos();
+
+            if c >= a + b {
+                1.
+            } else if c < b - a {
+                0.
+            } else if c < a - b {
+                // Annular: Earth disk inside the solar disk
+                1. - (b * b) / (a * a)
+            } else {
+                let x = (c * c + a * a - b * b) / (2. * c);
+                let y = (a * a - x * x).max(0.).sqrt();
+                let area = a * a * (x / a).clamp(-1., 1.).acos()
+                    + b * b * ((c - x) / b).clamp(-1., 1.).acos()
+                    - c * y;
+                1. - area / (PI * a * a)
+            }
+        }
+    }
+}
+
+// Earth Bond albedo [-]
+pub const EARTH_ALBEDO: f64 = 0.3;
+
+pub fn albedo_fraction(r_sc: &ArrayView1<f64>, r_sun: &ArrayView1<f64>) -> f64 {
+    /*
+    Earth-reflected irradiance relative to direct sunlight, with the Earth as
+    a Lambert sphere of geometric albedo 2A/3 seen along the nadir
+
+    Inputs:
+    r_sc: Spacecraft R-vector (ECI) [m]
+    r_sun: Sun R-vector (ECI) [m]
+
+    Outputs:
+    Albedo irradiance / solar irradiance [-]
+    */
+    let rmag = r_sc.dot(r_sc).sqrt();
+    let cos_phase = r_sc.dot(r_sun) / (rmag * r_sun.dot(r_sun).sqrt());
+    let phase = cos_phase.clamp(-1., 1.).acos(); // Sun-Earth-spacecraft angle
+    let phase_law = (phase.sin() + (PI - phase) * phase.cos()) / PI;
+    2. / 3. * EARTH_ALBEDO * (RE / rmag).min(1.).powi(2) * phase_law
+}
@@ -1,2 +1,16 @@
 pub const RE: f64 = 6378.1370e3;
 pub const MU: f64 = 3.986004e14;
+
+// Earth rotation rate [rad/s]
+pub const OMEGA_E: f64 = 7.292115e-5;
+
+// Unnormalized zonal harmonics; EGM96
+pub const J2: f64 = 1.08262668e-3;
+pub const J3: f64 = -2.53265649e-6;
+pub const J4: f64 = -1.61962159e-6;
+pub const J5: f64 = -2.27296082e-7;
+pub const J6: f64 = 5.40681239e-7;
+pub const JN: [f64; 7] = [0., 0., J2, J3, J4, J5, J6];
+
+// Mean motion of the Earth about the Sun; required nodal rate for SSO [rad/s]
+pub const OMEGA_SSO: f64 = 2. * std::f64::consts::PI / (365.2421897 * 86400.);
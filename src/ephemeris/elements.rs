@@ -0,0 +1,330 @@
+use std::f64::consts::PI;
+
+use altai_rs::meta::types::{Generic1D, Vector3};
+use altai_rs::veclib::mfcross;
+use ndarray::{array, ArrayView1};
+
+// Eccentricity/inclination below which the orbit is treated as circular/equatorial
+pub const ELEMENT_TOL: f64 = 1e-11;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassicalElements {
+    pub a: f64,    // Semi-major axis [m]
+    pub e: f64,    // Eccentricity [-]
+    pub i: f64,    // Inclination [rad]
+    pub raan: f64, // Right ascension of the ascending node [rad]
+    pub argp: f64, // Argument of periapsis [rad]
+    pub nu: f64,   // True anomaly [rad]
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EquinoctialElements {
+    pub a: f64,      // Semi-major axis [m]
+    pub h: f64,      // e * sin(argp + raan) [-]
+    pub k: f64,      // e * cos(argp + raan) [-]
+    pub p: f64,      // tan(i/2) * sin(raan) [-]
+    pub q: f64,      // tan(i/2) * cos(raan) [-]
+    pub lambda: f64, // Mean longitude [rad]
+}
+
+/// Modified equinoctial elements; Walker et al. (1985). Defined for all
+/// conics, singular only for retrograde equatorial orbits.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModifiedEquinoctialElements {
+    pub p: f64, // Semi-latus rectum [m]
+    pub f: f64, // e * cos(argp + raan) [-]
+    pub g: f64, // e * sin(argp + raan) [-]
+    pub h: f64, // tan(i/2) * cos(raan) [-]
+    pub k: f64, // tan(i/2) * sin(raan) [-]
+    pub l: f64, // True longitude [rad]
+}
+
+pub fn wrap_2pi(angle: f64) -> f64 {
+    angle.rem_euclid(2. * PI)
+}
+
+pub fn mean_to_eccentric_anomaly(m: f64, e: f64) -> f64 {
+    // Newton iteration on Kepler's equation; Vallado Alg. 2
+    let m = wrap_2pi(m);
+    let mut ea = if e < 0.8 { m } else { PI };
+    for _ in 0..50 {
+        let d_ea = (ea - e * ea.sin() - m) / (1. - e * ea.cos());
+        ea -= d_ea;
+        if d_ea.abs() < 1e-14 {
+            break;
+        }
+    }
+    ea
+}
+
+pub fn mean_to_hyperbolic_anomaly(m: f64, e: f64) -> f64 {
+    // Newton iteration on M = e sinh(H) - H; Vallado Alg. 4
+    let mut ha = m.signum() * (2. * m.abs() / e + 1.8).ln();
+    for _ in 0..50 {
+        let d_ha = (e * ha.sinh() - ha - m) / (e * ha.cosh() - 1.);
+        ha -= d_ha;
+        if d_ha.abs() < 1e-14 {
+            break;
+        }
+    }
+    ha
+}
+
+pub fn mean_to_true_anomaly(m: f64, e: f64) -> f64 {
+    // Hyperbolic mean anomaly is unbounded; true anomaly returned in (-pi, pi)
+    if e > 1. {
+        let ha = mean_to_hyperbolic_anomaly(m, e);
+        return 2. * (((e + 1.) / (e - 1.)).sqrt() * (ha / 2.).tanh()).atan();
+    }
+    let ea = mean_to_eccentric_anomaly(m, e);
+    wrap_2pi(2. * ((1. + e).sqrt() * (ea / 2.).sin()).atan2((1. - e).sqrt() * (ea / 2.).cos()))
+}
+
+pub fn true_to_mean_anomaly(nu: f64, e: f64) -> f64 {
+    if e > 1. {
+        let ha = 2. * (((e - 1.) / (e + 1.)).sqrt() * (nu / 2.).tan()).atanh();
+        return e * ha.sinh() - ha;
+    }
+    let ea = 2. * ((1. - e).sqrt() * (nu / 2.).sin()).atan2((1. + e).sqrt() * (nu / 2.).cos());
+    wrap_2pi(ea - e * ea.sin())
+}
+
+impl ClassicalElements {
+    pub fn from_cartesian(r_eci: &ArrayView1<f64>, v_eci: &ArrayView1<f64>, mu: f64) -> Self {
+        /*
+        Osculating elements; Vallado Alg. 9 with atan2 quadrant checks
+
+        Undefined angles are set to zero and absorbed into the next angle:
+        circular inclined: argp = 0, nu = argument of latitude
+        elliptic equatorial: raan = 0, argp = longitude of periapsis
+        circular equatorial: raan = argp = 0, nu = true longitude
+        Parabolic orbits have no finite semi-major axis (a = inf).
+        */
+        let rmag = r_eci.dot(r_eci).sqrt();
+        let vmag2 = v_eci.dot(v_eci);
+        let h_vec = mfcross(r_eci, v_eci);
+        let hmag = h_vec.dot(&h_vec).sqrt();
+        let h_hat = &h_vec / hmag;
+        let n_vec = array![-h_vec[1], h_vec[0], 0.];
+        let e_vec =
+            ((vmag2 - mu / rmag) * &r_eci.to_owned() - r_eci.dot(v_eci) * &v_eci.to_owned()) / mu;
+        let e = e_vec.dot(&e_vec).sqrt();
+        let a = -mu / (vmag2 - 2. * mu / rmag);
+        let i = (h_vec[2] / hmag).clamp(-1., 1.).acos();
+
+        // Signed angle from `from` to `to` about the orbit normal
+        let angle = |from: &Generic1D, to: &Generic1D| {
+            wrap_2pi(
+                h_hat
+                    .dot(&mfcross(&from.view(), &to.view()))
+                    .atan2(from.dot(to)),
+            )
+        };
+        let r_vec = r_eci.to_owned();
+        let circular = e < ELEMENT_TOL;
+        let equatorial = i < ELEMENT_TOL || PI - i < ELEMENT_TOL;
+
+        // Line of nodes replaced by the x-axis for equatorial orbits
+        let (raan, node) = if equatorial {
+            (0., array![1., 0., 0.])
+        } else {
+            (wrap_2pi(h_vec[0].atan2(-h_vec[1])), n_vec)
+        };
+        let (argp, nu) = if circular {
+            (0., angle(&node, &r_vec))
+        } else {
+            (angle(&node, &e_vec), angle(&e_vec, &r_vec))
+        };
+        Self {
+            a,
+            e,
+            i,
+            raan,
+            argp,
+            nu,
+        }
+    }
+
+    pub fn mean_anomaly(&self) -> f64 {
+        true_to_mean_anomaly(self.nu, self.e)
+    }
+
+    pub fn to_cartesian(&self, mu: f64) -> (Vector3, Vector3) {
+        // Perifocal state; Vallado Alg. 10
+        let slr = self.a * (1. - self.e.powi(2));
+        let rmag = slr / (1. + self.e * self.nu.cos());
+        let vfac = (mu / slr).sqrt();
+        let r_pqw = [rmag * self.nu.cos(), rmag * self.nu.sin()];
+        let v_pqw = [-vfac * self.nu.sin(), vfac * (self.e + self.nu.cos())];
+
+        // Perifocal -> ECI: R3(-raan) R1(-i) R3(-argp)
+        let (so, co) = self.raan.sin_cos();
+        let (si, ci) = self.i.sin_cos();
+        let (sw, cw) = self.argp.sin_cos();
+        let p_hat = [co * cw - so * sw * ci, so * cw + co * sw * ci, sw * si];
+        let q_hat = [-co * sw - so * cw * ci, -so * sw + co * cw * ci, cw * si];
+
+        let r_eci = array![
+            [r_pqw[0] * p_hat[0] + r_pqw[1] * q_hat[0]],
+            [r_pqw[0] * p_hat[1] + r_pqw[1] * q_hat[1]],
+            [r_pqw[0] * p_hat[2] + r_pqw[1] * q_hat[2]]
+        ];
+        let v_eci = array![
+            [v_pqw[0] * p_hat[0] + v_pqw[1] * q_hat[0]],
+            [v_pqw[0] * p_hat[1] + v_pqw[1] * q_hat[1]],
+            [v_pqw[0] * p_hat[2] + v_pqw[1] * q_hat[2]]
+        ];
+        (r_eci, v_eci)
+    }
+}
+
+impl EquinoctialElements {
+    pub fn from_classical(coe: &ClassicalElements) -> Self {
+        let lon_peri = coe.raan + coe.argp;
+        let tan_half_i = (coe.i / 2.).tan();
+        let lambda = lon_peri + coe.mean_anomaly();
+        Self {
+            a: coe.a,
+            h: coe.e * lon_peri.sin(),
+            k: coe.e * lon_peri.cos(),
+            p: tan_half_i * coe.raan.sin(),
+            q: tan_half_i * coe.raan.cos(),
+            lambda: if coe.e < 1. { wrap_2pi(lambda) } else { lambda },
+        }
+    }
+
+    pub fn from_cartesian(r_eci: &ArrayView1<f64>, v_eci: &ArrayView1<f64>, mu: f64) -> Self {
+        // Via modified elements to stay regular for circular and equatorial orbits
+        let mee = ModifiedEquinoctialElements::from_cartesian(r_eci, v_eci, mu);
+        let e2 = mee.f.powi(2) + mee.g.powi(2);
+        let lon_peri = mee.g.atan2(mee.f);
+        let m = true_to_mean_anomaly(mee.l - lon_peri, e2.sqrt());
+        let lambda = lon_peri + m;
+        Self {
+            a: mee.p / (1. - e2),
+            h: mee.g,
+            k: mee.f,
+            p: mee.k,
+            q: mee.h,
+            lambda: if e2 < 1. { wrap_2pi(lambda) } else { lambda },
+        }
+    }
+
+    pub fn to_cartesian(&self, mu: f64) -> (Vector3, Vector3) {
+        let e2 = self.h.powi(2) + self.k.powi(2);
+        let lon_peri = self.h.atan2(self.k);
+        let nu = mean_to_true_anomaly(self.lambda - lon_peri, e2.sqrt());
+        ModifiedEquinoctialElements {
+            p: self.a * (1. - e2),
+            f: self.k,
+            g: self.h,
+            h: self.q,
+            k: self.p,
+            l: wrap_2pi(lon_peri + nu),
+        }
+        .to_cartesian(mu)
+    }
+
+    pub fn to_classical(&self) -> ClassicalElements {
+        let e = self.h.hypot(self.k);
+        let tan_half_i = self.p.hypot(self.q);
+        let raan = wrap_2pi(self.p.atan2(self.q));
+        let lon_peri = self.h.atan2(self.k);
+        let m = self.lambda - lon_peri;
+        ClassicalElements {
+            a: self.a,
+            e,
+            i: 2. * tan_half_i.atan(),
+            raan,
+            argp: wrap_2pi(lon_peri - raan),
+            nu: mean_to_true_anomaly(m, e),
+        }
+    }
+}
+
+impl ModifiedEquinoctialElements {
+    pub fn from_classical(coe: &ClassicalElements) -> Self {
+        let lon_peri = coe.raan + coe.argp;
+        let tan_half_i = (coe.i / 2.).tan();
+        Self {
+            p: coe.a * (1. - coe.e.powi(2)),
+            f: coe.e * lon_peri.cos(),
+            g: coe.e * lon_peri.sin(),
+            h: tan_half_i * coe.raan.cos(),
+            k: tan_half_i * coe.raan.sin(),
+            l: wrap_2pi(lon_peri + coe.nu),
+        }
+    }
+
+    pub fn to_classical(&self) -> ClassicalElements {
+        // Undefined angles follow the conventions of `ClassicalElements::from_cartesian`
+        let e = self.f.hypot(self.g);
+        let tan_half_i = self.h.hypot(self.k);
+        let raan = if tan_half_i < ELEMENT_TOL {
+            0.
+        } else {
+            wrap_2pi(self.k.atan2(self.h))
+        };
+        let lon_peri = if e < ELEMENT_TOL {
+            raan
+        } else {
+            self.g.atan2(self.f)
+        };
+        ClassicalElements {
+            a: self.p / (1. - e * e),
+            e,
+            i: 2. * tan_half_i.atan(),
+            raan,
+            argp: wrap_2pi(lon_peri - raan),
+            nu: wrap_2pi(self.l - lon_peri),
+        }
+    }
+
+    pub fn from_cartesian(r_eci: &ArrayView1<f64>, v_eci: &ArrayView1<f64>, mu: f64) -> Self {
+        // Equinoctial frame (f, g, w); Walker et al. (1985)
+        let rmag = r_eci.dot(r_eci).sqrt();
+        let h_vec = mfcross(r_eci, v_eci);
+        let hmag = h_vec.dot(&h_vec).sqrt();
+        let w_hat = &h_vec / hmag;
+        let h = -w_hat[1] / (1. + w_hat[2]);
+        let k = w_hat[0] / (1. + w_hat[2]);
+
+        let s2 = 1. + h * h + k * k;
+        let f_hat = array![1. - k * k + h * h, 2. * h * k, -2. * k] / s2;
+        let g_hat = array![2. * h * k, 1. + k * k - h * h, 2. * h] / s2;
+        let e_vec = mfcross(v_eci, &h_vec.view()) / mu - &r_eci.to_owned() / rmag;
+
+        Self {
+            p: hmag * hmag / mu,
+            f: e_vec.dot(&f_hat),
+            g: e_vec.dot(&g_hat),
+            h,
+            k,
+            l: wrap_2pi(r_eci.dot(&g_hat).atan2(r_eci.dot(&f_hat))),
+        }
+    }
+
+    pub fn to_cartesian(&self, mu: f64) -> (Vector3, Vector3) {
+        // Walker et al. (1985) Eq. 3
+        let (sl, cl) = self.l.sin_cos();
+        let alpha2 = self.h * self.h - self.k * self.k;
+        let s2 = 1. + self.h * self.h + self.k * self.k;
+        let hk = self.h * self.k;
+        let rmag = self.p / (1. + self.f * cl + self.g * sl);
+        let vfac = (mu / self.p).sqrt() / s2;
+
+        let r_eci = array![
+            [rmag / s2 * (cl + alpha2 * cl + 2. * hk * sl)],
+            [rmag / s2 * (sl - alpha2 * sl + 2. * hk * cl)],
+            [rmag / s2 * 2. * (self.h * sl - self.k * cl)]
+        ];
+        let v_eci = array![
+            [-vfac
+                * (sl + alpha2 * sl - 2. * hk * cl + self.g - 2. * self.f * hk + alpha2 * self.g)],
+            [-vfac
+                * (-cl + alpha2 * cl + 2. * hk * sl - self.f + 2. * self.g * hk + alpha2 * self.f)],
+            [2. * vfac * (self.h * cl + self.k * sl + self.f * self.h + self.g * self.k)]
+        ];
+        (r_eci, v_eci)
+    }
+}
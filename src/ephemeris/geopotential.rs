@@ -0,0 +1,372 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use altai_rs::meta::types::{Generic1D, Generic2D};
+use ndarray::{array, ArrayView1};
+
+#[derive(Debug)]
+pub enum GravityFieldError {
+    Io(std::io::Error),
+    MissingHeader(&'static str),
+    Parse { line: usize, msg: String },
+}
+impl fmt::Display for GravityFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read gravity field: {err}"),
+            Self::MissingHeader(key) => write!(f, "gravity field header is missing `{key}`"),
+            Self::Parse { line, msg } => write!(f, "gravity field line {line}: {msg}"),
+        }
+    }
+}
+impl std::error::Error for GravityFieldError {}
+impl From<std::io::Error> for GravityFieldError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Spherical-harmonic geopotential, stored as fully normalized Cnm/Snm.
+#[derive(Debug, Clone)]
+pub struct GravityField {
+    pub gm: f64,     // Gravitational parameter [m3/s2]
+    pub radius: f64, // Reference radius [m]
+    pub max_degree: usize,
+    cnm: Generic2D, // cnm[[n, m]]
+    snm: Generic2D, // snm[[n, m]]
+}
+
+fn parse_f64(token: &str, line: usize) -> Result<f64, GravityFieldError> {
+    // Fortran-style exponents appear in older EGM distributions
+    token
+        .replace(['D', 'd'], "E")
+        .parse::<f64>()
+        .map_err(|err| GravityFieldError::Parse {
+            line,
+            msg: format!("`{token}`: {err}"),
+        })
+}
+
+fn parse_usize(token: &str, line: usize) -> Result<usize, GravityFieldError> {
+    token
+        .parse::<usize>()
+        .map_err(|err| GravityFieldError::Parse {
+            line,
+            msg: format!("`{token}`: {err}"),
+        })
+}
+
+fn normalization(n: usize, m: usize) -> f64 {
+    // Cnm = N * Cbar_nm; N = sqrt((2 - d0m)(2n + 1)(n - m)! / (n + m)!)
+    // Only used to read unnormalized files
+    // Ratio of factorials formed in log space to avoid overflow
+    let log_ratio: f64 = ((n - m + 1)..=(n + m)).map(|k| (k as f64).ln()).sum();
+    let delta = if m == 0 { 1. } else { 2. };
+    (0.5 * ((delta * (2 * n + 1) as f64).ln() - log_ratio)).exp()
+}
+
+impl GravityField {
+    pub fn from_icgem_file<P: AsRef<Path>>(
+        path: P,
+        max_degree: usize,
+    ) -> Result<Self, GravityFieldError> {
+        Self::from_icgem_str(&fs::read_to_string(path)?, max_degree)
+    }
+
+    pub fn from_egm_file<P: AsRef<Path>>(
+        path: P,
+        gm: f64,
+        radius: f64,
+        max_degree: usize,
+    ) -> Result<Self, GravityFieldError> {
+        Self::from_egm_str(&fs::read_to_string(path)?, gm, radius, max_degree)
+    }
+
+    /// ICGEM format: `key value` header closed by `end_of_head`, then
+    /// `gfc n m C S [sigma_C sigma_S]` records.
+    pub fn from_icgem_str(text: &str, max_degree: usize) -> Result<Self, GravityFieldError> {
+        let mut gm = None;
+        let mut radius = None;
+        let mut normalized = true;
+        let mut lines = text.lines().enumerate();
+
+        for (idx, line) in lines.by_ref() {
+            let mut tokens = line.split_whitespace();
+            match (tokens.next(), tokens.next()) {
+                (Some("end_of_head"), _) => break,
+                (Some("earth_gravity_constant"), Some(val)) => gm = Some(parse_f64(val, idx + 1)?),
+                (Some("radius"), Some(val)) => radius = Some(parse_f64(val, idx + 1)?),
+                (Some("norm"), Some(val)) => normalized = val != "unnormalized",
+                _ => {}
+            }
+        }
+        let gm = gm.ok_or(GravityFieldError::MissingHeader("earth_gravity_constant"))?;
+        let radius = radius.ok_or(GravityFieldError::MissingHeader("radius"))?;
+
+        let mut field = Self::empty(gm, radius, max_degree);
+        for (idx, line) in lines {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            // Time-variable terms (trnd/acos/asin) are not modelled
+            if !tokens
+                .first()
+                .is_some_and(|&key| key == "gfc" || key == "gfct")
+            {
+                continue;
+            }
+            field.set_record(&tokens[1..], idx + 1, normalized)?;
+        }
+        Ok(field)
+    }
+
+    /// EGM format: headerless, normalized `n m C S [sigma_C sigma_S]` records.
+    pub fn from_egm_str(
+        text: &str,
+        gm: f64,
+        radius: f64,
+        max_degree: usize,
+    ) -> Result<Self, GravityFieldError> {
+        let mut field = Self::empty(gm, radius, max_degree);
+        for (idx, line) in text.lines().enumerate() {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            if tokens.is_empty() {
+                continue;
+            }
+            field.set_record(&tokens, idx + 1, true)?;
+        }
+        Ok(field)
+    }
+
+    fn empty(gm: f64, radius: f64, max_degree: usize) -> Self {
+        let mut cnm = Generic2D::zeros((max_degree + 1, max_degree + 1));
+        cnm[[0, 0]] = 1.;
+        Self {
+            gm,
+            radius,
+            max_degree,
+            cnm,
+            snm: Generic2D::zeros((max_degree + 1, max_degree + 1)),
+        }
+    }
+
+    fn set_record(
+        &mut self,
+        tokens: &[&str],
+        line: usize,
+        normalized: bool,
+    ) -> Result<(), GravityFieldError> {
+        if tokens.len() < 4 {
+            return Err(GravityFieldError::Parse {
+                line,
+                msg: "expected `n m C S`".to_string(),
+            });
+        }
+        let n = parse_usize(tokens[0], line)?;
+        let m = parse_usize(tokens[1], line)?;
+        if m > n {
+            return Err(GravityFieldError::Parse {
+                line,
+                msg: format!("order {m} exceeds degree {n}"),
+            });
+        }
+        if n > self.max_degree {
+            return Ok(());
+        }
+        let scale = if normalized {
+            1.
+        } else {
+            1. / normalization(n, m)
+        };
+        self.cnm[[n, m]] = scale * parse_f64(tokens[2], line)?;
+        self.snm[[n, m]] = scale * parse_f64(tokens[3], line)?;
+        Ok(())
+    }
+
+    /// Fully normalized coefficients.
+    pub fn cnm(&self, n: usize, m: usize) -> f64 {
+        self.cnm[[n, m]]
+    }
+
+    pub fn snm(&self, n: usize, m: usize) -> f64 {
+        self.snm[[n, m]]
+    }
+
+    /// Gravitational potential (ECEF) [m2/s2], positive outward-decreasing
+    /// (V = GM / r for a point mass).
+    pub fn potential(
+        &self,
+        r_ecef: &ArrayView1<f64>,
+        degree: usize,
+        order: usize,
+        include_central: bool,
+    ) -> f64 {
+        let nmax = degree.min(self.max_degree);
+        let nmin = if include_central { 0 } else { 1 };
+        let sum = potential_sum(
+            &self.cnm,
+            &self.snm,
+            self.radius,
+            r_ecef,
+            nmax,
+            order.min(nmax),
+            nmin,
+        );
+        self.gm / self.radius * sum
+    }
+
+    pub fn acceleration(
+        &self,
+        r_ecef: &ArrayView1<f64>,
+        degree: usize,
+        order: usize,
+        include_central: bool,
+    ) -> Generic1D {
+        /*
+        Geopotential acceleration from fully normalized coefficients;
+        Holmes & Featherstone (2002) Legendre recursion
+
+        Inputs:
+        r_ecef: R-vector (ECEF) [m]
+        degree, order: Truncation, limited to the loaded field
+        include_central: Keep the n = 0 point-mass term
+
+        Outputs:
+        0-2: A-Vector (ECEF) [m/s2]
+        */
+        let nmax = degree.min(self.max_degree);
+        let nmin = if include_central { 0 } else { 1 };
+        let grad = potential_gradient(
+            &self.cnm,
+            &self.snm,
+            self.radius,
+            r_ecef,
+            nmax,
+            order.min(nmax),
+            nmin,
+        );
+        self.gm / (self.radius * self.radius) * grad
+    }
+}
+
+/// Fully normalized associated Legendre functions divided by cos^m(phi),
+/// Pbar_nm(sin phi) / cos^m(phi), for n <= nmax and m <= min(n, mmax).
+/// Holmes & Featherstone (2002) modified forward column recursion; dividing
+/// out cos^m keeps the recursion finite and accurate up to the poles.
+fn scaled_legendre(t: f64, nmax: usize, mmax: usize) -> Generic2D {
+    let mut p = Generic2D::zeros((nmax + 1, mmax.min(nmax) + 1));
+    p[[0, 0]] = 1.;
+    for m in 0..=mmax.min(nmax) {
+        let mf = m as f64;
+        // Sectoral
+        if m == 1 {
+            p[[1, 1]] = 3f64.sqrt();
+        } else if m > 1 {
+            p[[m, m]] = ((2. * mf + 1.) / (2. * mf)).sqrt() * p[[m - 1, m - 1]];
+        }
+        // Fixed order, increasing degree
+        for n in (m + 1)..=nmax {
+            let nf = n as f64;
+            let a = ((2. * nf - 1.) * (2. * nf + 1.) / ((nf - mf) * (nf + mf))).sqrt();
+            let b = if n >= m + 2 {
+                ((2. * nf + 1.) * (nf + mf - 1.) * (nf - mf - 1.)
+                    / ((nf - mf) * (nf + mf) * (2. * nf - 3.)))
+                    .sqrt()
+                    * p[[n - 2, m]]
+            } else {
+                0.
+            };
+            p[[n, m]] = a * t * p[[n - 1, m]] - b;
+        }
+    }
+    p
+}
+
+/// Geocentric latitude sine/cosine and longitude of a position.
+fn spherical(r_ecef: &ArrayView1<f64>) -> (f64, f64, f64, f64) {
+    let r = r_ecef.dot(r_ecef).sqrt();
+    let rxy = r_ecef[0].hypot(r_ecef[1]);
+    (r, r_ecef[2] / r, rxy / r, r_ecef[1].atan2(r_ecef[0]))
+}
+
+/// sum (R/r)^(n+1) Pbar_nm(sin phi) (Cnm cos m lambda + Snm sin m lambda)
+/// over degrees nmin..=nmax, fully normalized Cnm/Snm.
+pub(crate) fn potential_sum(
+    cnm: &Generic2D,
+    snm: &Generic2D,
+    radius: f64,
+    r_ecef: &ArrayView1<f64>,
+    nmax: usize,
+    mmax: usize,
+    nmin: usize,
+) -> f64 {
+    let (r, t, u, lambda) = spherical(r_ecef);
+    let p = scaled_legendre(t, nmax, mmax);
+    let rho = radius / r;
+    let mut sum = 0.;
+    for m in 0..=mmax.min(nmax) {
+        let (sin_ml, cos_ml) = (m as f64 * lambda).sin_cos();
+        let um = u.powi(m as i32);
+        for n in m.max(nmin)..=nmax {
+            let y = cnm[[n, m]] * cos_ml + snm[[n, m]] * sin_ml;
+            sum += rho.powi(n as i32 + 1) * um * p[[n, m]] * y;
+        }
+    }
+    sum
+}
+
+/// R grad sum (R/r)^(n+1) Pbar_nm(sin phi) (Cnm cos m lambda + Snm sin m lambda)
+/// over degrees nmin..=nmax, fully normalized Cnm/Snm. Gradient taken in
+/// spherical components from the scaled Legendre functions, then rotated
+/// to Cartesian.
+pub(crate) fn potential_gradient(
+    cnm: &Generic2D,
+    snm: &Generic2D,
+    radius: f64,
+    r_ecef: &ArrayView1<f64>,
+    nmax: usize,
+    mmax: usize,
+    nmin: usize,
+) -> Generic1D {
+    let (r, t, u, lambda) = spherical(r_ecef);
+    let mmax = mmax.min(nmax);
+    // Latitude derivatives need one order beyond the truncation
+    let p = scaled_legendre(t, nmax, mmax + 1);
+    let rho = radius / r;
+
+    // d/dr, (1/r) d/dphi, (1/(r cos phi)) d/dlambda, each scaled by R
+    let (mut g_r, mut g_phi, mut g_lambda) = (0., 0., 0.);
+    for m in 0..=mmax {
+        let mf = m as f64;
+        let (sin_ml, cos_ml) = (mf * lambda).sin_cos();
+        let um = u.powi(m as i32);
+        // cos^(m-1) phi, finite at the poles for m >= 1
+        let um1 = if m == 0 { 0. } else { u.powi(m as i32 - 1) };
+        for n in m.max(nmin)..=nmax {
+            let nf = n as f64;
+            let c = cnm[[n, m]];
+            let s = snm[[n, m]];
+            let y = c * cos_ml + s * sin_ml;
+            let dy = s * cos_ml - c * sin_ml;
+            let rho_n = rho.powi(n as i32 + 2);
+
+            // dPbar_nm/dphi = e_nm Pbar_n,m+1 - m tan(phi) Pbar_nm
+            let next = if m < n { p[[n, m + 1]] } else { 0. };
+            let dp = if m == 0 {
+                (nf * (nf + 1.) / 2.).sqrt() * u * next
+            } else {
+                um1 * (((nf - mf) * (nf + mf + 1.)).sqrt() * u * u * next - mf * t * p[[n, m]])
+            };
+
+            g_r -= rho_n * (nf + 1.) * um * p[[n, m]] * y;
+            g_phi += rho_n * dp * y;
+            g_lambda += rho_n * mf * um1 * p[[n, m]] * dy;
+        }
+    }
+
+    let (sin_l, cos_l) = lambda.sin_cos();
+    array![
+        u * cos_l * g_r - t * cos_l * g_phi - sin_l * g_lambda,
+        u * sin_l * g_r - t * sin_l * g_phi + cos_l * g_lambda,
+        t * g_r + u * g_phi
+    ]
+}
@@ -1,14 +1,25 @@
+use crate::ephemeris::atmosphere::SpaceWeather;
+use crate::ephemeris::celestial::{
+    moon_position_eci, shadow_fraction, sun_position_eci, third_body_acceleration, ShadowModel,
+    MU_MOON, MU_SUN,
+};
 use crate::ephemeris::consts;
+use crate::ephemeris::srp::SrpModel;
+use crate::ephemeris::types::{DragModel, ForceModel, GravityModel};
 use altai_rs::meta::types::{Generic1D, Generic2D};
 use altai_rs::veclib::unit;
-use ndarray::{concatenate, s, Axis};
+use ndarray::{array, concatenate, s, ArrayView1, Axis};
 
-pub fn orbital_twobody(_t: f64, state0: &Generic1D, _inpt: &Generic2D) -> Generic1D {
+pub fn orbital_twobody(_t: f64, state0: &Generic1D, inpt: &Generic2D) -> Generic1D {
     /*
     Inputs:
     0-2: R-vector (ECI) at Time [m]
     3-5: V-vector (ECI) at Time [m/s]
 
+    inpt[0..3, 0]: Net applied force (Body) [N]
+    inpt[0, 1]: Spacecraft mass [kg]
+    inpt[0..3, 2..5]: Attitude matrix (ECI -> Body) [-]
+
     Outputs:
     0-2: V-Vector (ECI) at time [m/s]
     3-5: A-Vector (ECI) at time [m/s2]
@@ -20,7 +31,175 @@ pub fn orbital_twobody(_t: f64, state0: &Generic1D, _inpt: &Generic2D) -> Generi
 
     // Compute Accel
     let (_, mrsc) = unit(rsc.to_owned().insert_axis(Axis(1)));
-    let asc = -1. * consts::MU * rsc.to_owned() / (mrsc.powi(3));
+    let fsc_body = inpt.slice(s![0..3, 0]);
+    let msc = inpt[[0, 1]];
+    let a_eci_body = inpt.slice(s![0..3, 2..5]);
+    let fsc = a_eci_body.t().dot(&fsc_body);
+    let asc = -consts::MU * rsc.to_owned() / (mrsc.powi(3)) + fsc / msc;
 
     concatenate![Axis(0), vsc, asc]
 }
+
+pub fn zonal_gravity(rsc: &ArrayView1<f64>, degree: usize) -> Generic1D {
+    /*
+    Zonal harmonic perturbing acceleration, J2 through J<degree>
+
+    Inputs:
+    rsc: R-vector (ECI) [m]
+    degree: 2..=6
+
+    Outputs:
+    0-2: Perturbing A-Vector (ECI) [m/s2]
+    */
+    let rmag = rsc.dot(rsc).sqrt();
+    let rhat = rsc.to_owned() / rmag;
+    let zhat = array![0., 0., 1.];
+    let u = rhat[2];
+
+    // Legendre polynomials and derivatives by recursion
+    let mut pn = vec![1., u];
+    let mut dpn = vec![0., 1.];
+    for n in 1..degree {
+        let nf = n as f64;
+        pn.push(((2. * nf + 1.) * u * pn[n] - nf * pn[n - 1]) / (nf + 1.));
+        dpn.push(dpn[n - 1] + (2. * nf + 1.) * pn[n]);
+    }
+
+    // a_n = mu Jn Re^n / r^(n+2) [(n+1) Pn(u) rhat - Pn'(u) (zhat - u rhat)]
+    let mut asc = Generic1D::zeros(3);
+    for n in 2..=degree {
+        let nf = n as f64;
+        let coef = consts::MU * consts::JN[n] * consts::RE.powi(n as i32) / rmag.powi(n as i32 + 2);
+        let radial = (nf + 1.) * pn[n] + dpn[n] * u;
+        asc = asc + coef * (radial * &rhat - dpn[n] * &zhat);
+    }
+    asc
+}
+
+pub fn orbital_perturbed(
+    t: f64,
+    state0: &Generic1D,
+    inpt: &Generic2D,
+    forces: &ForceModel,
+) -> Generic1D {
+    /*
+    Two-body dynamics plus the perturbations enabled in `forces`.
+    State and input layout as in `orbital_twobody`.
+    */
+    let mut dstate = orbital_twobody(t, state0, inpt);
+    let rsc = state0.slice(s![0..3]);
+
+    let mut apert = match &forces.gravity {
+        GravityModel::PointMass => Generic1D::zeros(3),
+        GravityModel::Zonal { degree } => zonal_gravity(&rsc, *degree),
+        GravityModel::SphericalHarmonic {
+            field,
+            degree,
+            order,
+        } => {
+            let eci_to_ecef = forces.frames.eci_to_ecef(t);
+            let r_ecef = eci_to_ecef.dot(&rsc);
+            let a_ecef = field.acceleration(&r_ecef.view(), *degree, *order, false);
+            eci_to_ecef.t().dot(&a_ecef)
+        }
+    };
+
+    if let Some(drag) = &forces.drag {
+        let vsc = state0.slice(s![3..6]);
+        apert = apert + drag_acceleration(t, &rsc, &vsc, inpt, drag, &forces.space_weather);
+    }
+
+    if let Some(srp) = &forces.srp {
+        apert = apert + srp_acceleration(t, &rsc, inpt, srp, forces.shadow);
+    }
+
+    if forces.third_body.sun {
+        let r_sun = sun_position_eci(t);
+        apert = apert + third_body_acceleration(&rsc, &r_sun.view(), MU_SUN);
+    }
+    if forces.third_body.moon {
+        let r_moon = moon_position_eci(t);
+        apert = apert + third_body_acceleration(&rsc, &r_moon.view(), MU_MOON);
+    }
+
+    let mut asc = dstate.slice_mut(s![3..6]);
+    asc += &apert;
+    dstate
+}
+
+pub fn drag_acceleration(
+    t: f64,
+    rsc: &ArrayView1<f64>,
+    vsc: &ArrayView1<f64>,
+    inpt: &Generic2D,
+    drag: &DragModel,
+    space_weather: &SpaceWeather,
+) -> Generic1D {
+    /*
+    Atmospheric drag against a co-rotating atmosphere; Vallado Eq. 8-28
+
+    Inputs:
+    rsc, vsc: R/V-vectors (ECI) [m], [m/s]
+    inpt: As in `orbital_twobody`; mass and attitude matrix are used
+
+    Outputs:
+    0-2: Drag A-Vector (ECI) [m/s2]
+    */
+    let rho = drag.atmosphere.density(t, rsc, space_weather);
+    if rho <= 0. {
+        return Generic1D::zeros(3);
+    }
+
+    // Velocity relative to the atmosphere
+    let v_atm = array![-consts::OMEGA_E * rsc[1], consts::OMEGA_E * rsc[0], 0.];
+    let v_rel = vsc.to_owned() - v_atm;
+    let v_rel_mag = v_rel.dot(&v_rel).sqrt();
+    if v_rel_mag == 0. {
+        return Generic1D::zeros(3);
+    }
+
+    let msc = inpt[[0, 1]];
+    let a_eci_body = inpt.slice(s![0..3, 2..5]);
+    let vhat_body = a_eci_body.dot(&v_rel) / v_rel_mag;
+    let area = drag.area.projected(&vhat_body.view());
+
+    -0.5 * rho * drag.cd * area / msc * v_rel_mag * v_rel
+}
+
+pub fn srp_acceleration(
+    t: f64,
+    rsc: &ArrayView1<f64>,
+    inpt: &Generic2D,
+    srp: &SrpModel,
+    shadow: ShadowModel,
+) -> Generic1D {
+    /*
+    Solar radiation pressure with Earth shadowing
+
+    Inputs:
+    rsc: R-vector (ECI) [m]
+    inpt: As in `orbital_twobody`; mass and attitude matrix are used
+
+    Outputs:
+    0-2: SRP A-Vector (ECI) [m/s2]
+    */
+    let r_sun = sun_position_eci(t);
+    let nu = shadow_fraction(shadow, rsc, &r_sun.view());
+    if nu <= 0. {
+        return Generic1D::zeros(3);
+    }
+    let d_sun = r_sun - rsc;
+    let sun_dist = d_sun.dot(&d_sun).sqrt();
+
+    let msc = inpt[[0, 1]];
+    let a_eci_body = inpt.slice(s![0..3, 2..5]);
+    let sun_hat_body = a_eci_body.dot(&d_sun) / sun_dist;
+    // Force only; CoM offset does not matter here
+    let (f_body, _) = srp.force_torque_body(
+        &sun_hat_body.view(),
+        sun_dist,
+        nu,
+        &Generic1D::zeros(3).view(),
+    );
+    a_eci_body.t().dot(&f_body) / msc
+}
@@ -0,0 +1,252 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use altai_rs::meta::types::{Generic1D, Generic2D};
+use ndarray::{array, ArrayView1};
+
+use crate::ephemeris::geopotential::potential_gradient;
+use crate::time::consts::SEC_PER_DAY;
+
+// IGRF reference radius [m]
+pub const IGRF_RADIUS: f64 = 6371.2e3;
+
+#[derive(Debug)]
+pub enum MagneticFieldError {
+    Io(std::io::Error),
+    MissingHeader,
+    Parse { line: usize, msg: String },
+}
+impl fmt::Display for MagneticFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read field coefficients: {err}"),
+            Self::MissingHeader => write!(f, "coefficient file has no `g/h n m` epoch header"),
+            Self::Parse { line, msg } => write!(f, "field coefficients line {line}: {msg}"),
+        }
+    }
+}
+impl std::error::Error for MagneticFieldError {}
+impl From<std::io::Error> for MagneticFieldError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Decimal year of a TT instant [TT s past J2000].
+pub fn decimal_year(time: f64) -> f64 {
+    2000. + time / (365.25 * SEC_PER_DAY)
+}
+
+fn schmidt_to_full(n: usize) -> f64 {
+    // Fully normalized Pbar_nm = sqrt(2n + 1) times the Schmidt semi-normalized Pnm
+    1. / ((2 * n + 1) as f64).sqrt()
+}
+
+/// International Geomagnetic Reference Field, Schmidt semi-normalized Gauss
+/// coefficients [nT] at 5-year epochs plus the secular variation [nT/yr]
+/// after the last one.
+#[derive(Debug, Clone)]
+pub struct IgrfModel {
+    pub max_degree: usize,
+    epochs: Vec<f64>,    // [yr]
+    gnm: Vec<Generic2D>, // gnm[epoch][[n, m]]
+    hnm: Vec<Generic2D>,
+    sv_g: Generic2D,
+    sv_h: Generic2D,
+}
+
+impl IgrfModel {
+    pub fn from_coeffs_file<P: AsRef<Path>>(path: P) -> Result<Self, MagneticFieldError> {
+        Self::from_coeffs_str(&fs::read_to_string(path)?)
+    }
+
+    /// IAGA distribution format (`igrf13coeffs.txt`): `#` comments, a
+    /// `g/h n m <epochs...> <SV span>` header, then `g|h n m <values...>` rows.
+    pub fn from_coeffs_str(text: &str) -> Result<Self, MagneticFieldError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty() && !l.starts_with('#'));
+
+        let parse = |token: &str, line: usize| -> Result<f64, MagneticFieldError> {
+            token
+                .parse::<f64>()
+                .map_err(|err| MagneticFieldError::Parse {
+                    line,
+                    msg: format!("`{token}`: {err}"),
+                })
+        };
+
+        let (idx, header) = lines
+            .by_ref()
+            .find(|(_, l)| l.starts_with("g/h"))
+            .ok_or(MagneticFieldError::MissingHeader)?;
+        // Last column is the secular variation, labelled e.g. `2020-25`
+        let columns: Vec<&str> = header.split_whitespace().skip(3).collect();
+        if columns.len() < 2 {
+            return Err(MagneticFieldError::MissingHeader);
+        }
+        let epochs = columns[..columns.len() - 1]
+            .iter()
+            .map(|c| parse(c, idx + 1))
+            .collect::<Result<Vec<f64>, _>>()?;
+
+        let mut rows = Vec::new();
+        for (idx, line) in lines {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            if tokens.len() != columns.len() + 3 {
+                return Err(MagneticFieldError::Parse {
+                    line: idx + 1,
+                    msg: format!("expected {} columns", columns.len() + 3),
+                });
+            }
+            let bad_index = |msg: String| MagneticFieldError::Parse { line: idx + 1, msg };
+            let n: usize = tokens[1]
+                .parse()
+                .map_err(|_| bad_index(format!("degree `{}`", tokens[1])))?;
+            let m: usize = tokens[2]
+                .parse()
+                .map_err(|_| bad_index(format!("order `{}`", tokens[2])))?;
+            if n == 0 || m > n {
+                return Err(bad_index(format!("invalid degree/order {n}/{m}")));
+            }
+            let values = tokens[3..]
+                .iter()
+                .map(|t| parse(t, idx + 1))
+                .collect::<Result<Vec<f64>, _>>()?;
+            let is_h = match tokens[0] {
+                "g" => false,
+                "h" => true,
+                key => return Err(bad_index(format!("unknown coefficient `{key}`"))),
+            };
+            rows.push((is_h, n, m, values));
+        }
+
+        let max_degree = rows.iter().map(|r| r.1).max().unwrap_or(0);
+        let zeros = || Generic2D::zeros((max_degree + 1, max_degree + 1));
+        let mut model = Self {
+            max_degree,
+            gnm: vec![zeros(); epochs.len()],
+            hnm: vec![zeros(); epochs.len()],
+            epochs,
+            sv_g: zeros(),
+            sv_h: zeros(),
+        };
+        let n_epochs = model.epochs.len();
+        for (is_h, n, m, values) in rows {
+            let (coef, sv) = if is_h {
+                (&mut model.hnm, &mut model.sv_h)
+            } else {
+                (&mut model.gnm, &mut model.sv_g)
+            };
+            for (k, value) in values[..n_epochs].iter().enumerate() {
+                coef[k][[n, m]] = *value;
+            }
+            sv[[n, m]] = values[n_epochs];
+        }
+        Ok(model)
+    }
+
+    pub fn epochs(&self) -> &[f64] {
+        &self.epochs
+    }
+
+    /// Gauss coefficients (g, h) [nT] at a decimal year: linear between
+    /// epochs, secular variation past the last, held before the first.
+    pub fn coefficients(&self, year: f64) -> (Generic2D, Generic2D) {
+        let last = self.epochs.len() - 1;
+        if year >= self.epochs[last] {
+            let dt = year - self.epochs[last];
+            return (
+                &self.gnm[last] + &(dt * &self.sv_g),
+                &self.hnm[last] + &(dt * &self.sv_h),
+            );
+        }
+        // A single-epoch file holds its values before the epoch
+        if last == 0 {
+            return (self.gnm[0].clone(), self.hnm[0].clone());
+        }
+        let k = self
+            .epochs
+            .partition_point(|&e| e <= year)
+            .saturating_sub(1);
+        let frac = ((year - self.epochs[k]) / (self.epochs[k + 1] - self.epochs[k])).max(0.);
+        (
+            (1. - frac) * &self.gnm[k] + frac * &self.gnm[k + 1],
+            (1. - frac) * &self.hnm[k] + frac * &self.hnm[k + 1],
+        )
+    }
+
+    /// Field vector (ECEF) [T] at a geocentric position and decimal year.
+    pub fn field_ecef(&self, r_ecef: &ArrayView1<f64>, year: f64, degree: usize) -> Generic1D {
+        let nmax = degree.min(self.max_degree);
+        let (gnm, hnm) = self.coefficients(year);
+        let mut cnm = Generic2D::zeros((nmax + 1, nmax + 1));
+        let mut snm = Generic2D::zeros((nmax + 1, nmax + 1));
+        for n in 1..=nmax {
+            for m in 0..=n {
+                let scale = 1e-9 * schmidt_to_full(n);
+                cnm[[n, m]] = scale * gnm[[n, m]];
+                snm[[n, m]] = scale * hnm[[n, m]];
+            }
+        }
+        // V = R sum (R/r)^(n+1) Pnm (g cos + h sin); B = -grad V
+        -potential_gradient(&cnm, &snm, IGRF_RADIUS, r_ecef, nmax, nmax, 1)
+    }
+}
+
+/// Centered tilted dipole from the degree-1 Gauss coefficients [nT].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DipoleField {
+    pub g10: f64,
+    pub g11: f64,
+    pub h11: f64,
+}
+impl Default for DipoleField {
+    fn default() -> Self {
+        // IGRF-13, epoch 2020.0
+        Self {
+            g10: -29404.8,
+            g11: -1450.9,
+            h11: 4652.5,
+        }
+    }
+}
+impl DipoleField {
+    /// Field vector (ECEF) [T]; B = R^3/r^3 (3 (m.rhat) rhat - m).
+    pub fn field_ecef(&self, r_ecef: &ArrayView1<f64>) -> Generic1D {
+        let m = 1e-9 * array![self.g11, self.h11, self.g10];
+        let rmag = r_ecef.dot(r_ecef).sqrt();
+        let rhat = r_ecef.to_owned() / rmag;
+        (IGRF_RADIUS / rmag).powi(3) * (3. * m.dot(&rhat) * rhat - m)
+    }
+}
+
+/// Geomagnetic field model selectable from `SpacecraftEphemerisArchitecture`.
+#[derive(Debug, Clone)]
+pub enum MagneticFieldModel {
+    None,
+    Dipole(DipoleField),
+    // Full IGRF truncated to degree
+    Igrf {
+        model: Arc<IgrfModel>,
+        degree: usize,
+    },
+}
+impl Default for MagneticFieldModel {
+    fn default() -> Self {
+        Self::Dipole(DipoleField::default())
+    }
+}
+impl MagneticFieldModel {
+    /// Field vector (ECEF) [T] at TT seconds past J2000.
+    pub fn field_ecef(&self, time: f64, r_ecef: &ArrayView1<f64>) -> Generic1D {
+        match self {
+            Self::None => Generic1D::zeros(3),
+            Self::Dipole(dipole) => dipole.field_ecef(r_ecef),
+            Self::Igrf { model, degree } => model.field_ecef(r_ecef, decimal_year(time), *degree),
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use std::f64::consts::PI;
+use std::fmt;
+
+use crate::ephemeris::consts::{J2, RE};
+use crate::ephemeris::elements::{
+    mean_to_eccentric_anomaly, mean_to_true_anomaly, wrap_2pi, ClassicalElements,
+};
+
+// Fixed-point iterations for the osculating -> mean inversion
+const MEAN_ELEMENT_ITERATIONS: usize = 20;
+// Closest approach to sin(i) = 0 or 1 - 5 cos^2(i) = 0 the mapping accepts
+const SINGULARITY_TOL: f64 = 1e-3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MeanElementsError {
+    NotElliptic(f64),
+    Equatorial(f64),
+    CriticalInclination(f64),
+}
+impl fmt::Display for MeanElementsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotElliptic(e) => write!(f, "eccentricity {e} is not elliptic"),
+            Self::Equatorial(i) => write!(
+                f,
+                "inclination {:.4} deg is too close to equatorial",
+                i.to_degrees()
+            ),
+            Self::CriticalInclination(i) => write!(
+                f,
+                "inclination {:.4} deg is too close to the critical inclination",
+                i.to_degrees()
+            ),
+        }
+    }
+}
+impl std::error::Error for MeanElementsError {}
+
+fn check_domain(coe: &ClassicalElements) -> Result<(), MeanElementsError> {
+    if !(0. ..1.).contains(&coe.e) {
+        return Err(MeanElementsError::NotElliptic(coe.e));
+    }
+    if coe.i.sin().abs() < SINGULARITY_TOL {
+        return Err(MeanElementsError::Equatorial(coe.i));
+    }
+    if (1. - 5. * coe.i.cos().powi(2)).abs() < SINGULARITY_TOL {
+        return Err(MeanElementsError::CriticalInclination(coe.i));
+    }
+    Ok(())
+}
+
+fn wrap_pi(angle: f64) -> f64 {
+    (angle + PI).rem_euclid(2. * PI) - PI
+}
+
+/// Brouwer-Lyddane first-order J2 mean -> osculating mapping, short- and
+/// long-period terms; Schaub & Junkins Appendix F.
+///
+/// Elliptic orbits only. Singular at zero inclination and at the critical
+/// inclination (cos^2 i = 1/5); elements near either are rejected.
+pub fn brouwer_mean_to_osculating(
+    mean: &ClassicalElements,
+) -> Result<ClassicalElements, MeanElementsError> {
+    check_domain(mean)?;
+    Ok(brouwer_mapping(mean, J2 / 2. * (RE / mean.a).powi(2)))
+}
+
+/// Inverse of `brouwer_mean_to_osculating`, solved by fixed-point iteration
+/// in non-singular variables. Same domain of validity.
+pub fn brouwer_osculating_to_mean(
+    osc: &ClassicalElements,
+) -> Result<ClassicalElements, MeanElementsError> {
+    // Non-singular set: a, e cos(argp), e sin(argp), i, raan, argp + M
+    let to_vars = |coe: &ClassicalElements| {
+        [
+            coe.a,
+            coe.e * coe.argp.cos(),
+            coe.e * coe.argp.sin(),
+            coe.i,
+            coe.raan,
+            coe.argp + coe.mean_anomaly(),
+        ]
+    };
+    let from_vars = |x: &[f64; 6]| {
+        let e = x[1].hypot(x[2]);
+        let argp = x[2].atan2(x[1]);
+        ClassicalElements {
+            a: x[0],
+            e,
+            i: x[3],
+            raan: wrap_2pi(x[4]),
+            argp: wrap_2pi(argp),
+            nu: mean_to_true_anomaly(x[5] - argp, e),
+        }
+    };
+
+    check_domain(osc)?;
+    let target = to_vars(osc);
+    let mut mean = target;
+    for _ in 0..MEAN_ELEMENT_ITERATIONS {
+        let mapped = to_vars(&brouwer_mean_to_osculating(&from_vars(&mean))?);
+        let mut max_corr: f64 = 0.;
+        for idx in 0..6 {
+            let mut corr = target[idx] - mapped[idx];
+            if idx >= 4 {
+                corr = wrap_pi(corr);
+            }
+            mean[idx] += corr;
+            max_corr = max_corr.max(if idx == 0 {
+                corr.abs() / osc.a
+            } else {
+                corr.abs()
+            });
+        }
+        if max_corr < 1e-13 {
+            break;
+        }
+    }
+    Ok(from_vars(&mean))
+}
+
+fn brouwer_mapping(coe: &ClassicalElements, gamma2: f64) -> ClassicalElements {
+    /*
+    Inputs:
+    coe: Mean elements
+    gamma2: J2/2 (Re/a)^2; the sign selects the mapping direction
+
+    Outputs:
+    Osculating elements
+    */
+    let (a, e, i, raan, argp) = (coe.a, coe.e, coe.i, coe.raan, coe.argp);
+    let m = coe.mean_anomaly();
+    let eta = (1. - e * e).sqrt();
+    let gp = gamma2 / eta.powi(4);
+
+    let ea = mean_to_eccentric_anomaly(m, e);
+    let f =
+        wrap_2pi(2. * ((1. + e).sqrt() * (ea / 2.).sin()).atan2((1. - e).sqrt() * (ea / 2.).cos()));
+    let ar = (1. + e * f.cos()) / eta.powi(2);
+
+    let ci = i.cos();
+    let c2 = ci * ci;
+    let s2 = 1. - c2;
+    let crit = 1. - 5. * c2;
+    let (cf, sf) = (f.cos(), f.sin());
+    let cos2w2f = (2. * argp + 2. * f).cos();
+    let sin_terms = 3. * (2. * argp + 2. * f).sin()
+        + 3. * e * (2. * argp + f).sin()
+        + e * (2. * argp + 3. * f).sin();
+    let eq_center = wrap_pi(f - m + e * sf);
+
+    // Semi-major axis
+    let a_osc = a + a
+        * gamma2
+        * ((3. * c2 - 1.) * (ar.powi(3) - 1. / eta.powi(3)) + 3. * s2 * ar.powi(3) * cos2w2f);
+
+    // Eccentricity and inclination
+    let de1 = gp / 8. * e * eta * eta * (1. - 11. * c2 - 40. * c2 * c2 / crit) * (2. * argp).cos();
+    let cubic = 3. * cf + 3. * e * cf * cf + e * e * cf.powi(3);
+    let de = de1
+        + eta * eta / 2.
+            * (gamma2
+                * ((3. * c2 - 1.) / eta.powi(6) * (e * eta + e / (1. + eta) + cubic)
+                    + 3. * s2 / eta.powi(6) * (e + cubic) * cos2w2f)
+                - gp * s2 * (3. * (2. * argp + f).cos() + (2. * argp + 3. * f).cos()));
+    let di = -e * de1 / (eta * eta * i.tan())
+        + gp / 2.
+            * ci
+            * s2.sqrt()
+            * (3. * cos2w2f + 3. * e * (2. * argp + f).cos() + e * (2. * argp + 3. * f).cos());
+
+    // Angles
+    let lon_sum = m + argp + raan + gp / 8. * eta.powi(3) * (1. - 11. * c2 - 40. * c2 * c2 / crit)
+        - gp / 16.
+            * (2. + e * e
+                - 11. * (2. + 3. * e * e) * c2
+                - 40. * (2. + 5. * e * e) * c2 * c2 / crit
+                - 400. * e * e * c2.powi(3) / crit.powi(2))
+        + gp / 4. * (-6. * crit * eq_center + (3. - 5. * c2) * sin_terms)
+        - gp / 8. * e * e * ci * (11. + 80. * c2 / crit + 200. * c2 * c2 / crit.powi(2))
+        - gp / 2. * ci * (6. * eq_center - sin_terms);
+    let ar_eta2 = (ar * eta).powi(2);
+    let e_dm = gp / 8. * e * eta.powi(3) * (1. - 11. * c2 - 40. * c2 * c2 / crit)
+        - gp / 4.
+            * eta.powi(3)
+            * (2. * (3. * c2 - 1.) * (ar_eta2 + ar + 1.) * sf
+                + 3. * s2
+                    * ((-ar_eta2 - ar + 1.) * (2. * argp + f).sin()
+                        + (ar_eta2 + ar + 1. / 3.) * (2. * argp + 3. * f).sin()));
+    let draan = -gp / 8. * e * e * ci * (11. + 80. * c2 / crit + 200. * c2 * c2 / crit.powi(2))
+        - gp / 2. * ci * (6. * eq_center - sin_terms);
+
+    // Lyddane's recombination avoids division by small e and sin(i)
+    let d1 = (e + de) * m.sin() + e_dm * m.cos();
+    let d2 = (e + de) * m.cos() - e_dm * m.sin();
+    let m_osc = d1.atan2(d2);
+    let e_osc = d1.hypot(d2);
+
+    let (sh, ch) = (i / 2.).sin_cos();
+    let d3 = (sh + ch * di / 2.) * raan.sin() + sh * draan * raan.cos();
+    let d4 = (sh + ch * di / 2.) * raan.cos() - sh * draan * raan.sin();
+    let raan_osc = d3.atan2(d4);
+    let i_osc = 2. * d3.hypot(d4).min(1.).asin();
+
+    ClassicalElements {
+        a: a_osc,
+        e: e_osc,
+        i: i_osc,
+        raan: wrap_2pi(raan_osc),
+        argp: wrap_2pi(lon_sum - m_osc - raan_osc),
+        nu: mean_to_true_anomaly(m_osc, e_osc),
+    }
+}
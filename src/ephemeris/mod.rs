@@ -1,3 +1,12 @@
+pub mod atmosphere;
+pub mod celestial;
 pub mod consts;
+pub mod elements;
+pub mod geopotential;
 pub mod kinedynamics;
+pub mod magnetic;
+pub mod mean_elements;
+pub mod sgp4;
+pub mod srp;
+pub mod tle;
 pub mod types;
@@ -0,0 +1,1094 @@
+use std::f64::consts::PI;
+use std::fmt;
+
+use altai_rs::meta::types::Vector3;
+use ndarray::array;
+
+use crate::ephemeris::tle::Tle;
+use crate::frames::rotations::{gcrf_to_teme, SEC_PER_CENTURY};
+use crate::time::types::TimeScale;
+
+const TWO_PI: f64 = 2. * PI;
+const X2O3: f64 = 2. / 3.;
+
+// WGS-72 constants used by the element sets; Vallado et al. (2006)
+const MU_WGS72: f64 = 398600.8; // [km3/s2]
+const RE_WGS72: f64 = 6378.135; // [km]
+const J2_WGS72: f64 = 0.001082616;
+const J3_WGS72: f64 = -0.00000253881;
+const J4_WGS72: f64 = -0.00000165597;
+
+fn xke() -> f64 {
+    // sqrt(mu) in earth radii^1.5 per minute
+    60. / (RE_WGS72.powi(3) / MU_WGS72).sqrt()
+}
+
+/// Propagation failures, numbered as in Vallado's `sgp4` error codes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sgp4Error {
+    Eccentricity(f64),          // 1: mean eccentricity outside [0, 1)
+    MeanMotion(f64),            // 2: mean motion not positive
+    PerturbedEccentricity(f64), // 3: perturbed eccentricity outside [0, 1]
+    SemiLatusRectum(f64),       // 4: semi-latus rectum negative
+    Decayed(f64),               // 6: radius below one earth radius [km]
+}
+impl fmt::Display for Sgp4Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Eccentricity(e) => write!(f, "mean eccentricity {e} out of range"),
+            Self::MeanMotion(n) => write!(f, "mean motion {n} rad/min is not positive"),
+            Self::PerturbedEccentricity(e) => {
+                write!(f, "perturbed eccentricity {e} out of range")
+            }
+            Self::SemiLatusRectum(p) => write!(f, "semi-latus rectum {p} is negative"),
+            Self::Decayed(r) => write!(f, "orbit has decayed (radius {r} km)"),
+        }
+    }
+}
+impl std::error::Error for Sgp4Error {}
+
+/// Lunisolar periodic and secular coefficients for SDP4 (`dscom`/`dsinit`).
+#[derive(Debug, Clone, Default)]
+struct DeepSpace {
+    e3: f64,
+    ee2: f64,
+    peo: f64,
+    pgho: f64,
+    pho: f64,
+    pinco: f64,
+    plo: f64,
+    se2: f64,
+    se3: f64,
+    sgh2: f64,
+    sgh3: f64,
+    sgh4: f64,
+    sh2: f64,
+    sh3: f64,
+    si2: f64,
+    si3: f64,
+    sl2: f64,
+    sl3: f64,
+    sl4: f64,
+    xgh2: f64,
+    xgh3: f64,
+    xgh4: f64,
+    xh2: f64,
+    xh3: f64,
+    xi2: f64,
+    xi3: f64,
+    xl2: f64,
+    xl3: f64,
+    xl4: f64,
+    zmol: f64,
+    zmos: f64,
+    // Secular rates
+    dedt: f64,
+    didt: f64,
+    dmdt: f64,
+    dnodt: f64,
+    domdt: f64,
+    // Resonance: 0 none, 1 one-day, 2 half-day
+    irez: u8,
+    d2201: f64,
+    d2211: f64,
+    d3210: f64,
+    d3222: f64,
+    d4410: f64,
+    d4422: f64,
+    d5220: f64,
+    d5232: f64,
+    d5421: f64,
+    d5433: f64,
+    del1: f64,
+    del2: f64,
+    del3: f64,
+    xfact: f64,
+    xlamo: f64,
+}
+
+/// Outputs of `dscom` that only `dsinit` needs.
+struct DscomTerms {
+    inclm: f64,
+    sinim: f64,
+    cosim: f64,
+    emsq: f64,
+    s: [f64; 5],
+    ss: [f64; 5],
+    sz: [f64; 12], // sz1, sz2, sz3, sz11, sz12, sz13, sz21, sz22, sz23, sz31, sz32, sz33
+    z: [f64; 12],  // Same ordering as sz
+}
+
+/// SGP4/SDP4 analytic propagator; a transcription of Vallado, Crawford,
+/// Hujsak & Kelso, "Revisiting Spacetrack Report #3" (AIAA 2006-6753),
+/// AFSPC operations mode.
+#[derive(Debug, Clone)]
+pub struct Sgp4 {
+    pub tle: Tle,
+    deep_space: Option<Box<DeepSpace>>,
+    isimp: bool,
+    // Mean elements at epoch, mean motion un-Kozai'd [rad/min]
+    ecco: f64,
+    inclo: f64,
+    nodeo: f64,
+    argpo: f64,
+    mo: f64,
+    no: f64,
+    bstar: f64,
+    gsto: f64,
+    // Near-earth coefficients
+    aycof: f64,
+    con41: f64,
+    cc1: f64,
+    cc4: f64,
+    cc5: f64,
+    d2: f64,
+    d3: f64,
+    d4: f64,
+    delmo: f64,
+    eta: f64,
+    argpdot: f64,
+    omgcof: f64,
+    sinmao: f64,
+    t2cof: f64,
+    t3cof: f64,
+    t4cof: f64,
+    t5cof: f64,
+    x1mth2: f64,
+    x7thm1: f64,
+    mdot: f64,
+    nodedot: f64,
+    xlcof: f64,
+    xmcof: f64,
+    nodecf: f64,
+}
+
+fn gsto_afspc(epoch: f64) -> f64 {
+    // Greenwich sidereal angle from days since 1950 Jan 0.0; AFSPC form
+    let ts70 = epoch - 7305.;
+    let ds70 = (ts70 + 1e-8).floor();
+    let tfrac = ts70 - ds70;
+    let c1 = 1.7202791694070362e-2;
+    let thgr70 = 1.7321343856509374;
+    let fk5r = 5.075514194322695e-15;
+    (thgr70 + c1 * ds70 + (c1 + TWO_PI) * tfrac + ts70 * ts70 * fk5r).rem_euclid(TWO_PI)
+}
+
+impl Sgp4 {
+    pub fn initialize(tle: &Tle) -> Result<Self, Sgp4Error> {
+        // sgp4init/initl
+        let xke = xke();
+        let j3oj2 = J3_WGS72 / J2_WGS72;
+        let ss = 78. / RE_WGS72 + 1.;
+        let qzms2t = ((120. - 78.) / RE_WGS72).powi(4);
+        let temp4 = 1.5e-12;
+
+        let ecco = tle.eccentricity;
+        let inclo = tle.inclination;
+        let (nodeo, argpo, mo) = (tle.raan, tle.arg_perigee, tle.mean_anomaly);
+        let bstar = tle.bstar;
+        if !(0. ..1.).contains(&ecco) {
+            return Err(Sgp4Error::Eccentricity(ecco));
+        }
+        if tle.mean_motion <= 0. {
+            return Err(Sgp4Error::MeanMotion(tle.mean_motion));
+        }
+        // Days since 1950 Jan 0.0 UTC
+        let epoch = tle.epoch.jd(TimeScale::UTC) - 2433281.5;
+
+        // initl: recover the original mean motion and semi-major axis
+        let eccsq = ecco * ecco;
+        let omeosq = 1. - eccsq;
+        let rteosq = omeosq.sqrt();
+        let cosio = inclo.cos();
+        let cosio2 = cosio * cosio;
+        let ak = (xke / tle.mean_motion).powf(X2O3);
+        let d1 = 0.75 * J2_WGS72 * (3. * cosio2 - 1.) / (rteosq * omeosq);
+        let mut del = d1 / (ak * ak);
+        let adel = ak * (1. - del * del - del * (1. / 3. + 134. * del * del / 81.));
+        del = d1 / (adel * adel);
+        let no = tle.mean_motion / (1. + del);
+        let ao = (xke / no).powf(X2O3);
+        let sinio = inclo.sin();
+        let po = ao * omeosq;
+        let con42 = 1. - 5. * cosio2;
+        let con41 = -con42 - cosio2 - cosio2;
+        let posq = po * po;
+        let rp = ao * (1. - ecco);
+        let gsto = gsto_afspc(epoch);
+
+        let mut sat = Self {
+            tle: tle.clone(),
+            deep_space: None,
+            isimp: rp < 220. / RE_WGS72 + 1.,
+            ecco,
+            inclo,
+            nodeo,
+            argpo,
+            mo,
+            no,
+            bstar,
+            gsto,
+            aycof: 0.,
+            con41,
+            cc1: 0.,
+            cc4: 0.,
+            cc5: 0.,
+            d2: 0.,
+            d3: 0.,
+            d4: 0.,
+            delmo: 0.,
+            eta: 0.,
+            argpdot: 0.,
+            omgcof: 0.,
+            sinmao: 0.,
+            t2cof: 0.,
+            t3cof: 0.,
+            t4cof: 0.,
+            t5cof: 0.,
+            x1mth2: 0.,
+            x7thm1: 0.,
+            mdot: 0.,
+            nodedot: 0.,
+            xlcof: 0.,
+            xmcof: 0.,
+            nodecf: 0.,
+        };
+
+        // Atmospheric fit parameter s for low perigees
+        let mut sfour = ss;
+        let mut qzms24 = qzms2t;
+        let perige = (rp - 1.) * RE_WGS72;
+        if perige < 156. {
+            sfour = if perige < 98. { 20. } else { perige - 78. };
+            qzms24 = ((120. - sfour) / RE_WGS72).powi(4);
+            sfour = sfour / RE_WGS72 + 1.;
+        }
+        let pinvsq = 1. / posq;
+        let tsi = 1. / (ao - sfour);
+        sat.eta = ao * ecco * tsi;
+        let etasq = sat.eta * sat.eta;
+        let eeta = ecco * sat.eta;
+        let psisq = (1. - etasq).abs();
+        let coef = qzms24 * tsi.powi(4);
+        let coef1 = coef / psisq.powf(3.5);
+        let cc2 = coef1
+            * no
+            * (ao * (1. + 1.5 * etasq + eeta * (4. + etasq))
+                + 0.375 * J2_WGS72 * tsi / psisq * con41 * (8. + 3. * etasq * (8. + etasq)));
+        sat.cc1 = bstar * cc2;
+        let cc3 = if ecco > 1e-4 {
+            -2. * coef * tsi * j3oj2 * no * sinio / ecco
+        } else {
+            0.
+        };
+        sat.x1mth2 = 1. - cosio2;
+        sat.cc4 = 2.
+            * no
+            * coef1
+            * ao
+            * omeosq
+            * (sat.eta * (2. + 0.5 * etasq) + ecco * (0.5 + 2. * etasq)
+                - J2_WGS72 * tsi / (ao * psisq)
+                    * (-3. * con41 * (1. - 2. * eeta + etasq * (1.5 - 0.5 * eeta))
+                        + 0.75
+                            * sat.x1mth2
+                            * (2. * etasq - eeta * (1. + etasq))
+                            * (2. * argpo).cos()));
+        sat.cc5 = 2. * coef1 * ao * omeosq * (1. + 2.75 * (etasq + eeta) + eeta * etasq);
+
+        // Secular rates
+        let cosio4 = cosio2 * cosio2;
+        let temp1 = 1.5 * J2_WGS72 * pinvsq * no;
+        let temp2 = 0.5 * temp1 * J2_WGS72 * pinvsq;
+        let temp3 = -0.46875 * J4_WGS72 * pinvsq * pinvsq * no;
+        sat.mdot = no
+            + 0.5 * temp1 * rteosq * con41
+            + 0.0625 * temp2 * rteosq * (13. - 78. * cosio2 + 137. * cosio4);
+        sat.argpdot = -0.5 * temp1 * con42
+            + 0.0625 * temp2 * (7. - 114. * cosio2 + 395. * cosio4)
+            + temp3 * (3. - 36. * cosio2 + 49. * cosio4);
+        let xhdot1 = -temp1 * cosio;
+        sat.nodedot =
+            xhdot1 + (0.5 * temp2 * (4. - 19. * cosio2) + 2. * temp3 * (3. - 7. * cosio2)) * cosio;
+        let xpidot = sat.argpdot + sat.nodedot;
+        sat.omgcof = bstar * cc3 * argpo.cos();
+        sat.xmcof = if ecco > 1e-4 {
+            -X2O3 * coef * bstar / eeta
+        } else {
+            0.
+        };
+        sat.nodecf = 3.5 * omeosq * xhdot1 * sat.cc1;
+        sat.t2cof = 1.5 * sat.cc1;
+        let den = if (cosio + 1.).abs() > 1.5e-12 {
+            1. + cosio
+        } else {
+            temp4
+        };
+        sat.xlcof = -0.25 * j3oj2 * sinio * (3. + 5. * cosio) / den;
+        sat.aycof = -0.5 * j3oj2 * sinio;
+        sat.delmo = (1. + sat.eta * mo.cos()).powi(3);
+        sat.sinmao = mo.sin();
+        sat.x7thm1 = 7. * cosio2 - 1.;
+
+        // Deep space for periods of 225 min and longer
+        if TWO_PI / no >= 225. {
+            sat.isimp = true;
+            let (mut ds, terms) = dscom(epoch, ecco, argpo, 0., inclo, nodeo, no);
+            dsinit(
+                &mut ds,
+                &terms,
+                xke,
+                argpo,
+                0.,
+                gsto,
+                mo,
+                sat.mdot,
+                no,
+                nodeo,
+                sat.nodedot,
+                xpidot,
+                ecco,
+                eccsq,
+            );
+            sat.deep_space = Some(Box::new(ds));
+        }
+
+        if !sat.isimp {
+            let cc1sq = sat.cc1 * sat.cc1;
+            sat.d2 = 4. * ao * tsi * cc1sq;
+            let temp = sat.d2 * tsi * sat.cc1 / 3.;
+            sat.d3 = (17. * ao + sfour) * temp;
+            sat.d4 = 0.5 * temp * ao * tsi * (221. * ao + 31. * sfour) * sat.cc1;
+            sat.t3cof = sat.d2 + 2. * cc1sq;
+            sat.t4cof = 0.25 * (3. * sat.d3 + sat.cc1 * (12. * sat.d2 + 10. * cc1sq));
+            sat.t5cof = 0.2
+                * (3. * sat.d4
+                    + 12. * sat.cc1 * sat.d3
+                    + 6. * sat.d2 * sat.d2
+                    + 15. * cc1sq * (2. * sat.d2 + cc1sq));
+        }
+
+        sat.propagate(0.)?;
+        Ok(sat)
+    }
+
+    /// ECI (GCRF) position [m] and velocity [m/s] at TT seconds past J2000.
+    pub fn state_eci(&self, time: f64) -> Result<(Vector3, Vector3), Sgp4Error> {
+        let tsince = (time - self.tle.epoch.seconds_since_j2000(TimeScale::TT)) / 60.;
+        let (r, v) = self.propagate(tsince)?;
+        // TEME is quasi-inertial; no transport term for the velocity
+        let teme_to_eci = gcrf_to_teme(time / SEC_PER_CENTURY).reversed_axes();
+        Ok((
+            teme_to_eci.dot(&array![[r[0]], [r[1]], [r[2]]]) * 1e3,
+            teme_to_eci.dot(&array![[v[0]], [v[1]], [v[2]]]) * 1e3,
+        ))
+    }
+
+    /// TEME position [km] and velocity [km/s] at `tsince` minutes past the
+    /// element set epoch.
+    pub fn propagate(&self, tsince: f64) -> Result<([f64; 3], [f64; 3]), Sgp4Error> {
+        let xke = xke();
+        let j3oj2 = J3_WGS72 / J2_WGS72;
+        let vkmpersec = RE_WGS72 * xke / 60.;
+        let t = tsince;
+
+        // Secular gravity and atmospheric drag
+        let xmdf = self.mo + self.mdot * t;
+        let argpdf = self.argpo + self.argpdot * t;
+        let nodedf = self.nodeo + self.nodedot * t;
+        let mut argpm = argpdf;
+        let mut mm = xmdf;
+        let t2 = t * t;
+        let mut nodem = nodedf + self.nodecf * t2;
+        let mut tempa = 1. - self.cc1 * t;
+        let mut tempe = self.bstar * self.cc4 * t;
+        let mut templ = self.t2cof * t2;
+
+        if !self.isimp {
+            let delomg = self.omgcof * t;
+            let delm = self.xmcof * ((1. + self.eta * xmdf.cos()).powi(3) - self.delmo);
+            let temp = delomg + delm;
+            mm = xmdf + temp;
+            argpm = argpdf - temp;
+            let t3 = t2 * t;
+            let t4 = t3 * t;
+            tempa = tempa - self.d2 * t2 - self.d3 * t3 - self.d4 * t4;
+            tempe += self.bstar * self.cc5 * (mm.sin() - self.sinmao);
+            templ += self.t3cof * t3 + t4 * (self.t4cof + t * self.t5cof);
+        }
+
+        let mut nm = self.no;
+        let mut em = self.ecco;
+        let mut inclm = self.inclo;
+        if let Some(ds) = &self.deep_space {
+            (em, argpm, inclm, mm, nodem, nm) = dspace(
+                ds,
+                self.argpo,
+                self.argpdot,
+                t,
+                self.gsto,
+                self.no,
+                em,
+                argpm,
+                inclm,
+                mm,
+                nodem,
+            );
+        }
+        if nm <= 0. {
+            return Err(Sgp4Error::MeanMotion(nm));
+        }
+        let am = (xke / nm).powf(X2O3) * tempa * tempa;
+        nm = xke / am.powf(1.5);
+        em -= tempe;
+        if !(-0.001..1.).contains(&em) {
+            return Err(Sgp4Error::Eccentricity(em));
+        }
+        em = em.max(1e-6);
+        mm += self.no * templ;
+        let xlm = mm + argpm + nodem;
+        nodem %= TWO_PI;
+        argpm %= TWO_PI;
+        let xlm = xlm % TWO_PI;
+        mm = (xlm - argpm - nodem) % TWO_PI;
+
+        // Lunisolar periodics
+        let (mut ep, mut xincp, mut nodep, mut argpp, mut mp) = (em, inclm, nodem, argpm, mm);
+        let (mut aycof, mut xlcof) = (self.aycof, self.xlcof);
+        let (mut con41, mut x1mth2, mut x7thm1) = (self.con41, self.x1mth2, self.x7thm1);
+        if let Some(ds) = &self.deep_space {
+            (ep, xincp, nodep, argpp, mp) = dpper(ds, t, ep, xincp, nodep, argpp, mp);
+            if xincp < 0. {
+                xincp = -xincp;
+                nodep += PI;
+                argpp -= PI;
+            }
+            if !(0. ..=1.).contains(&ep) {
+                return Err(Sgp4Error::PerturbedEccentricity(ep));
+            }
+            let (sinip, cosip) = xincp.sin_cos();
+            aycof = -0.5 * j3oj2 * sinip;
+            let den = if (cosip + 1.).abs() > 1.5e-12 {
+                1. + cosip
+            } else {
+                1.5e-12
+            };
+            xlcof = -0.25 * j3oj2 * sinip * (3. + 5. * cosip) / den;
+            let cosisq = cosip * cosip;
+            con41 = 3. * cosisq - 1.;
+            x1mth2 = 1. - cosisq;
+            x7thm1 = 7. * cosisq - 1.;
+        }
+
+        // Long-period periodics
+        let axnl = ep * argpp.cos();
+        let temp = 1. / (am * (1. - ep * ep));
+        let aynl = ep * argpp.sin() + temp * aycof;
+        let xl = mp + argpp + nodep + temp * xlcof * axnl;
+
+        // Kepler's equation in equinoctial form
+        let u = (xl - nodep) % TWO_PI;
+        let mut eo1 = u;
+        let mut tem5: f64 = 9999.9;
+        let (mut sineo1, mut coseo1) = (0., 0.);
+        let mut ktr = 1;
+        while tem5.abs() >= 1e-12 && ktr <= 10 {
+            (sineo1, coseo1) = eo1.sin_cos();
+            tem5 = 1. - coseo1 * axnl - sineo1 * aynl;
+            tem5 = (u - aynl * coseo1 + axnl * sineo1 - eo1) / tem5;
+            if tem5.abs() >= 0.95 {
+                tem5 = 0.95 * tem5.signum();
+            }
+            eo1 += tem5;
+            ktr += 1;
+        }
+
+        // Short-period periodics
+        let ecose = axnl * coseo1 + aynl * sineo1;
+        let esine = axnl * sineo1 - aynl * coseo1;
+        let el2 = axnl * axnl + aynl * aynl;
+        let pl = am * (1. - el2);
+        if pl < 0. {
+            return Err(Sgp4Error::SemiLatusRectum(pl));
+        }
+        let rl = am * (1. - ecose);
+        let rdotl = am.sqrt() * esine / rl;
+        let rvdotl = pl.sqrt() / rl;
+        let betal = (1. - el2).sqrt();
+        let temp = esine / (1. + betal);
+        let sinu = am / rl * (sineo1 - aynl - axnl * temp);
+        let cosu = am / rl * (coseo1 - axnl + aynl * temp);
+        let mut su = sinu.atan2(cosu);
+        let sin2u = (cosu + cosu) * sinu;
+        let cos2u = 1. - 2. * sinu * sinu;
+        let temp = 1. / pl;
+        let temp1 = 0.5 * J2_WGS72 * temp;
+        let temp2 = temp1 * temp;
+
+        let (sinip, cosip) = xincp.sin_cos();
+        let mrt = rl * (1. - 1.5 * temp2 * betal * con41) + 0.5 * temp1 * x1mth2 * cos2u;
+        su -= 0.25 * temp2 * x7thm1 * sin2u;
+        let xnode = nodep + 1.5 * temp2 * cosip * sin2u;
+        let xinc = xincp + 1.5 * temp2 * cosip * sinip * cos2u;
+        let mvt = rdotl - nm * temp1 * x1mth2 * sin2u / xke;
+        let rvdot = rvdotl + nm * temp1 * (x1mth2 * cos2u + 1.5 * con41) / xke;
+
+        // Orientation vectors
+        let (sinsu, cossu) = su.sin_cos();
+        let (snod, cnod) = xnode.sin_cos();
+        let (sini, cosi) = xinc.sin_cos();
+        let xmx = -snod * cosi;
+        let xmy = cnod * cosi;
+        let uu = [
+            xmx * sinsu + cnod * cossu,
+            xmy * sinsu + snod * cossu,
+            sini * sinsu,
+        ];
+        let vv = [
+            xmx * cossu - cnod * sinsu,
+            xmy * cossu - snod * sinsu,
+            sini * cossu,
+        ];
+
+        if mrt < 1. {
+            return Err(Sgp4Error::Decayed(mrt * RE_WGS72));
+        }
+        let r = uu.map(|x| mrt * x * RE_WGS72);
+        let v = [0, 1, 2].map(|k| (mvt * uu[k] + rvdot * vv[k]) * vkmpersec);
+        Ok((r, v))
+    }
+}
+
+fn dscom(
+    epoch: f64,
+    ep: f64,
+    argpp: f64,
+    tc: f64,
+    inclp: f64,
+    nodep: f64,
+    np: f64,
+) -> (DeepSpace, DscomTerms) {
+    // Lunar and solar perturbation coefficients
+    let zes = 0.01675;
+    let zel = 0.05490;
+    let c1ss = 2.9864797e-6;
+    let c1l = 4.7968065e-7;
+    let zsinis = 0.39785416;
+    let zcosis = 0.91744867;
+    let zcosgs = 0.1945905;
+    let zsings = -0.98088458;
+
+    let (snodm, cnodm) = nodep.sin_cos();
+    let (sinomm, cosomm) = argpp.sin_cos();
+    let (sinim, cosim) = inclp.sin_cos();
+    let emsq = ep * ep;
+    let betasq = 1. - emsq;
+    let rtemsq = betasq.sqrt();
+
+    let day = epoch + 18261.5 + tc / 1440.;
+    let xnodce = (4.5236020 - 9.2422029e-4 * day) % TWO_PI;
+    let (stem, ctem) = xnodce.sin_cos();
+    let zcosil = 0.91375164 - 0.03568096 * ctem;
+    let zsinil = (1. - zcosil * zcosil).sqrt();
+    let zsinhl = 0.089683511 * stem / zsinil;
+    let zcoshl = (1. - zsinhl * zsinhl).sqrt();
+    let gam = 5.8351514 + 0.0019443680 * day;
+    let zx = 0.39785416 * stem / zsinil;
+    let zy = zcoshl * ctem + 0.91744867 * zsinhl * stem;
+    let zx = gam + zx.atan2(zy) - xnodce;
+    let (zsingl, zcosgl) = zx.sin_cos();
+
+    let (mut zcosg, mut zsing, mut zcosi, mut zsini) = (zcosgs, zsings, zcosis, zsinis);
+    let (mut zcosh, mut zsinh) = (cnodm, snodm);
+    let mut cc = c1ss;
+    let xnoi = 1. / np;
+
+    let mut sun = ([0.; 7], [0.; 12]);
+    let mut moon = ([0.; 7], [0.; 12]);
+    for lsflg in 0..2 {
+        let a1 = zcosg * zcosh + zsing * zcosi * zsinh;
+        let a3 = -zsing * zcosh + zcosg * zcosi * zsinh;
+        let a7 = -zcosg * zsinh + zsing * zcosi * zcosh;
+        let a8 = zsing * zsini;
+        let a9 = zsing * zsinh + zcosg * zcosi * zcosh;
+        let a10 = zcosg * zsini;
+        let a2 = cosim * a7 + sinim * a8;
+        let a4 = cosim * a9 + sinim * a10;
+        let a5 = -sinim * a7 + cosim * a8;
+        let a6 = -sinim * a9 + cosim * a10;
+
+        let x1 = a1 * cosomm + a2 * sinomm;
+        let x2 = a3 * cosomm + a4 * sinomm;
+        let x3 = -a1 * sinomm + a2 * cosomm;
+        let x4 = -a3 * sinomm + a4 * cosomm;
+        let x5 = a5 * sinomm;
+        let x6 = a6 * sinomm;
+        let x7 = a5 * cosomm;
+        let x8 = a6 * cosomm;
+
+        let z31 = 12. * x1 * x1 - 3. * x3 * x3;
+        let z32 = 24. * x1 * x2 - 6. * x3 * x4;
+        let z33 = 12. * x2 * x2 - 3. * x4 * x4;
+        let mut z1 = 3. * (a1 * a1 + a2 * a2) + z31 * emsq;
+        let mut z2 = 6. * (a1 * a3 + a2 * a4) + z32 * emsq;
+        let mut z3 = 3. * (a3 * a3 + a4 * a4) + z33 * emsq;
+        let z11 = -6. * a1 * a5 + emsq * (-24. * x1 * x7 - 6. * x3 * x5);
+        let z12 = -6. * (a1 * a6 + a3 * a5)
+            + emsq * (-24. * (x2 * x7 + x1 * x8) - 6. * (x3 * x6 + x4 * x5));
+        let z13 = -6. * a3 * a6 + emsq * (-24. * x2 * x8 - 6. * x4 * x6);
+        let z21 = 6. * a2 * a5 + emsq * (24. * x1 * x5 - 6. * x3 * x7);
+        let z22 = 6. * (a4 * a5 + a2 * a6)
+            + emsq * (24. * (x2 * x5 + x1 * x6) - 6. * (x4 * x7 + x3 * x8));
+        let z23 = 6. * a4 * a6 + emsq * (24. * x2 * x6 - 6. * x4 * x8);
+        z1 = z1 + z1 + betasq * z31;
+        z2 = z2 + z2 + betasq * z32;
+        z3 = z3 + z3 + betasq * z33;
+
+        let s3 = cc * xnoi;
+        let s2 = -0.5 * s3 / rtemsq;
+        let s4 = s3 * rtemsq;
+        let s1 = -15. * ep * s4;
+        let s5 = x1 * x3 + x2 * x4;
+        let s6 = x2 * x3 + x1 * x4;
+        let s7 = x2 * x4 - x1 * x3;
+
+        let terms = (
+            [s1, s2, s3, s4, s5, s6, s7],
+            [z1, z2, z3, z11, z12, z13, z21, z22, z23, z31, z32, z33],
+        );
+        if lsflg == 0 {
+            sun = terms;
+            zcosg = zcosgl;
+            zsing = zsingl;
+            zcosi = zcosil;
+            zsini = zsinil;
+            zcosh = zcoshl * cnodm + zsinhl * snodm;
+            zsinh = snodm * zcoshl - cnodm * zsinhl;
+            cc = c1l;
+        } else {
+            moon = terms;
+        }
+    }
+
+    let (ss, sz) = sun;
+    let (s, z) = moon;
+    let ds = DeepSpace {
+        zmol: (4.7199672 + 0.22997150 * day - gam) % TWO_PI,
+        zmos: (6.2565837 + 0.017201977 * day) % TWO_PI,
+        // Solar terms
+        se2: 2. * ss[0] * ss[5],
+        se3: 2. * ss[0] * ss[6],
+        si2: 2. * ss[1] * sz[4],
+        si3: 2. * ss[1] * (sz[5] - sz[3]),
+        sl2: -2. * ss[2] * sz[1],
+        sl3: -2. * ss[2] * (sz[2] - sz[0]),
+        sl4: -2. * ss[2] * (-21. - 9. * emsq) * zes,
+        sgh2: 2. * ss[3] * sz[10],
+        sgh3: 2. * ss[3] * (sz[11] - sz[9]),
+        sgh4: -18. * ss[3] * zes,
+        sh2: -2. * ss[1] * sz[7],
+        sh3: -2. * ss[1] * (sz[8] - sz[6]),
+        // Lunar terms
+        ee2: 2. * s[0] * s[5],
+        e3: 2. * s[0] * s[6],
+        xi2: 2. * s[1] * z[4],
+        xi3: 2. * s[1] * (z[5] - z[3]),
+        xl2: -2. * s[2] * z[1],
+        xl3: -2. * s[2] * (z[2] - z[0]),
+        xl4: -2. * s[2] * (-21. - 9. * emsq) * zel,
+        xgh2: 2. * s[3] * z[10],
+        xgh3: 2. * s[3] * (z[11] - z[9]),
+        xgh4: -18. * s[3] * zel,
+        xh2: -2. * s[1] * z[7],
+        xh3: -2. * s[1] * (z[8] - z[6]),
+        ..Default::default()
+    };
+    let terms = DscomTerms {
+        inclm: inclp,
+        sinim,
+        cosim,
+        emsq,
+        s: [s[0], s[1], s[2], s[3], s[4]],
+        ss: [ss[0], ss[1], ss[2], ss[3], ss[4]],
+        sz,
+        z,
+    };
+    (ds, terms)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn dpper(
+    ds: &DeepSpace,
+    t: f64,
+    mut ep: f64,
+    mut inclp: f64,
+    mut nodep: f64,
+    mut argpp: f64,
+    mut mp: f64,
+) -> (f64, f64, f64, f64, f64) {
+    // Lunisolar periodic terms
+    let zns = 1.19459e-5;
+    let zes = 0.01675;
+    let znl = 1.5835218e-4;
+    let zel = 0.05490;
+
+    let zm = ds.zmos + zns * t;
+    let zf = zm + 2. * zes * zm.sin();
+    let sinzf = zf.sin();
+    let f2 = 0.5 * sinzf * sinzf - 0.25;
+    let f3 = -0.5 * sinzf * zf.cos();
+    let ses = ds.se2 * f2 + ds.se3 * f3;
+    let sis = ds.si2 * f2 + ds.si3 * f3;
+    let sls = ds.sl2 * f2 + ds.sl3 * f3 + ds.sl4 * sinzf;
+    let sghs = ds.sgh2 * f2 + ds.sgh3 * f3 + ds.sgh4 * sinzf;
+    let shs = ds.sh2 * f2 + ds.sh3 * f3;
+
+    let zm = ds.zmol + znl * t;
+    let zf = zm + 2. * zel * zm.sin();
+    let sinzf = zf.sin();
+    let f2 = 0.5 * sinzf * sinzf - 0.25;
+    let f3 = -0.5 * sinzf * zf.cos();
+    let sel = ds.ee2 * f2 + ds.e3 * f3;
+    let sil = ds.xi2 * f2 + ds.xi3 * f3;
+    let sll = ds.xl2 * f2 + ds.xl3 * f3 + ds.xl4 * sinzf;
+    let sghl = ds.xgh2 * f2 + ds.xgh3 * f3 + ds.xgh4 * sinzf;
+    let shll = ds.xh2 * f2 + ds.xh3 * f3;
+
+    // Offsets at epoch (peo, ...) are zero in the operational model
+    let pe = ses + sel - ds.peo;
+    let pinc = sis + sil - ds.pinco;
+    let pl = sls + sll - ds.plo;
+    let mut pgh = sghs + sghl - ds.pgho;
+    let mut ph = shs + shll - ds.pho;
+
+    inclp += pinc;
+    ep += pe;
+    let (sinip, cosip) = inclp.sin_cos();
+    if inclp >= 0.2 {
+        ph /= sinip;
+        pgh -= cosip * ph;
+        argpp += pgh;
+        nodep += ph;
+        mp += pl;
+    } else {
+        // Lyddane modification for low inclinations
+        let (sinop, cosop) = nodep.sin_cos();
+        let mut alfdp = sinip * sinop;
+        let mut betdp = sinip * cosop;
+        let dalf = ph * cosop + pinc * cosip * sinop;
+        let dbet = -ph * sinop + pinc * cosip * cosop;
+        alfdp += dalf;
+        betdp += dbet;
+        nodep %= TWO_PI;
+        if nodep < 0. {
+            nodep += TWO_PI;
+        }
+        let mut xls = mp + argpp + cosip * nodep;
+        let dls = pl + pgh - pinc * nodep * sinip;
+        xls += dls;
+        let xnoh = nodep;
+        nodep = alfdp.atan2(betdp);
+        if nodep < 0. {
+            nodep += TWO_PI;
+        }
+        if (xnoh - nodep).abs() > PI {
+            if nodep < xnoh {
+                nodep += TWO_PI;
+            } else {
+                nodep -= TWO_PI;
+            }
+        }
+        mp += pl;
+        argpp = xls - mp - cosip * nodep;
+    }
+    (ep, inclp, nodep, argpp, mp)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn dsinit(
+    ds: &mut DeepSpace,
+    terms: &DscomTerms,
+    xke: f64,
+    argpo: f64,
+    tc: f64,
+    gsto: f64,
+    mo: f64,
+    mdot: f64,
+    no: f64,
+    nodeo: f64,
+    nodedot: f64,
+    xpidot: f64,
+    ecco: f64,
+    eccsq: f64,
+) {
+    // Deep-space secular rates and resonance coefficients
+    let q22 = 1.7891679e-6;
+    let q31 = 2.1460748e-6;
+    let q33 = 2.2123015e-7;
+    let root22 = 1.7891679e-6;
+    let root44 = 7.3636953e-9;
+    let root54 = 2.1765803e-9;
+    let rptim = 4.3752690880113e-3;
+    let root32 = 3.7393792e-7;
+    let root52 = 1.1428639e-7;
+    let znl = 1.5835218e-4;
+    let zns = 1.19459e-5;
+
+    let DscomTerms {
+        sinim,
+        cosim,
+        emsq,
+        s,
+        ss,
+        sz,
+        z,
+        ..
+    } = terms;
+    let (sinim, cosim, emsq) = (*sinim, *cosim, *emsq);
+    let (nm, em, inclm) = (no, ecco, terms.inclm);
+
+    ds.irez = 0;
+    if nm < 0.0052359877 && nm > 0.0034906585 {
+        ds.irez = 1;
+    }
+    if (8.26e-3..=9.24e-3).contains(&nm) && em >= 0.5 {
+        ds.irez = 2;
+    }
+
+    // Solar terms
+    let ses = ss[0] * zns * ss[4];
+    let sis = ss[1] * zns * (sz[3] + sz[5]);
+    let sls = -zns * ss[2] * (sz[0] + sz[2] - 14. - 6. * emsq);
+    let sghs = ss[3] * zns * (sz[9] + sz[11] - 6.);
+    let mut shs = -zns * ss[1] * (sz[6] + sz[8]);
+    if !(5.2359877e-2..=PI - 5.2359877e-2).contains(&inclm) {
+        shs = 0.;
+    }
+    if sinim != 0. {
+        shs /= sinim;
+    }
+    let sgs = sghs - cosim * shs;
+
+    // Lunar terms
+    ds.dedt = ses + s[0] * znl * s[4];
+    ds.didt = sis + s[1] * znl * (z[3] + z[5]);
+    ds.dmdt = sls - znl * s[2] * (z[0] + z[2] - 14. - 6. * emsq);
+    let sghl = s[3] * znl * (z[9] + z[11] - 6.);
+    let mut shll = -znl * s[1] * (z[6] + z[8]);
+    if !(5.2359877e-2..=PI - 5.2359877e-2).contains(&inclm) {
+        shll = 0.;
+    }
+    ds.domdt = sgs + sghl;
+    ds.dnodt = shs;
+    if sinim != 0. {
+        ds.domdt -= cosim / sinim * shll;
+        ds.dnodt += shll / sinim;
+    }
+
+    // Geopotential resonance
+    let theta = (gsto + tc * rptim) % TWO_PI;
+    if ds.irez == 0 {
+        return;
+    }
+    let aonv = (nm / xke).powf(X2O3);
+    if ds.irez == 2 {
+        // Half-day resonance, evaluated at the epoch eccentricity
+        let cosisq = cosim * cosim;
+        let em = ecco;
+        let emsq = eccsq;
+        let eoc = em * emsq;
+        let g201 = -0.306 - (em - 0.64) * 0.440;
+        let (g211, g310, g322, g410, g422, g520);
+        if em <= 0.65 {
+            g211 = 3.616 - 13.2470 * em + 16.2900 * emsq;
+            g310 = -19.302 + 117.3900 * em - 228.4190 * emsq + 156.5910 * eoc;
+            g322 = -18.9068 + 109.7927 * em - 214.6334 * emsq + 146.5816 * eoc;
+            g410 = -41.122 + 242.6940 * em - 471.0940 * emsq + 313.9530 * eoc;
+            g422 = -146.407 + 841.8800 * em - 1629.014 * emsq + 1083.4350 * eoc;
+            g520 = -532.114 + 3017.977 * em - 5740.032 * emsq + 3708.2760 * eoc;
+        } else {
+            g211 = -72.099 + 331.819 * em - 508.738 * emsq + 266.724 * eoc;
+            g310 = -346.844 + 1582.851 * em - 2415.925 * emsq + 1246.113 * eoc;
+            g322 = -342.585 + 1554.908 * em - 2366.899 * emsq + 1215.972 * eoc;
+            g410 = -1052.797 + 4758.686 * em - 7193.992 * emsq + 3651.957 * eoc;
+            g422 = -3581.690 + 16178.110 * em - 24462.770 * emsq + 12422.520 * eoc;
+            g520 = if em > 0.715 {
+                -5149.66 + 29936.92 * em - 54087.36 * emsq + 31324.56 * eoc
+            } else {
+                1464.74 - 4664.75 * em + 3763.64 * emsq
+            };
+        }
+        let (g533, g521, g532);
+        if em < 0.7 {
+            g533 = -919.22770 + 4988.6100 * em - 9064.7700 * emsq + 5542.21 * eoc;
+            g521 = -822.71072 + 4568.6173 * em - 8491.4146 * emsq + 5337.524 * eoc;
+            g532 = -853.66600 + 4690.2500 * em - 8624.7700 * emsq + 5341.4 * eoc;
+        } else {
+            g533 = -37995.780 + 161616.52 * em - 229838.20 * emsq + 109377.94 * eoc;
+            g521 = -51752.104 + 218913.95 * em - 309468.16 * emsq + 146349.42 * eoc;
+            g532 = -40023.880 + 170470.89 * em - 242699.48 * emsq + 115605.82 * eoc;
+        }
+
+        let sini2 = sinim * sinim;
+        let f220 = 0.75 * (1. + 2. * cosim + cosisq);
+        let f221 = 1.5 * sini2;
+        let f321 = 1.875 * sinim * (1. - 2. * cosim - 3. * cosisq);
+        let f322 = -1.875 * sinim * (1. + 2. * cosim - 3. * cosisq);
+        let f441 = 35. * sini2 * f220;
+        let f442 = 39.3750 * sini2 * sini2;
+        let f522 = 9.84375
+            * sinim
+            * (sini2 * (1. - 2. * cosim - 5. * cosisq)
+                + 0.33333333 * (-2. + 4. * cosim + 6. * cosisq));
+        let f523 = sinim
+            * (4.92187512 * sini2 * (-2. - 4. * cosim + 10. * cosisq)
+                + 6.56250012 * (1. + 2. * cosim - 3. * cosisq));
+        let f542 =
+            29.53125 * sinim * (2. - 8. * cosim + cosisq * (-12. + 8. * cosim + 10. * cosisq));
+        let f543 =
+            29.53125 * sinim * (-2. - 8. * cosim + cosisq * (12. + 8. * cosim - 10. * cosisq));
+
+        let xno2 = nm * nm;
+        let ainv2 = aonv * aonv;
+        let mut temp1 = 3. * xno2 * ainv2;
+        let mut temp = temp1 * root22;
+        ds.d2201 = temp * f220 * g201;
+        ds.d2211 = temp * f221 * g211;
+        temp1 *= aonv;
+        temp = temp1 * root32;
+        ds.d3210 = temp * f321 * g310;
+        ds.d3222 = temp * f322 * g322;
+        temp1 *= aonv;
+        temp = 2. * temp1 * root44;
+        ds.d4410 = temp * f441 * g410;
+        ds.d4422 = temp * f442 * g422;
+        temp1 *= aonv;
+        temp = temp1 * root52;
+        ds.d5220 = temp * f522 * g520;
+        ds.d5232 = temp * f523 * g532;
+        temp = 2. * temp1 * root54;
+        ds.d5421 = temp * f542 * g521;
+        ds.d5433 = temp * f543 * g533;
+        ds.xlamo = (mo + nodeo + nodeo - theta - theta) % TWO_PI;
+        ds.xfact = mdot + ds.dmdt + 2. * (nodedot + ds.dnodt - rptim) - no;
+    } else {
+        // One-day (synchronous) resonance
+        let g200 = 1. + emsq * (-2.5 + 0.8125 * emsq);
+        let g310 = 1. + 2. * emsq;
+        let g300 = 1. + emsq * (-6. + 6.60937 * emsq);
+        let f220 = 0.75 * (1. + cosim) * (1. + cosim);
+        let f311 = 0.9375 * sinim * sinim * (1. + 3. * cosim) - 0.75 * (1. + cosim);
+        let f330 = 1.875 * (1. + cosim).powi(3);
+        let del1 = 3. * nm * nm * aonv * aonv;
+        ds.del2 = 2. * del1 * f220 * g200 * q22;
+        ds.del3 = 3. * del1 * f330 * g300 * q33 * aonv;
+        ds.del1 = del1 * f311 * g310 * q31 * aonv;
+        ds.xlamo = (mo + nodeo + argpo - theta) % TWO_PI;
+        ds.xfact = mdot + xpidot - rptim + ds.dmdt + ds.domdt + ds.dnodt - no;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn dspace(
+    ds: &DeepSpace,
+    argpo: f64,
+    argpdot: f64,
+    t: f64,
+    gsto: f64,
+    no: f64,
+    em: f64,
+    argpm: f64,
+    inclm: f64,
+    mm: f64,
+    nodem: f64,
+) -> (f64, f64, f64, f64, f64, f64) {
+    /*
+    Deep-space secular effects and resonance integration
+
+    The resonance integrator restarts from epoch on every call with fixed
+    720 min steps, which reproduces the cached-integrator results exactly.
+
+    Outputs:
+    em, argpm, inclm, mm, nodem, nm
+    */
+    let fasx2 = 0.13130908;
+    let fasx4 = 2.8843198;
+    let fasx6 = 0.37448087;
+    let g22 = 5.7686396;
+    let g32 = 0.95240898;
+    let g44 = 1.8014998;
+    let g52 = 1.0508330;
+    let g54 = 4.4108898;
+    let rptim = 4.3752690880113e-3;
+    let stepp = 720.;
+    let stepn = -720.;
+    let step2 = 259200.;
+
+    let theta = (gsto + t * rptim) % TWO_PI;
+    let em = em + ds.dedt * t;
+    let inclm = inclm + ds.didt * t;
+    let argpm = argpm + ds.domdt * t;
+    let nodem = nodem + ds.dnodt * t;
+    let mut mm = mm + ds.dmdt * t;
+    let mut nm = no;
+
+    if ds.irez != 0 {
+        let mut atime = 0.;
+        let mut xni = no;
+        let mut xli = ds.xlamo;
+        let delt = if t > 0. { stepp } else { stepn };
+        let (xndt, xldot, xnddt) = loop {
+            let (xndt, xldot, mut xnddt);
+            if ds.irez != 2 {
+                xndt = ds.del1 * (xli - fasx2).sin()
+                    + ds.del2 * (2. * (xli - fasx4)).sin()
+                    + ds.del3 * (3. * (xli - fasx6)).sin();
+                xldot = xni + ds.xfact;
+                xnddt = ds.del1 * (xli - fasx2).cos()
+                    + 2. * ds.del2 * (2. * (xli - fasx4)).cos()
+                    + 3. * ds.del3 * (3. * (xli - fasx6)).cos();
+            } else {
+                let xomi = argpo + argpdot * atime;
+                let x2omi = xomi + xomi;
+                let x2li = xli + xli;
+                xndt = ds.d2201 * (x2omi + xli - g22).sin()
+                    + ds.d2211 * (xli - g22).sin()
+                    + ds.d3210 * (xomi + xli - g32).sin()
+                    + ds.d3222 * (-xomi + xli - g32).sin()
+                    + ds.d4410 * (x2omi + x2li - g44).sin()
+                    + ds.d4422 * (x2li - g44).sin()
+                    + ds.d5220 * (xomi + xli - g52).sin()
+                    + ds.d5232 * (-xomi + xli - g52).sin()
+                    + ds.d5421 * (xomi + x2li - g54).sin()
+                    + ds.d5433 * (-xomi + x2li - g54).sin();
+                xldot = xni + ds.xfact;
+                xnddt = ds.d2201 * (x2omi + xli - g22).cos()
+                    + ds.d2211 * (xli - g22).cos()
+                    + ds.d3210 * (xomi + xli - g32).cos()
+                    + ds.d3222 * (-xomi + xli - g32).cos()
+                    + ds.d5220 * (xomi + xli - g52).cos()
+                    + ds.d5232 * (-xomi + xli - g52).cos()
+                    + 2. * (ds.d4410 * (x2omi + x2li - g44).cos()
+                        + ds.d4422 * (x2li - g44).cos()
+                        + ds.d5421 * (xomi + x2li - g54).cos()
+                        + ds.d5433 * (-xomi + x2li - g54).cos());
+            }
+            xnddt *= xldot;
+            if (t - atime).abs() < stepp {
+                break (xndt, xldot, xnddt);
+            }
+            xli += xldot * delt + xndt * step2;
+            xni += xndt * delt + xnddt * step2;
+            atime += delt;
+        };
+        let ft = t - atime;
+        nm = xni + xndt * ft + xnddt * ft * ft * 0.5;
+        let xl = xli + xldot * ft + xndt * ft * ft * 0.5;
+        mm = if ds.irez != 1 {
+            xl - 2. * nodem + 2. * theta
+        } else {
+            xl - nodem - argpm + theta
+        };
+    }
+    (em, argpm, inclm, mm, nodem, nm)
+}
@@ -0,0 +1,119 @@
+use altai_rs::meta::types::Generic1D;
+use ndarray::{array, ArrayView1};
+
+use crate::ephemeris::celestial::AU;
+use crate::ephemeris::types::ForceModelError;
+
+// Solar radiation pressure at 1 AU [N/m2]
+pub const P_SRP_AU: f64 = 4.56e-6;
+
+/// Flat surface with optical properties; position used for torque.
+#[derive(Debug, Clone)]
+pub struct SrpPlate {
+    pub normal_body: [f64; 3], // Outward unit normal (Body) [-]
+    pub center_body: [f64; 3], // Center of pressure (Body) [m]
+    pub area: f64,             // [m2]
+    pub specular: f64,         // Specular reflectivity [-]
+    pub diffuse: f64,          // Diffuse reflectivity [-]
+}
+
+#[derive(Debug, Clone)]
+pub enum SrpModel {
+    // Sphere with radiation pressure coefficient cr; produces no torque
+    Cannonball { cr: f64, area: f64 },
+    Plates(Vec<SrpPlate>),
+}
+impl Default for SrpModel {
+    fn default() -> Self {
+        Self::Cannonball { cr: 1.3, area: 1. }
+    }
+}
+
+impl SrpModel {
+    pub fn validate(&self) -> Result<(), ForceModelError> {
+        match self {
+            // Cr = 1 absorbs, Cr = 2 reflects everything straight back
+            Self::Cannonball { cr, area } => {
+                ForceModelError::check_range("srp", "cr", *cr, 1., 2.)?;
+                ForceModelError::check_positive("srp", "area", *area)
+            }
+            Self::Plates(plates) => plates.iter().try_for_each(|plate| {
+                ForceModelError::check_unit_normal("srp", &plate.normal_body)?;
+                ForceModelError::check_positive("srp", "area", plate.area)?;
+                ForceModelError::check_non_negative("srp", "specular", plate.specular)?;
+                ForceModelError::check_non_negative("srp", "diffuse", plate.diffuse)?;
+                if plate.specular + plate.diffuse > 1. {
+                    return Err(ForceModelError::ReflectivityAboveUnity {
+                        specular: plate.specular,
+                        diffuse: plate.diffuse,
+                    });
+                }
+                Ok(())
+            }),
+        }
+    }
+
+    pub fn force_torque_body(
+        &self,
+        sun_hat_body: &ArrayView1<f64>,
+        sun_dist: f64,
+        shadow: f64,
+        r_cm_body: &ArrayView1<f64>,
+    ) -> (Generic1D, Generic1D) {
+        /*
+        SRP force and torque about the CoM; Montenbruck & Gill 3.75
+
+        Inputs:
+        sun_hat_body: Spacecraft-to-Sun unit vector (Body) [-]
+        sun_dist: Spacecraft-to-Sun distance [m]
+        shadow: Visible fraction of the solar disk [-]
+        r_cm_body: Center of mass (Body) [m]
+
+        Outputs:
+        Force (Body) [N], Torque (Body) [N m]
+        */
+        let pressure = shadow * P_SRP_AU * (AU / sun_dist).powi(2);
+        let mut force = Generic1D::zeros(3);
+        let mut torque = Generic1D::zeros(3);
+        if pressure <= 0. {
+            return (force, torque);
+        }
+
+        match self {
+            Self::Cannonball { cr, area } => {
+                force = -pressure * cr * area * sun_hat_body.to_owned();
+            }
+            Self::Plates(plates) => {
+                for plate in plates {
+                    let normal = array![
+                        plate.normal_body[0],
+                        plate.normal_body[1],
+                        plate.normal_body[2]
+                    ];
+                    let cos_inc = normal.dot(sun_hat_body);
+                    if cos_inc <= 0. {
+                        continue;
+                    }
+                    let f_plate = -pressure
+                        * plate.area
+                        * cos_inc
+                        * ((1. - plate.specular) * sun_hat_body.to_owned()
+                            + 2. * (plate.specular * cos_inc + plate.diffuse / 3.) * normal);
+                    let arm = array![
+                        plate.center_body[0] - r_cm_body[0],
+                        plate.center_body[1] - r_cm_body[1],
+                        plate.center_body[2] - r_cm_body[2]
+                    ];
+                    torque = torque
+                        + array![
+                            arm[1] * f_plate[2] - arm[2] * f_plate[1],
+                            arm[2] * f_plate[0] - arm[0] * f_plate[2],
+                            arm[0] * f_plate[1] - arm[1] * f_plate[0]
+                        ];
+                    force = force + f_plate;
+                }
+            }
+        }
+        (force, torque)
+    }
+}
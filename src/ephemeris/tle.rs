@@ -0,0 +1,306 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use crate::time::types::{gregorian_to_jd, Epoch, TimeScale};
+
+#[derive(Debug)]
+pub enum TleError {
+    Io(std::io::Error),
+    Format {
+        line: usize,
+        msg: String,
+    },
+    Checksum {
+        line: usize,
+        expected: u32,
+        found: u32,
+    },
+    MissingField(&'static str),
+}
+impl fmt::Display for TleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read element set: {err}"),
+            Self::Format { line, msg } => write!(f, "element set line {line}: {msg}"),
+            Self::Checksum {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "TLE line {line}: checksum {found} does not match computed {expected}"
+            ),
+            Self::MissingField(key) => write!(f, "OMM is missing `{key}`"),
+        }
+    }
+}
+impl std::error::Error for TleError {}
+impl From<std::io::Error> for TleError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// SGP4 mean element set, read from a TLE/3LE or a CCSDS OMM.
+/// Angles in [rad], mean motion in [rad/min] (Kozai convention).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tle {
+    pub name: Option<String>,
+    pub catalog_number: u32,
+    pub epoch: Epoch,          // UTC epoch of the element set
+    pub mean_motion_dot: f64,  // First derivative of mean motion / 2 [rad/min2]
+    pub mean_motion_ddot: f64, // Second derivative of mean motion / 6 [rad/min3]
+    pub bstar: f64,            // Drag term [1/earth radii]
+    pub inclination: f64,      // [rad]
+    pub raan: f64,             // [rad]
+    pub eccentricity: f64,     // [-]
+    pub arg_perigee: f64,      // [rad]
+    pub mean_anomaly: f64,     // [rad]
+    pub mean_motion: f64,      // [rad/min]
+    pub revolution_number: u32,
+}
+
+// [rev/day] -> [rad/min]
+const REV_PER_DAY: f64 = 2. * std::f64::consts::PI / 1440.;
+
+fn field(line: &str, range: std::ops::Range<usize>, idx: usize) -> Result<&str, TleError> {
+    line.get(range.clone())
+        .map(str::trim)
+        .ok_or_else(|| TleError::Format {
+            line: idx,
+            msg: format!("too short for columns {}-{}", range.start + 1, range.end),
+        })
+}
+
+fn parse<T: std::str::FromStr>(token: &str, idx: usize) -> Result<T, TleError>
+where
+    T::Err: fmt::Display,
+{
+    token.parse::<T>().map_err(|err| TleError::Format {
+        line: idx,
+        msg: format!("`{token}`: {err}"),
+    })
+}
+
+fn parse_implied_decimal(token: &str, idx: usize) -> Result<f64, TleError> {
+    // TLE exponent notation: ` 12345-3` = 0.12345e-3
+    let token = token.trim();
+    if token.is_empty() {
+        return Ok(0.);
+    }
+    let (mantissa, exponent) = match token[1..].rfind(['-', '+']) {
+        Some(pos) => token.split_at(pos + 1),
+        None => (token, "0"),
+    };
+    let (sign, digits) = match mantissa.strip_prefix('-') {
+        Some(digits) => (-1., digits),
+        None => (1., mantissa.trim_start_matches('+')),
+    };
+    let value: f64 = parse(&format!("0.{digits}"), idx)?;
+    let exponent: i32 = parse(exponent.trim_start_matches('+'), idx)?;
+    Ok(sign * value * 10f64.powi(exponent))
+}
+
+fn checksum(line: &str) -> u32 {
+    line.chars()
+        .take(68)
+        .map(|c| match c {
+            '-' => 1,
+            _ => c.to_digit(10).unwrap_or(0),
+        })
+        .sum::<u32>()
+        % 10
+}
+
+fn verify_checksum(line: &str, idx: usize) -> Result<(), TleError> {
+    // A missing checksum column is tolerated; a wrong one is not
+    let Some(found) = line.chars().nth(68).and_then(|c| c.to_digit(10)) else {
+        return Ok(());
+    };
+    let expected = checksum(line);
+    if found != expected {
+        return Err(TleError::Checksum {
+            line: idx,
+            expected,
+            found,
+        });
+    }
+    Ok(())
+}
+
+fn epoch_from_year_day(year: i32, day_of_year: f64) -> Epoch {
+    // Day 1.0 is January 1 00:00 UTC
+    let jan0 = gregorian_to_jd(year, 1, 1, 0.) - 1.;
+    Epoch::from_jd(TimeScale::UTC, jan0 + day_of_year)
+}
+
+impl Tle {
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Vec<Self>, TleError> {
+        Self::from_str_multi(&fs::read_to_string(path)?)
+    }
+
+    /// Every element set in a TLE or 3LE file; name lines are optional.
+    pub fn from_str_multi(text: &str) -> Result<Vec<Self>, TleError> {
+        let mut sets = Vec::new();
+        let mut name = None;
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty());
+        while let Some((idx, line)) = lines.next() {
+            if line.starts_with("1 ") {
+                let (idx2, line2) = lines.next().ok_or(TleError::Format {
+                    line: idx + 1,
+                    msg: "line 1 without line 2".to_string(),
+                })?;
+                sets.push(Self::from_lines(
+                    name.take(),
+                    (idx + 1, line),
+                    (idx2 + 1, line2),
+                )?);
+            } else {
+                // 3LE name lines may carry a leading "0 "
+                name = Some(line.trim_start_matches("0 ").trim().to_string());
+            }
+        }
+        Ok(sets)
+    }
+
+    pub fn from_two_lines(line1: &str, line2: &str) -> Result<Self, TleError> {
+        Self::from_lines(None, (1, line1), (2, line2))
+    }
+
+    fn from_lines(
+        name: Option<String>,
+        (idx1, line1): (usize, &str),
+        (idx2, line2): (usize, &str),
+    ) -> Result<Self, TleError> {
+        // Column layout; Vallado Fig. 2-13
+        if !line1.starts_with('1') || !line2.starts_with('2') {
+            return Err(TleError::Format {
+                line: idx1,
+                msg: "expected lines numbered 1 and 2".to_string(),
+            });
+        }
+        verify_checksum(line1, idx1)?;
+        verify_checksum(line2, idx2)?;
+
+        let catalog_number: u32 = parse(field(line1, 2..7, idx1)?, idx1)?;
+        let year: i32 = parse(field(line1, 18..20, idx1)?, idx1)?;
+        let year = if year < 57 { 2000 + year } else { 1900 + year };
+        let day_of_year: f64 = parse(field(line1, 20..32, idx1)?, idx1)?;
+        let ndot: f64 = parse(field(line1, 33..43, idx1)?, idx1)?;
+        let nddot = parse_implied_decimal(field(line1, 44..52, idx1)?, idx1)?;
+        let bstar = parse_implied_decimal(field(line1, 53..61, idx1)?, idx1)?;
+
+        let line2_number: u32 = parse(field(line2, 2..7, idx2)?, idx2)?;
+        if line2_number != catalog_number {
+            return Err(TleError::Format {
+                line: idx2,
+                msg: format!("catalog number {line2_number} differs from line 1"),
+            });
+        }
+        let deg = |range, idx| -> Result<f64, TleError> {
+            Ok(parse::<f64>(field(line2, range, idx)?, idx)?.to_radians())
+        };
+        let eccentricity: f64 = parse(&format!("0.{}", field(line2, 26..33, idx2)?), idx2)?;
+        let revolution_number = line2
+            .get(63..68)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map_or(Ok(0), |s| parse(s, idx2))?;
+
+        Ok(Self {
+            name,
+            catalog_number,
+            epoch: epoch_from_year_day(year, day_of_year),
+            mean_motion_dot: ndot * REV_PER_DAY / 1440.,
+            mean_motion_ddot: nddot * REV_PER_DAY / (1440. * 1440.),
+            bstar,
+            inclination: deg(8..16, idx2)?,
+            raan: deg(17..25, idx2)?,
+            eccentricity,
+            arg_perigee: deg(34..42, idx2)?,
+            mean_anomaly: deg(43..51, idx2)?,
+            mean_motion: parse::<f64>(field(line2, 52..63, idx2)?, idx2)? * REV_PER_DAY,
+            revolution_number,
+        })
+    }
+
+    pub fn from_omm_file<P: AsRef<Path>>(path: P) -> Result<Self, TleError> {
+        Self::from_omm_str(&fs::read_to_string(path)?)
+    }
+
+    /// CCSDS OMM in KVN (`KEY = value`) or XML (`<KEY>value</KEY>`) form,
+    /// SGP4 mean elements only.
+    pub fn from_omm_str(text: &str) -> Result<Self, TleError> {
+        let lookup = |key: &'static str| -> Option<String> {
+            if let Some(start) = text.find(&format!("<{key}>")) {
+                let rest = &text[start + key.len() + 2..];
+                return rest.find("</").map(|end| rest[..end].trim().to_string());
+            }
+            text.lines().find_map(|line| {
+                let (k, v) = line.split_once('=')?;
+                (k.trim() == key).then(|| v.trim().to_string())
+            })
+        };
+        let required = |key: &'static str| -> Result<f64, TleError> {
+            let token = lookup(key).ok_or(TleError::MissingField(key))?;
+            parse(&token, 0)
+        };
+        let optional = |key: &'static str| -> Result<f64, TleError> {
+            lookup(key).map_or(Ok(0.), |token| parse(&token, 0))
+        };
+
+        let epoch_text = lookup("EPOCH").ok_or(TleError::MissingField("EPOCH"))?;
+        Ok(Self {
+            name: lookup("OBJECT_NAME"),
+            catalog_number: optional("NORAD_CAT_ID")? as u32,
+            epoch: parse_iso_epoch(&epoch_text)?,
+            mean_motion_dot: optional("MEAN_MOTION_DOT")? * REV_PER_DAY / 1440.,
+            mean_motion_ddot: optional("MEAN_MOTION_DDOT")? * REV_PER_DAY / (1440. * 1440.),
+            bstar: optional("BSTAR")?,
+            inclination: required("INCLINATION")?.to_radians(),
+            raan: required("RA_OF_ASC_NODE")?.to_radians(),
+            eccentricity: required("ECCENTRICITY")?,
+            arg_perigee: required("ARG_OF_PERICENTER")?.to_radians(),
+            mean_anomaly: required("MEAN_ANOMALY")?.to_radians(),
+            mean_motion: required("MEAN_MOTION")? * REV_PER_DAY,
+            revolution_number: optional("REV_AT_EPOCH")? as u32,
+        })
+    }
+}
+
+fn parse_iso_epoch(text: &str) -> Result<Epoch, TleError> {
+    // YYYY-MM-DDThh:mm:ss[.f] or YYYY-DDDThh:mm:ss[.f], UTC
+    let bad = || TleError::Format {
+        line: 0,
+        msg: format!("unrecognized epoch `{text}`"),
+    };
+    let (date, time) = text
+        .trim()
+        .trim_end_matches('Z')
+        .split_once('T')
+        .ok_or_else(bad)?;
+    let date_parts: Vec<&str> = date.split('-').collect();
+    let time_parts: Vec<&str> = time.split(':').collect();
+    if time_parts.len() != 3 {
+        return Err(bad());
+    }
+    let hour: f64 = parse(time_parts[0], 0)?;
+    let minute: f64 = parse(time_parts[1], 0)?;
+    let second: f64 = parse(time_parts[2], 0)?;
+    let day_fraction = (hour * 3600. + minute * 60. + second) / 86400.;
+    match date_parts.as_slice() {
+        [year, month, day] => {
+            let jd = gregorian_to_jd(parse(year, 0)?, parse(month, 0)?, parse(day, 0)?, 0.);
+            Ok(Epoch::from_jd(TimeScale::UTC, jd + day_fraction))
+        }
+        [year, doy] => Ok(epoch_from_year_day(
+            parse(year, 0)?,
+            parse::<f64>(doy, 0)? + day_fraction,
+        )),
+        _ => Err(bad()),
+    }
+}
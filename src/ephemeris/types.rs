@@ -1,18 +1,502 @@
+use std::fmt;
+use std::sync::Arc;
+
 use crate::{
-    ephemeris::consts::{MU, RE},
-    ode::{self, Integrator},
+    ephemeris::atmosphere::{AtmosphereModel, ExponentialAtmosphere, SpaceWeather},
+    ephemeris::celestial::{
+        albedo_fraction, moon_position_eci, shadow_fraction, sun_position_eci, ShadowModel, AU,
+    },
+    ephemeris::consts::{self, J2, MU, OMEGA_E, OMEGA_SSO, RE},
+    ephemeris::elements::{ClassicalElements, EquinoctialElements, ModifiedEquinoctialElements},
+    ephemeris::geopotential::GravityField,
+    ephemeris::magnetic::MagneticFieldModel,
+    ephemeris::mean_elements::{brouwer_osculating_to_mean, MeanElementsError},
+    ephemeris::sgp4::{Sgp4, Sgp4Error},
+    ephemeris::srp::SrpModel,
+    frames::geodetic::{ecef_to_enu, ecef_to_geodetic, ecef_to_ned, geodetic_to_ecef, Geodetic},
+    frames::types::FrameModel,
+    ode::{self, Integrator, IntegratorStats},
+    sc_types::{SpacecraftEphemerisArchitecture, SpacecraftMassArchitecture},
+    time::types::TimeScale,
 };
 use altai_rs::meta::types::{Generic1D, Generic2D, Vector3};
-use ndarray::{array, s, Axis};
+use ndarray::{array, concatenate, s, ArrayView1, Axis};
 
 use crate::actuators::types::TruthActuatorBus;
+use crate::attitude::{kinedynamics::quat_to_dcm, types::TruthAttitudeSignal};
 
 use super::kinedynamics;
 
+/// Ways of stating the initial orbit in `SpacecraftEphemerisArchitecture`.
+/// Angles in [rad], distances in [m].
+#[derive(Debug, Clone)]
+pub enum InitialOrbit {
+    Cartesian {
+        r_sc_eci: Vector3,
+        v_sc_eci: Vector3,
+    },
+    Keplerian(ClassicalElements),
+    Equinoctial(EquinoctialElements),
+    Circular {
+        altitude: f64,
+        inclination: f64,
+        raan: f64,
+        arg_latitude: f64,
+    },
+    SunSynchronous {
+        altitude: f64,
+        raan: f64,
+        arg_latitude: f64,
+    },
+    // Catalog element set, evaluated by SGP4 at the simulation start
+    Tle(Arc<Sgp4>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InitialOrbitError {
+    NonFinite,
+    BelowSurface { periapsis_radius: f64 },
+    InvalidEccentricity { e: f64, a: f64 },
+    InvalidInclination { i: f64 },
+    UnreachableTrueAnomaly { nu: f64 },
+    NoSunSynchronousSolution { altitude: f64 },
+    MissingElementSet,
+    Sgp4(Sgp4Error),
+}
+impl fmt::Display for InitialOrbitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonFinite => write!(f, "initial orbit contains non-finite values"),
+            Self::BelowSurface { periapsis_radius } => write!(
+                f,
+                "periapsis radius {periapsis_radius} m is below the Earth's surface"
+            ),
+            Self::InvalidEccentricity { e, a } => write!(
+                f,
+                "eccentricity {e} is inconsistent with semi-major axis {a} m"
+            ),
+            Self::InvalidInclination { i } => {
+                write!(f, "inclination {i} rad is outside [0, pi]")
+            }
+            Self::UnreachableTrueAnomaly { nu } => write!(
+                f,
+                "true anomaly {nu} rad lies beyond the hyperbolic asymptote"
+            ),
+            Self::NoSunSynchronousSolution { altitude } => write!(
+                f,
+                "no sun-synchronous inclination exists at altitude {altitude} m"
+            ),
+            Self::MissingElementSet => {
+                write!(f, "SGP4 propagation requires a TLE initial orbit")
+            }
+            Self::Sgp4(err) => write!(f, "element set cannot be propagated: {err}"),
+        }
+    }
+}
+impl std::error::Error for InitialOrbitError {}
+
+impl InitialOrbit {
+    pub fn sso_inclination(altitude: f64) -> Result<f64, InitialOrbitError> {
+        // Circular SSO; Vallado Eq. 9-44 solved for inclination
+        let a = RE + altitude;
+        let cos_i = -2. * a.powf(3.5) * OMEGA_SSO / (3. * J2 * RE.powi(2) * MU.sqrt());
+        if !(-1. ..=1.).contains(&cos_i) {
+            return Err(InitialOrbitError::NoSunSynchronousSolution { altitude });
+        }
+        Ok(cos_i.acos())
+    }
+
+    pub fn to_classical(&self) -> Result<Option<ClassicalElements>, InitialOrbitError> {
+        let coe = match self {
+            Self::Cartesian { .. } | Self::Tle(_) => return Ok(None),
+            Self::Keplerian(coe) => coe.clone(),
+            Self::Equinoctial(eq) => {
+                if eq.h.hypot(eq.k) >= 1. {
+                    return Err(InitialOrbitError::InvalidEccentricity {
+                        e: eq.h.hypot(eq.k),
+                        a: eq.a,
+                    });
+                }
+                eq.to_classical()
+            }
+            Self::Circular {
+                altitude,
+                inclination,
+                raan,
+                arg_latitude,
+            } => ClassicalElements {
+                a: RE + altitude,
+                e: 0.,
+                i: *inclination,
+                raan: *raan,
+                argp: 0.,
+                nu: *arg_latitude,
+            },
+            Self::SunSynchronous {
+                altitude,
+                raan,
+                arg_latitude,
+            } => ClassicalElements {
+                a: RE + altitude,
+                e: 0.,
+                i: Self::sso_inclination(*altitude)?,
+                raan: *raan,
+                argp: 0.,
+                nu: *arg_latitude,
+            },
+        };
+        Ok(Some(coe))
+    }
+
+    fn validate_classical(coe: &ClassicalElements) -> Result<(), InitialOrbitError> {
+        let vals = [coe.a, coe.e, coe.i, coe.raan, coe.argp, coe.nu];
+        if vals.iter().any(|x| !x.is_finite()) {
+            return Err(InitialOrbitError::NonFinite);
+        }
+        let elliptic = (0. ..1.).contains(&coe.e) && coe.a > 0.;
+        let hyperbolic = coe.e > 1. && coe.a < 0.;
+        if !(elliptic || hyperbolic) {
+            return Err(InitialOrbitError::InvalidEccentricity { e: coe.e, a: coe.a });
+        }
+        if !(0. ..=std::f64::consts::PI).contains(&coe.i) {
+            return Err(InitialOrbitError::InvalidInclination { i: coe.i });
+        }
+        if 1. + coe.e * coe.nu.cos() <= 0. {
+            return Err(InitialOrbitError::UnreachableTrueAnomaly { nu: coe.nu });
+        }
+        let periapsis_radius = coe.a * (1. - coe.e);
+        if periapsis_radius <= RE {
+            return Err(InitialOrbitError::BelowSurface { periapsis_radius });
+        }
+        Ok(())
+    }
+
+    pub fn to_signal(&self) -> Result<TruthEphemerisSignal, InitialOrbitError> {
+        if let Self::Tle(sgp4) = self {
+            // State at the element set epoch; re-evaluated at the simulation start
+            let epoch = sgp4.tle.epoch.seconds_since_j2000(TimeScale::TT);
+            let (r_sc_eci, v_sc_eci) = sgp4.state_eci(epoch).map_err(InitialOrbitError::Sgp4)?;
+            return Ok(TruthEphemerisSignal {
+                r_sc_eci,
+                v_sc_eci,
+                ..Default::default()
+            });
+        }
+        let (r_sc_eci, v_sc_eci) = match self.to_classical()? {
+            Some(coe) => {
+                Self::validate_classical(&coe)?;
+                coe.to_cartesian(MU)
+            }
+            None => {
+                let Self::Cartesian { r_sc_eci, v_sc_eci } = self else {
+                    unreachable!()
+                };
+                if r_sc_eci
+                    .iter()
+                    .chain(v_sc_eci.iter())
+                    .any(|x| !x.is_finite())
+                {
+                    return Err(InitialOrbitError::NonFinite);
+                }
+                let rmag = r_sc_eci.iter().map(|x| x * x).sum::<f64>().sqrt();
+                if rmag <= RE {
+                    return Err(InitialOrbitError::BelowSurface {
+                        periapsis_radius: rmag,
+                    });
+                }
+                (r_sc_eci.to_owned(), v_sc_eci.to_owned())
+            }
+        };
+        Ok(TruthEphemerisSignal {
+            r_sc_eci,
+            v_sc_eci,
+            ..Default::default()
+        })
+    }
+}
+
+/// Orbit propagation mode selectable from `SpacecraftEphemerisArchitecture`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum OrbitPropagator {
+    // Integrate the force model
+    #[default]
+    Numerical,
+    // Analytic SGP4/SDP4 from `InitialOrbit::Tle`; applied forces are ignored
+    Sgp4,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ForceModelError {
+    ZonalDegree(usize),
+    NonPositive {
+        model: &'static str,
+        field: &'static str,
+        value: f64,
+    },
+    Negative {
+        model: &'static str,
+        field: &'static str,
+        value: f64,
+    },
+    OutOfRange {
+        model: &'static str,
+        field: &'static str,
+        value: f64,
+        min: f64,
+        max: f64,
+    },
+    ReflectivityAboveUnity {
+        specular: f64,
+        diffuse: f64,
+    },
+    NormalNotUnit {
+        model: &'static str,
+        norm: f64,
+    },
+}
+impl fmt::Display for ForceModelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZonalDegree(n) => write!(
+                f,
+                "zonal degree {n} is outside 2..={}",
+                consts::JN.len() - 1
+            ),
+            Self::NonPositive {
+                model,
+                field,
+                value,
+            } => write!(f, "{model}: `{field}` = {value} must be positive"),
+            Self::Negative {
+                model,
+                field,
+                value,
+            } => write!(f, "{model}: `{field}` = {value} must not be negative"),
+            Self::OutOfRange {
+                model,
+                field,
+                value,
+                min,
+                max,
+            } => write!(f, "{model}: `{field}` = {value} is outside {min}..={max}"),
+            Self::ReflectivityAboveUnity { specular, diffuse } => write!(
+                f,
+                "srp: specular {specular} and diffuse {diffuse} reflectivities sum above 1"
+            ),
+            Self::NormalNotUnit { model, norm } => {
+                write!(f, "{model}: plate normal norm {norm} is not unity")
+            }
+        }
+    }
+}
+impl std::error::Error for ForceModelError {}
+
+impl ForceModelError {
+    /// Rejects zero, negative and NaN values.
+    pub(crate) fn check_positive(
+        model: &'static str,
+        field: &'static str,
+        value: f64,
+    ) -> Result<(), Self> {
+        if value.is_nan() || value <= 0. {
+            return Err(Self::NonPositive {
+                model,
+                field,
+                value,
+            });
+        }
+        Ok(())
+    }
+
+    /// Rejects negative and NaN values.
+    pub(crate) fn check_non_negative(
+        model: &'static str,
+        field: &'static str,
+        value: f64,
+    ) -> Result<(), Self> {
+        if value.is_nan() || value < 0. {
+            return Err(Self::Negative {
+                model,
+                field,
+                value,
+            });
+        }
+        Ok(())
+    }
+
+    /// Rejects values outside `min..=max`, including NaN.
+    pub(crate) fn check_range(
+        model: &'static str,
+        field: &'static str,
+        value: f64,
+        min: f64,
+        max: f64,
+    ) -> Result<(), Self> {
+        if !(min..=max).contains(&value) {
+            return Err(Self::OutOfRange {
+                model,
+                field,
+                value,
+                min,
+                max,
+            });
+        }
+        Ok(())
+    }
+
+    /// Rejects plate normals that are not unit vectors, including NaN.
+    pub(crate) fn check_unit_normal(model: &'static str, normal: &[f64; 3]) -> Result<(), Self> {
+        let norm = normal.iter().map(|x| x * x).sum::<f64>().sqrt();
+        if norm.is_nan() || (norm - 1.).abs() > 1e-6 {
+            return Err(Self::NormalNotUnit { model, norm });
+        }
+        Ok(())
+    }
+}
+
+/// Gravity model selectable from `SpacecraftEphemerisArchitecture`.
+#[derive(Debug, Clone, Default)]
+pub enum GravityModel {
+    #[default]
+    PointMass,
+    // Zonal harmonics J2 through J<degree>, degree in 2..=6
+    Zonal {
+        degree: usize,
+    },
+    // Full geopotential truncated to degree/order, evaluated in ECEF
+    SphericalHarmonic {
+        field: Arc<GravityField>,
+        degree: usize,
+        order: usize,
+    },
+}
+impl GravityModel {
+    pub fn validate(&self) -> Result<(), ForceModelError> {
+        match self {
+            Self::Zonal { degree } if !(2..consts::JN.len()).contains(degree) => {
+                Err(ForceModelError::ZonalDegree(*degree))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Flat surface used for attitude-dependent projected area.
+#[derive(Debug, Clone)]
+pub struct DragPlate {
+    pub normal_body: [f64; 3], // Outward unit normal (Body) [-]
+    pub area: f64,             // [m2]
+}
+
+#[derive(Debug, Clone)]
+pub enum DragArea {
+    // Constant reference area [m2]
+    Reference(f64),
+    // Sum of plate areas facing the relative wind
+    Plates(Vec<DragPlate>),
+}
+impl DragArea {
+    pub fn projected(&self, vhat_body: &ArrayView1<f64>) -> f64 {
+        match self {
+            Self::Reference(area) => *area,
+            Self::Plates(plates) => plates
+                .iter()
+                .map(|plate| {
+                    let cos_inc = plate
+                        .normal_body
+                        .iter()
+                        .zip(vhat_body.iter())
+                        .map(|(n, v)| n * v)
+                        .sum::<f64>();
+                    plate.area * cos_inc.max(0.)
+                })
+                .sum(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DragModel {
+    pub cd: f64, // Drag coefficient [-]
+    pub area: DragArea,
+    pub atmosphere: Arc<dyn AtmosphereModel>,
+}
+impl DragModel {
+    pub fn validate(&self) -> Result<(), ForceModelError> {
+        ForceModelError::check_positive("drag", "cd", self.cd)?;
+        match &self.area {
+            DragArea::Reference(area) => ForceModelError::check_positive("drag", "area", *area),
+            DragArea::Plates(plates) => plates.iter().try_for_each(|plate| {
+                ForceModelError::check_unit_normal("drag", &plate.normal_body)?;
+                ForceModelError::check_positive("drag", "area", plate.area)
+            }),
+        }
+    }
+}
+impl Default for DragModel {
+    fn default() -> Self {
+        Self {
+            cd: 2.2,
+            area: DragArea::Reference(1.),
+            atmosphere: Arc::new(ExponentialAtmosphere),
+        }
+    }
+}
+
+/// Lunisolar point-mass perturbations, each enabled individually.
+#[derive(Debug, Clone, Default)]
+pub struct ThirdBodyModel {
+    pub sun: bool,
+    pub moon: bool,
+}
+
+/// Force terms evaluated by the ephemeris derivative.
+#[derive(Debug, Clone, Default)]
+pub struct ForceModel {
+    pub gravity: GravityModel,
+    pub drag: Option<DragModel>,
+    pub space_weather: SpaceWeather,
+    pub srp: Option<SrpModel>,
+    pub shadow: ShadowModel,
+    pub third_body: ThirdBodyModel,
+    pub frames: FrameModel,
+    pub magnetic_field: MagneticFieldModel,
+}
+impl ForceModel {
+    pub fn initialize(ephemeris_params: &SpacecraftEphemerisArchitecture) -> Self {
+        Self {
+            gravity: ephemeris_params.gravity.clone(),
+            drag: ephemeris_params.drag.clone(),
+            space_weather: ephemeris_params.space_weather.clone(),
+            srp: ephemeris_params.srp.clone(),
+            shadow: ephemeris_params.shadow,
+            third_body: ephemeris_params.third_body.clone(),
+            frames: ephemeris_params.frames.clone(),
+            magnetic_field: ephemeris_params.magnetic_field.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TruthEphemerisSignal {
     pub r_sc_eci: Vector3,
     pub v_sc_eci: Vector3,
+    pub r_sc_ecef: Vector3,  // Earth-fixed position [m]
+    pub v_sc_ecef: Vector3,  // Velocity relative to the rotating Earth [m/s]
+    pub geodetic: Geodetic,  // WGS-84 latitude, longitude and altitude
+    pub r_ssp_ecef: Vector3, // Sub-satellite point on the ellipsoid [m]
+    pub ecef_to_enu: Generic2D,
+    pub ecef_to_ned: Generic2D,
+    pub reentered: bool,      // Latched once altitude falls below the floor
+    pub r_sun_eci: Vector3,   // Sun position [m]
+    pub r_moon_eci: Vector3,  // Moon position [m]
+    pub shadow_fraction: f64, // 1: Sunlit, 0: Umbra
+    pub albedo_fraction: f64, // Earth-reflected irradiance relative to direct Sun [-]
+    pub b_ecef: Vector3,      // Geomagnetic field [T]
+    pub b_eci: Vector3,       // [T]
+    pub b_body: Vector3,      // At the current attitude [T]
+    pub sun_body: Vector3,    // Unit Sun direction from the spacecraft (Body) [-]
+    pub nadir_body: Vector3,  // Unit nadir (Body) [-]
 }
 impl Default for TruthEphemerisSignal {
     fn default() -> Self {
@@ -22,12 +506,35 @@ impl Default for TruthEphemerisSignal {
         // O_sv: 0 deg
         // w_sv: 0 deg
         // M_sv: 0 deg
-        let a_sv = RE + 900e3;
+        let a_sv = RE + 500e3;
+        let geodetic = ecef_to_geodetic(&array![a_sv, 0., 0.].view());
         Self {
             r_sc_eci: array![a_sv, 0., 0.].into_shape_with_order((3, 1)).unwrap(),
             v_sc_eci: array![0., (MU / a_sv).sqrt(), 0.]
                 .into_shape_with_order((3, 1))
                 .unwrap(),
+            r_sc_ecef: array![a_sv, 0., 0.].into_shape_with_order((3, 1)).unwrap(),
+            v_sc_ecef: array![0., (MU / a_sv).sqrt() - OMEGA_E * a_sv, 0.]
+                .into_shape_with_order((3, 1))
+                .unwrap(),
+            geodetic,
+            r_ssp_ecef: geodetic_to_ecef(&Geodetic {
+                altitude: 0.,
+                ..geodetic
+            })
+            .insert_axis(Axis(1)),
+            ecef_to_enu: ecef_to_enu(&geodetic),
+            ecef_to_ned: ecef_to_ned(&geodetic),
+            reentered: false,
+            r_sun_eci: array![0., 0., 0.].into_shape_with_order((3, 1)).unwrap(),
+            r_moon_eci: array![0., 0., 0.].into_shape_with_order((3, 1)).unwrap(),
+            shadow_fraction: 1.,
+            albedo_fraction: 0.,
+            b_ecef: Vector3::zeros((3, 1)),
+            b_eci: Vector3::zeros((3, 1)),
+            b_body: Vector3::zeros((3, 1)),
+            sun_body: Vector3::zeros((3, 1)),
+            nadir_body: array![-1., 0., 0.].into_shape_with_order((3, 1)).unwrap(),
         }
     }
 }
@@ -40,7 +547,7 @@ impl TruthEphemerisSignal {
                 .remove_axis(Axis(1))
                 .iter()
                 .chain(self.v_sc_eci.to_owned().remove_axis(Axis(1)).iter())
-                .map(|&x| x),
+                .copied(),
         )
     }
     pub fn from_state_vector(&mut self, state_vec: Generic1D) {
@@ -51,47 +558,250 @@ impl TruthEphemerisSignal {
             .slice(s![3..6])
             .assign_to(self.v_sc_eci.slice_mut(s![0..3, 0]));
     }
+
+    /// Osculating two-body elements of the current state.
+    pub fn classical_elements(&self) -> ClassicalElements {
+        ClassicalElements::from_cartesian(&self.r_sc_eci.column(0), &self.v_sc_eci.column(0), MU)
+    }
+
+    pub fn equinoctial_elements(&self) -> EquinoctialElements {
+        EquinoctialElements::from_cartesian(&self.r_sc_eci.column(0), &self.v_sc_eci.column(0), MU)
+    }
+
+    pub fn modified_equinoctial_elements(&self) -> ModifiedEquinoctialElements {
+        ModifiedEquinoctialElements::from_cartesian(
+            &self.r_sc_eci.column(0),
+            &self.v_sc_eci.column(0),
+            MU,
+        )
+    }
+
+    /// Brouwer-Lyddane J2 mean elements; see `brouwer_osculating_to_mean`.
+    /// Fails for equatorial, critically inclined or unbound orbits.
+    pub fn mean_elements(&self) -> Result<ClassicalElements, MeanElementsError> {
+        brouwer_osculating_to_mean(&self.classical_elements())
+    }
+
+    pub fn update_environment(&mut self, time: f64, forces: &ForceModel) {
+        let r_sun = sun_position_eci(time);
+        let r_sc = self.r_sc_eci.column(0);
+        self.shadow_fraction = shadow_fraction(forces.shadow, &r_sc, &r_sun.view());
+        self.albedo_fraction = albedo_fraction(&r_sc, &r_sun.view());
+        r_sun.assign_to(self.r_sun_eci.slice_mut(s![0..3, 0]));
+        moon_position_eci(time).assign_to(self.r_moon_eci.slice_mut(s![0..3, 0]));
+
+        let (r_ecef, v_ecef) = forces
+            .frames
+            .rv_eci_to_ecef(time, &r_sc, &self.v_sc_eci.column(0));
+        r_ecef.assign_to(self.r_sc_ecef.slice_mut(s![0..3, 0]));
+        v_ecef.assign_to(self.v_sc_ecef.slice_mut(s![0..3, 0]));
+
+        self.geodetic = ecef_to_geodetic(&r_ecef.view());
+        geodetic_to_ecef(&Geodetic {
+            altitude: 0.,
+            ..self.geodetic
+        })
+        .assign_to(self.r_ssp_ecef.slice_mut(s![0..3, 0]));
+        self.ecef_to_enu = ecef_to_enu(&self.geodetic);
+        self.ecef_to_ned = ecef_to_ned(&self.geodetic);
+
+        let b_ecef = forces.magnetic_field.field_ecef(time, &r_ecef.view());
+        forces
+            .frames
+            .eci_to_ecef(time)
+            .t()
+            .dot(&b_ecef)
+            .assign_to(self.b_eci.slice_mut(s![0..3, 0]));
+        b_ecef.assign_to(self.b_ecef.slice_mut(s![0..3, 0]));
+    }
+
+    /// Body-frame environment vectors at the given attitude.
+    pub fn update_body_vectors(&mut self, attitude: &TruthAttitudeSignal) {
+        let a_body_eci = quat_to_dcm(&attitude.q_sc_eci);
+        self.b_body = a_body_eci.dot(&self.b_eci);
+        let d_sun = &self.r_sun_eci - &self.r_sc_eci;
+        self.sun_body = a_body_eci.dot(&d_sun) / d_sun.iter().map(|x| x * x).sum::<f64>().sqrt();
+        let r_sc = self.r_sc_eci.iter().map(|x| x * x).sum::<f64>().sqrt();
+        self.nadir_body = -a_body_eci.dot(&self.r_sc_eci) / r_sc;
+    }
+
+    /// Direct sunlight relative to 1 AU, including eclipse [-].
+    pub fn solar_irradiance(&self) -> f64 {
+        let d_sun = &self.r_sun_eci - &self.r_sc_eci;
+        self.shadow_fraction * AU * AU / d_sun.iter().map(|x| x * x).sum::<f64>()
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct TruthEphemerisBus {
     pub signal: TruthEphemerisSignal,
-    integrator: ode::RK5,
+    pub forces: ForceModel,
+    pub propagator: OrbitPropagator,
+    sgp4: Option<Arc<Sgp4>>,
+    altitude_floor: f64,
+    ts: f64,
+    integrator: ode::Solver,
 }
 
 impl Default for TruthEphemerisBus {
     fn default() -> Self {
         Self {
             signal: TruthEphemerisSignal::default(),
-            integrator: ode::RK5(0.1),
+            forces: ForceModel::default(),
+            propagator: OrbitPropagator::default(),
+            sgp4: None,
+            altitude_floor: 100e3,
+            ts: 0.1,
+            integrator: ode::Solver::RK5(ode::RK5(0.1)),
         }
     }
 }
 
 impl TruthEphemerisBus {
-    pub fn initialize(SC_Ts: f64) -> Self {
-        Self {
-            signal: TruthEphemerisSignal::default(),
-            integrator: ode::RK5(SC_Ts),
+    pub fn initialize(
+        sc_ts: f64,
+        ephemeris_params: SpacecraftEphemerisArchitecture,
+    ) -> Result<Self, InitialOrbitError> {
+        let sgp4 = match &ephemeris_params.initial_orbit {
+            InitialOrbit::Tle(sgp4) => Some(sgp4.clone()),
+            _ => None,
+        };
+        if ephemeris_params.propagator == OrbitPropagator::Sgp4 && sgp4.is_none() {
+            return Err(InitialOrbitError::MissingElementSet);
         }
+        Ok(Self {
+            signal: ephemeris_params.initial_orbit.to_signal()?,
+            forces: ForceModel::initialize(&ephemeris_params),
+            propagator: ephemeris_params.propagator,
+            sgp4,
+            altitude_floor: ephemeris_params.altitude_floor,
+            ts: sc_ts,
+            integrator: ode::Solver::initialize(&ephemeris_params.integrator, sc_ts),
+        })
     }
 
-    pub fn process(&mut self, actuator_dynamics: &TruthActuatorBus, prev_ephem: &Self) {
-        self.propagate(actuator_dynamics, &prev_ephem.signal);
+    pub fn integrator_stats(&self) -> Option<IntegratorStats> {
+        self.integrator.stats()
+    }
+
+    /// Evaluate a TLE initial orbit at the simulation start, then the environment.
+    pub fn start(&mut self, time: f64) -> Result<(), InitialOrbitError> {
+        if let Some(sgp4) = &self.sgp4 {
+            let (r_sc_eci, v_sc_eci) = sgp4.state_eci(time).map_err(InitialOrbitError::Sgp4)?;
+            self.signal.r_sc_eci = r_sc_eci;
+            self.signal.v_sc_eci = v_sc_eci;
+        }
+        self.update_environment(time);
+        Ok(())
+    }
+
+    pub fn update_environment(&mut self, time: f64) {
+        self.signal.update_environment(time, &self.forces);
+        if !self.signal.reentered && self.signal.geodetic.altitude < self.altitude_floor {
+            log::warn!(
+                "Re-entry: altitude {:.0} m below the {:.0} m floor",
+                self.signal.geodetic.altitude,
+                self.altitude_floor
+            );
+            self.signal.reentered = true;
+        }
+    }
+
+    pub fn srp_torque(
+        &self,
+        mass_props: &SpacecraftMassArchitecture,
+        attitude: &TruthAttitudeSignal,
+    ) -> Vector3 {
+        // SRP torque (Body) at the current signal; zero when SRP is disabled
+        let Some(srp) = &self.forces.srp else {
+            return Vector3::zeros((3, 1));
+        };
+        let d_sun = &self.signal.r_sun_eci - &self.signal.r_sc_eci;
+        let sun_dist = d_sun.iter().map(|x| x * x).sum::<f64>().sqrt();
+        let sun_hat_body = quat_to_dcm(&attitude.q_sc_eci).dot(&d_sun.column(0)) / sun_dist;
+        let (_, torque) = srp.force_torque_body(
+            &sun_hat_body.view(),
+            sun_dist,
+            self.signal.shadow_fraction,
+            &mass_props.r_cm_body.column(0),
+        );
+        torque.insert_axis(Axis(1))
+    }
+
+    pub fn process(
+        &mut self,
+        time: f64,
+        mass_props: &SpacecraftMassArchitecture,
+        actuator_dynamics: &TruthActuatorBus,
+        attitude: &TruthAttitudeSignal,
+        prev_ephem: &Self,
+    ) {
+        // Carry integrator step-size and statistics forward across the swap
+        self.integrator.clone_from(&prev_ephem.integrator);
+        self.forces.clone_from(&prev_ephem.forces);
+        self.propagator = prev_ephem.propagator;
+        self.sgp4.clone_from(&prev_ephem.sgp4);
+        self.altitude_floor = prev_ephem.altitude_floor;
+        self.ts = prev_ephem.ts;
+        self.signal.reentered = prev_ephem.signal.reentered;
+        // `initialize` rejects SGP4 mode without an element set
+        match (self.propagator, self.sgp4.clone()) {
+            (OrbitPropagator::Sgp4, Some(sgp4)) => {
+                self.propagate_sgp4(&sgp4, time + self.ts, &prev_ephem.signal)
+            }
+            _ => self.propagate(
+                time,
+                mass_props,
+                actuator_dynamics,
+                attitude,
+                &prev_ephem.signal,
+            ),
+        }
+        self.update_environment(time + self.ts);
     }
 
     fn propagate(
         &mut self,
+        time: f64,
+        mass_props: &SpacecraftMassArchitecture,
         actuator_dynamics: &TruthActuatorBus,
+        attitude: &TruthAttitudeSignal,
         prev_ephem: &TruthEphemerisSignal,
     ) {
         let state0 = prev_ephem.to_state_vector();
+        let inpts = concatenate![
+            Axis(1),
+            actuator_dynamics.net_forces.to_owned(),
+            array![[mass_props.mass], [0.], [0.]],
+            quat_to_dcm(&attitude.q_sc_eci)
+        ];
+        let forces = &self.forces;
         let nstate = self.integrator.integrate(
-            &kinedynamics::orbital_twobody,
-            0.,
+            &|t, state, inpt| kinedynamics::orbital_perturbed(t, state, inpt, forces),
+            time,
             &state0,
-            &actuator_dynamics.net_forces.to_owned(),
+            &inpts,
         );
         self.signal.from_state_vector(nstate);
     }
+
+    fn propagate_sgp4(&mut self, sgp4: &Sgp4, time: f64, prev_ephem: &TruthEphemerisSignal) {
+        match sgp4.state_eci(time) {
+            Ok((r_sc_eci, v_sc_eci)) => {
+                self.signal.r_sc_eci = r_sc_eci;
+                self.signal.v_sc_eci = v_sc_eci;
+            }
+            Err(err) => {
+                // Hold the last good state
+                if !self.signal.reentered {
+                    log::error!("SGP4 failed at t = {time:.1} s: {err}");
+                }
+                self.signal.r_sc_eci.assign(&prev_ephem.r_sc_eci);
+                self.signal.v_sc_eci.assign(&prev_ephem.v_sc_eci);
+                if matches!(err, Sgp4Error::Decayed(_)) {
+                    self.signal.reentered = true;
+                }
+            }
+        }
+    }
 }
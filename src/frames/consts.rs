@@ -0,0 +1,39 @@
+// IAU-1980 nutation series, truncated to the 30 largest terms (<= ~5 mas)
+// Multipliers of (M_moon, M_sun, F, D, Omega), then longitude (A + B T) and
+// obliquity (C + D T) coefficients [0.0001 arcsec]; Vallado Table D-6
+pub const NUTATION_1980: [([f64; 5], [f64; 4]); 30] = [
+    ([0., 0., 0., 0., 1.], [-171996., -174.2, 92025., 8.9]),
+    ([0., 0., 2., -2., 2.], [-13187., -1.6, 5736., -3.1]),
+    ([0., 0., 2., 0., 2.], [-2274., -0.2, 977., -0.5]),
+    ([0., 0., 0., 0., 2.], [2062., 0.2, -895., 0.5]),
+    ([0., 1., 0., 0., 0.], [1426., -3.4, 54., -0.1]),
+    ([1., 0., 0., 0., 0.], [712., 0.1, -7., 0.]),
+    ([0., 1., 2., -2., 2.], [-517., 1.2, 224., -0.6]),
+    ([0., 0., 2., 0., 1.], [-386., -0.4, 200., 0.]),
+    ([1., 0., 2., 0., 2.], [-301., 0., 129., -0.1]),
+    ([0., -1., 2., -2., 2.], [217., -0.5, -95., 0.3]),
+    ([1., 0., 0., -2., 0.], [-158., 0., -1., 0.]),
+    ([0., 0., 2., -2., 1.], [129., 0.1, -70., 0.]),
+    ([-1., 0., 2., 0., 2.], [123., 0., -53., 0.]),
+    ([1., 0., 0., 0., 1.], [63., 0.1, -33., 0.]),
+    ([0., 0., 0., 2., 0.], [63., 0., -2., 0.]),
+    ([-1., 0., 2., 2., 2.], [-59., 0., 26., 0.]),
+    ([-1., 0., 0., 0., 1.], [-58., -0.1, 32., 0.]),
+    ([1., 0., 2., 0., 1.], [-51., 0., 27., 0.]),
+    ([2., 0., 0., -2., 0.], [48., 0., 1., 0.]),
+    ([-2., 0., 2., 0., 1.], [46., 0., -24., 0.]),
+    ([0., 0., 2., 2., 2.], [-38., 0., 16., 0.]),
+    ([2., 0., 2., 0., 2.], [-31., 0., 13., 0.]),
+    ([2., 0., 0., 0., 0.], [29., 0., -1., 0.]),
+    ([1., 0., 2., -2., 2.], [29., 0., -12., 0.]),
+    ([0., 0., 2., 0., 0.], [26., 0., -1., 0.]),
+    ([0., 0., 2., -2., 0.], [-22., 0., 0., 0.]),
+    ([-1., 0., 2., 0., 1.], [21., 0., -10., 0.]),
+    ([0., 2., 0., 0., 0.], [17., -0.1, 0., 0.]),
+    ([0., 2., 2., -2., 2.], [-16., 0.1, 7., 0.]),
+    ([-1., 0., 0., 2., 1.], [16., 0., -8., 0.]),
+];
+
+// WGS-84 ellipsoid
+pub const WGS84_A: f64 = 6378137.; // Semi-major axis [m]
+pub const WGS84_F: f64 = 1. / 298.257223563; // Flattening [-]
@@ -0,0 +1,123 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum EopError {
+    Io(std::io::Error),
+    MissingColumn(&'static str),
+    Parse { line: usize, msg: String },
+}
+impl fmt::Display for EopError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read EOP file: {err}"),
+            Self::MissingColumn(col) => write!(f, "EOP file has no `{col}` column"),
+            Self::Parse { line, msg } => write!(f, "EOP line {line}: {msg}"),
+        }
+    }
+}
+impl std::error::Error for EopError {}
+impl From<std::io::Error> for EopError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EopRecord {
+    pub mjd: f64,  // UTC modified Julian date [day]
+    pub xp: f64,   // Polar motion x [rad]
+    pub yp: f64,   // Polar motion y [rad]
+    pub dut1: f64, // UT1 - UTC [s]
+    pub lod: f64,  // Excess length of day [s]
+}
+
+/// Daily Earth orientation parameters, linearly interpolated.
+#[derive(Debug, Clone, Default)]
+pub struct EopData {
+    records: Vec<EopRecord>,
+}
+
+const ARCSEC_TO_RAD: f64 = std::f64::consts::PI / (180. * 3600.);
+
+impl EopData {
+    pub fn from_csv_file<P: AsRef<Path>>(path: P) -> Result<Self, EopError> {
+        Self::from_csv_str(&fs::read_to_string(path)?)
+    }
+
+    /// CelesTrak EOP CSV: header row naming at least MJD, X, Y, UT1-UTC and
+    /// LOD; polar motion in arcsec, times in seconds.
+    pub fn from_csv_str(text: &str) -> Result<Self, EopError> {
+        let mut lines = text.lines().enumerate();
+        let header: Vec<&str> = lines
+            .next()
+            .map(|(_, line)| line.split(',').map(str::trim).collect())
+            .unwrap_or_default();
+        let column = |name: &'static str| {
+            header
+                .iter()
+                .position(|&col| col == name)
+                .ok_or(EopError::MissingColumn(name))
+        };
+        let (i_mjd, i_x, i_y, i_dut1, i_lod) = (
+            column("MJD")?,
+            column("X")?,
+            column("Y")?,
+            column("UT1-UTC")?,
+            column("LOD")?,
+        );
+
+        let mut records = Vec::new();
+        for (idx, line) in lines {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let get = |i: usize| -> Result<f64, EopError> {
+                let token = fields.get(i).copied().unwrap_or("");
+                token.parse::<f64>().map_err(|err| EopError::Parse {
+                    line: idx + 1,
+                    msg: format!("`{token}`: {err}"),
+                })
+            };
+            records.push(EopRecord {
+                mjd: get(i_mjd)?,
+                xp: get(i_x)? * ARCSEC_TO_RAD,
+                yp: get(i_y)? * ARCSEC_TO_RAD,
+                dut1: get(i_dut1)?,
+                lod: get(i_lod)?,
+            });
+        }
+        records.sort_by(|a, b| a.mjd.total_cmp(&b.mjd));
+        Ok(Self { records })
+    }
+
+    pub fn from_records(mut records: Vec<EopRecord>) -> Self {
+        records.sort_by(|a, b| a.mjd.total_cmp(&b.mjd));
+        Self { records }
+    }
+
+    /// Parameters at a UTC MJD; held at the end values outside the table.
+    pub fn at(&self, mjd_utc: f64) -> Option<EopRecord> {
+        let first = self.records.first()?;
+        let last = self.records.last()?;
+        if mjd_utc <= first.mjd {
+            return Some(*first);
+        }
+        if mjd_utc >= last.mjd {
+            return Some(*last);
+        }
+        let idx = self.records.partition_point(|rec| rec.mjd <= mjd_utc);
+        let (a, b) = (self.records[idx - 1], self.records[idx]);
+        let frac = (mjd_utc - a.mjd) / (b.mjd - a.mjd);
+        let lerp = |x0: f64, x1: f64| x0 + frac * (x1 - x0);
+        Some(EopRecord {
+            mjd: mjd_utc,
+            xp: lerp(a.xp, b.xp),
+            yp: lerp(a.yp, b.yp),
+            dut1: lerp(a.dut1, b.dut1),
+            lod: lerp(a.lod, b.lod),
+        })
+    }
+}
@@ -0,0 +1,82 @@
+use altai_rs::meta::types::{Generic1D, Generic2D};
+use ndarray::{array, ArrayView1};
+
+use crate::frames::consts::{WGS84_A, WGS84_F};
+
+/// Geodetic coordinates on the WGS-84 ellipsoid.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Geodetic {
+    pub latitude: f64,  // Geodetic latitude [rad]
+    pub longitude: f64, // East longitude in (-pi, pi] [rad]
+    pub altitude: f64,  // Height above the ellipsoid [m]
+}
+
+pub fn ecef_to_geodetic(r_ecef: &ArrayView1<f64>) -> Geodetic {
+    /*
+    Bowring's method iterated on the parametric latitude; Bowring (1976)
+
+    Inputs:
+    r_ecef: R-vector (ECEF) [m]
+
+    Outputs:
+    Geodetic latitude, longitude and ellipsoidal height
+    */
+    let e2 = WGS84_F * (2. - WGS84_F);
+    let b = WGS84_A * (1. - WGS84_F);
+    let ep2 = e2 / (1. - e2);
+    let (x, y, z) = (r_ecef[0], r_ecef[1], r_ecef[2]);
+    let p = x.hypot(y);
+
+    // Three passes converge to sub-millimetre for any orbit altitude
+    let mut beta = z.atan2((1. - WGS84_F) * p);
+    let mut latitude = 0.;
+    for _ in 0..3 {
+        let (sb, cb) = beta.sin_cos();
+        latitude = (z + ep2 * b * sb.powi(3)).atan2(p - e2 * WGS84_A * cb.powi(3));
+        beta = ((1. - WGS84_F) * latitude.sin()).atan2(latitude.cos());
+    }
+
+    // Height form that stays well conditioned at the poles
+    let (sl, cl) = latitude.sin_cos();
+    let n = WGS84_A / (1. - e2 * sl * sl).sqrt();
+    Geodetic {
+        latitude,
+        longitude: y.atan2(x),
+        altitude: p * cl + z * sl - WGS84_A * WGS84_A / n,
+    }
+}
+
+pub fn geodetic_to_ecef(geo: &Geodetic) -> Generic1D {
+    // Vallado Eq. 3-7
+    let e2 = WGS84_F * (2. - WGS84_F);
+    let (sl, cl) = geo.latitude.sin_cos();
+    let (so, co) = geo.longitude.sin_cos();
+    let n = WGS84_A / (1. - e2 * sl * sl).sqrt();
+    array![
+        (n + geo.altitude) * cl * co,
+        (n + geo.altitude) * cl * so,
+        (n * (1. - e2) + geo.altitude) * sl
+    ]
+}
+
+pub fn ecef_to_enu(geo: &Geodetic) -> Generic2D {
+    // Rows are the local East, North and Up axes in ECEF
+    let (sl, cl) = geo.latitude.sin_cos();
+    let (so, co) = geo.longitude.sin_cos();
+    array![
+        [-so, co, 0.],
+        [-sl * co, -sl * so, cl],
+        [cl * co, cl * so, sl]
+    ]
+}
+
+pub fn ecef_to_ned(geo: &Geodetic) -> Generic2D {
+    // Rows are the local North, East and Down axes in ECEF
+    let (sl, cl) = geo.latitude.sin_cos();
+    let (so, co) = geo.longitude.sin_cos();
+    array![
+        [-sl * co, -sl * so, cl],
+        [-so, co, 0.],
+        [-cl * co, -cl * so, -sl]
+    ]
+}
@@ -0,0 +1,5 @@
+pub mod consts;
+pub mod eop;
+pub mod geodetic;
+pub mod rotations;
+pub mod types;
@@ -0,0 +1,126 @@
+use std::f64::consts::PI;
+
+use altai_rs::meta::types::Generic2D;
+use ndarray::array;
+
+use crate::frames::consts::NUTATION_1980;
+
+// Seconds in a Julian century
+pub const SEC_PER_CENTURY: f64 = 36525. * 86400.;
+
+pub fn rot1(angle: f64) -> Generic2D {
+    let (s, c) = angle.sin_cos();
+    array![[1., 0., 0.], [0., c, s], [0., -s, c]]
+}
+
+pub fn rot2(angle: f64) -> Generic2D {
+    let (s, c) = angle.sin_cos();
+    array![[c, 0., -s], [0., 1., 0.], [s, 0., c]]
+}
+
+pub fn rot3(angle: f64) -> Generic2D {
+    let (s, c) = angle.sin_cos();
+    array![[c, s, 0.], [-s, c, 0.], [0., 0., 1.]]
+}
+
+pub fn gmst(time: f64) -> f64 {
+    // Greenwich mean sidereal time [rad]; IAU-82, Vallado Eq. 3-47
+    // time: UT1 seconds past J2000
+    let t_ut1 = time / SEC_PER_CENTURY;
+    let gmst_sec =
+        67310.54841 + (876600. * 3600. + 8640184.812866) * t_ut1 + 0.093104 * t_ut1.powi(2)
+            - 6.2e-6 * t_ut1.powi(3);
+    (gmst_sec / 240.).to_radians().rem_euclid(2. * PI)
+}
+
+pub fn eci_to_ecef_gmst(time: f64) -> Generic2D {
+    // GMST-only Earth rotation; ignores precession-nutation and polar motion
+    rot3(gmst(time))
+}
+
+/// IAU-1980 nutation angles and the mean obliquity they are referred to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Nutation {
+    pub dpsi: f64,       // Nutation in longitude [rad]
+    pub deps: f64,       // Nutation in obliquity [rad]
+    pub eps_mean: f64,   // Mean obliquity of the ecliptic [rad]
+    pub omega_moon: f64, // Mean longitude of the lunar ascending node [rad]
+}
+
+pub fn precession_iau76(t_tt: f64) -> (f64, f64, f64) {
+    // Precession angles (zeta, theta, z) [rad]; Vallado Eq. 3-88
+    // t_tt: TT Julian centuries past J2000
+    let arcsec = |x: f64| (x / 3600.).to_radians();
+    let zeta = arcsec(2306.2181 * t_tt + 0.30188 * t_tt.powi(2) + 0.017998 * t_tt.powi(3));
+    let theta = arcsec(2004.3109 * t_tt - 0.42665 * t_tt.powi(2) - 0.041833 * t_tt.powi(3));
+    let z = arcsec(2306.2181 * t_tt + 1.09468 * t_tt.powi(2) + 0.018203 * t_tt.powi(3));
+    (zeta, theta, z)
+}
+
+pub fn nutation_iau80(t_tt: f64) -> Nutation {
+    // IAU-1980 theory of nutation; Vallado Eqs. 3-82 to 3-86
+    // Delaunay arguments [deg]; r = 360 deg per revolution
+    let t2 = t_tt.powi(2);
+    let t3 = t_tt.powi(3);
+    let m_moon = 134.96298139 + (1325. * 360. + 198.8673981) * t_tt + 0.0086972 * t2 + 1.78e-5 * t3;
+    let m_sun = 357.52772333 + (99. * 360. + 359.0503400) * t_tt - 0.0001603 * t2 - 3.3e-6 * t3;
+    let u_moon = 93.27191028 + (1342. * 360. + 82.0175381) * t_tt - 0.0036825 * t2 + 3.1e-6 * t3;
+    let d_sun = 297.85036306 + (1236. * 360. + 307.1114800) * t_tt - 0.0019142 * t2 + 5.3e-6 * t3;
+    let omega_moon = 125.04452222 - (5. * 360. + 134.1362608) * t_tt + 0.0020708 * t2 + 2.2e-6 * t3;
+    let args = [m_moon, m_sun, u_moon, d_sun, omega_moon].map(|x| x.to_radians());
+
+    let (mut dpsi, mut deps) = (0., 0.);
+    for (mult, coef) in NUTATION_1980.iter() {
+        let arg: f64 = mult.iter().zip(args.iter()).map(|(k, a)| k * a).sum();
+        dpsi += (coef[0] + coef[1] * t_tt) * arg.sin();
+        deps += (coef[2] + coef[3] * t_tt) * arg.cos();
+    }
+    let eps_mean = 23.439291 - 0.0130042 * t_tt - 1.64e-7 * t2 + 5.04e-7 * t3;
+
+    Nutation {
+        dpsi: (dpsi * 1e-4 / 3600.).to_radians(),
+        deps: (deps * 1e-4 / 3600.).to_radians(),
+        eps_mean: eps_mean.to_radians(),
+        omega_moon: args[4],
+    }
+}
+
+pub fn equation_of_equinoxes(nut: &Nutation) -> f64 {
+    // IAU-1982 equation of the equinoxes with kinematic terms [rad]; Vallado Eq. 3-79
+    let kinematic = 0.00264 * nut.omega_moon.sin() + 0.000063 * (2. * nut.omega_moon).sin();
+    nut.dpsi * nut.eps_mean.cos() + (kinematic / 3600.).to_radians()
+}
+
+pub fn gcrf_to_mod(t_tt: f64) -> Generic2D {
+    // GCRF/J2000 -> mean of date, frame bias neglected
+    let (zeta, theta, z) = precession_iau76(t_tt);
+    rot3(-z).dot(&rot2(theta)).dot(&rot3(-zeta))
+}
+
+pub fn mod_to_tod(nut: &Nutation) -> Generic2D {
+    rot1(-(nut.eps_mean + nut.deps))
+        .dot(&rot3(-nut.dpsi))
+        .dot(&rot1(nut.eps_mean))
+}
+
+pub fn gcrf_to_tod(t_tt: f64) -> Generic2D {
+    mod_to_tod(&nutation_iau80(t_tt)).dot(&gcrf_to_mod(t_tt))
+}
+
+pub fn teme_to_tod(nut: &Nutation) -> Generic2D {
+    // TEME is referred to the mean equinox: geometric equation of the equinoxes only
+    rot3(-nut.dpsi * nut.eps_mean.cos())
+}
+
+pub fn gcrf_to_teme(t_tt: f64) -> Generic2D {
+    let nut = nutation_iau80(t_tt);
+    teme_to_tod(&nut)
+        .t()
+        .dot(&mod_to_tod(&nut))
+        .dot(&gcrf_to_mod(t_tt))
+}
+
+pub fn pef_to_itrf(xp: f64, yp: f64) -> Generic2D {
+    // Polar motion [rad]; transpose of Vallado Eq. 3-77
+    rot2(-xp).dot(&rot1(-yp))
+}
@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use altai_rs::meta::types::{Generic1D, Generic2D};
+use ndarray::{array, ArrayView1};
+
+use crate::ephemeris::consts::OMEGA_E;
+use crate::frames::eop::EopData;
+use crate::frames::rotations::{
+    equation_of_equinoxes, gcrf_to_mod, gmst, mod_to_tod, nutation_iau80, pef_to_itrf, rot3,
+    SEC_PER_CENTURY,
+};
+use crate::time::consts::SEC_PER_DAY;
+use crate::time::types::{Epoch, TimeScale};
+
+/// Inertial-to-Earth-fixed rotation selectable from `SpacecraftEphemerisArchitecture`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EarthRotationModel {
+    // Sidereal rotation about the ECI z-axis only
+    #[default]
+    GmstOnly,
+    // IAU-76 precession, IAU-80 nutation, GAST and polar motion
+    Iau76Fk5,
+}
+
+/// Earth orientation at an instant, from the EOP table when one is loaded.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EarthOrientation {
+    pub ut1: f64, // UT1 seconds past J2000 [s]
+    pub xp: f64,  // Polar motion [rad]
+    pub yp: f64,  // Polar motion [rad]
+    pub lod: f64, // Excess length of day [s]
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FrameModel {
+    pub rotation: EarthRotationModel,
+    pub eop: Option<Arc<EopData>>,
+    pub dut1: f64, // UT1 - UTC used when no EOP record covers the epoch [s]
+}
+
+impl FrameModel {
+    pub fn earth_orientation(&self, time: f64) -> EarthOrientation {
+        // time: TT seconds past J2000
+        let epoch = Epoch::from_j2000_seconds(TimeScale::TT, time);
+        let utc = epoch.seconds_since_j2000(TimeScale::UTC);
+        match self
+            .eop
+            .as_ref()
+            .and_then(|eop| eop.at(epoch.mjd(TimeScale::UTC)))
+        {
+            Some(rec) => EarthOrientation {
+                ut1: utc + rec.dut1,
+                xp: rec.xp,
+                yp: rec.yp,
+                lod: rec.lod,
+            },
+            None => EarthOrientation {
+                ut1: utc + self.dut1,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// ECI -> TOD (or ECI for GMST-only), sidereal angle and Earth orientation.
+    fn components(&self, time: f64) -> (Generic2D, f64, EarthOrientation) {
+        let eo = self.earth_orientation(time);
+        match self.rotation {
+            EarthRotationModel::GmstOnly => (Generic2D::eye(3), gmst(eo.ut1), eo),
+            EarthRotationModel::Iau76Fk5 => {
+                let t_tt = time / SEC_PER_CENTURY;
+                let nut = nutation_iau80(t_tt);
+                let gast = gmst(eo.ut1) + equation_of_equinoxes(&nut);
+                (mod_to_tod(&nut).dot(&gcrf_to_mod(t_tt)), gast, eo)
+            }
+        }
+    }
+
+    fn polar_motion(&self, eo: &EarthOrientation) -> Generic2D {
+        match self.rotation {
+            EarthRotationModel::GmstOnly => Generic2D::eye(3),
+            EarthRotationModel::Iau76Fk5 => pef_to_itrf(eo.xp, eo.yp),
+        }
+    }
+
+    /// Rotation matrix ECI (GCRF) -> ECEF (ITRF) at TT seconds past J2000.
+    pub fn eci_to_ecef(&self, time: f64) -> Generic2D {
+        let (eci_to_tod, theta, eo) = self.components(time);
+        self.polar_motion(&eo).dot(&rot3(theta)).dot(&eci_to_tod)
+    }
+
+    pub fn rv_eci_to_ecef(
+        &self,
+        time: f64,
+        r_eci: &ArrayView1<f64>,
+        v_eci: &ArrayView1<f64>,
+    ) -> (Generic1D, Generic1D) {
+        /*
+        Position and Earth-relative velocity; Vallado Eq. 3-80
+
+        Inputs:
+        time: TT seconds past J2000 [s]
+        r_eci, v_eci: R/V-vectors (ECI) [m], [m/s]
+
+        Outputs:
+        R/V-vectors (ECEF) [m], [m/s]
+        */
+        let (eci_to_tod, theta, eo) = self.components(time);
+        let tod_to_pef = rot3(theta);
+        let pef_to_ecef = self.polar_motion(&eo);
+
+        let omega = OMEGA_E * (1. - eo.lod / SEC_PER_DAY);
+        let r_pef = tod_to_pef.dot(&eci_to_tod.dot(r_eci));
+        let v_pef = tod_to_pef.dot(&eci_to_tod.dot(v_eci))
+            - array![-omega * r_pef[1], omega * r_pef[0], 0.];
+        (pef_to_ecef.dot(&r_pef), pef_to_ecef.dot(&v_pef))
+    }
+}
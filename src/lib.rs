@@ -2,108 +2,189 @@ pub mod attitude;
 pub mod ephemeris;
 
 pub mod actuators;
+pub mod frames;
 pub mod ode;
 pub mod sc_types;
 pub mod sensors;
+pub mod time;
 
-use actuators::types::TruthActuatorBus;
-use attitude::types::{TruthAttitudeBus, TruthAttitudeSignal, TruthMultibodyBus};
+use actuators::types::{ActuatorCommands, TruthActuatorBus};
+use attitude::types::{TruthAttitudeBus, TruthMultibodyBus};
 use ephemeris::types::TruthEphemerisBus;
 use polaris_fsw::actuators::types::ActuatorBus;
 use polaris_fsw::sensors::types::RawSensorBus;
-use sc_types::{SpacecraftParamBus, SpacecraftState};
+use sc_types::{ConfigurationError, SpacecraftParamBus, SpacecraftState};
 
-use log;
 use sensors::types::TruthSensorBus;
+use time::types::{Epoch, TimeScale};
 
 #[derive(Clone, Debug)]
 pub struct Spacecraft {
     pub sim_time: f64,
+    pub epoch: Epoch,
     pub ts: f64,
     pub sc_param_bus: SpacecraftParamBus,
     pub prev_sc_state: SpacecraftState,
     pub curr_sc_state: SpacecraftState,
 }
 impl Spacecraft {
-    pub fn initialize(SC_Ts: f64, param_bus: SpacecraftParamBus) -> Self {
+    pub fn initialize(
+        sc_ts: f64,
+        param_bus: SpacecraftParamBus,
+    ) -> Result<Self, ConfigurationError> {
         log::trace!("Initializing Plant");
 
+        log::trace!("Validating Mass Properties");
+        param_bus.sc_mass.validate()?;
+
+        log::trace!("Validating Actuators");
+        param_bus.sc_actuators.validate(&param_bus.sc_mass)?;
+
+        log::trace!("Validating Sensors");
+        param_bus.sc_sensors.validate()?;
+
+        log::trace!("Validating Orbit and Force Models");
+        param_bus.sc_ephemeris.validate()?;
+
+        log::trace!("Initializing Actuator Bus");
+        let act_bus = TruthActuatorBus::initialize(sc_ts, param_bus.sc_actuators.clone());
+
         log::trace!("Initializing Attitude Bus");
-        let att_bus = TruthAttitudeBus::initialize(SC_Ts, param_bus.sc_attitude.clone());
+        let att_bus = TruthAttitudeBus::initialize(
+            sc_ts,
+            param_bus.sc_attitude.clone(),
+            &param_bus.sc_actuators,
+        );
+
+        log::trace!("Initializing Multibody Bus");
+        let multibody_bus =
+            TruthMultibodyBus::initialize(&param_bus.sc_mass, &act_bus, &att_bus.signal);
+
+        log::trace!("Initializing Ephemeris Bus");
+        let mut ephem_bus = TruthEphemerisBus::initialize(sc_ts, param_bus.sc_ephemeris.clone())?;
+        ephem_bus.start(param_bus.epoch_time(0.))?;
+        ephem_bus.signal.update_body_vectors(&att_bus.signal);
+
+        log::trace!("Initializing Sensor Bus");
+        let sensor_bus =
+            TruthSensorBus::initialize(sc_ts, param_bus.sc_sensors.clone(), &att_bus.signal);
+
+        let sc_state = SpacecraftState::initialize(
+            sc_ts,
+            Some(act_bus),
+            Some(ephem_bus),
+            Some(att_bus),
+            Some(multibody_bus),
+            Some(sensor_bus),
+        );
 
         // Initialize Params
-        Self {
+        // Both buffers start from the configured state so the buses keep their
+        // configuration after the first swap
+        Ok(Self {
             sim_time: 0.,
-            ts: SC_Ts,
+            epoch: param_bus.epoch,
+            ts: sc_ts,
             sc_param_bus: param_bus,
-            prev_sc_state: SpacecraftState::initialize(SC_Ts, None, None, None, None, None),
-            curr_sc_state: SpacecraftState::initialize(
-                SC_Ts,
-                None,
-                None,
-                Some(att_bus),
-                None,
-                None,
-            ),
-        }
+            prev_sc_state: sc_state.clone(),
+            curr_sc_state: sc_state,
+        })
     }
 
     pub fn initial_state(&self) -> RawSensorBus {
-        RawSensorBus {}
+        self.curr_sc_state.truth_sensor_bus.to_raw_bus()
+    }
+
+    pub fn simulate_plant(&mut self, actuator_bus: &ActuatorBus) -> RawSensorBus {
+        self.simulate_plant_commands(&ActuatorCommands::from(actuator_bus))
     }
 
-    pub fn simulate_plant(&mut self, actuator_commands: &ActuatorBus) -> RawSensorBus {
+    /// One plant step driven by plant-side actuator commands.
+    pub fn simulate_plant_commands(
+        &mut self,
+        actuator_commands: &ActuatorCommands,
+    ) -> RawSensorBus {
         log::trace!("Running GNC Plant Loop");
 
         // Update prev/curr
         std::mem::swap(&mut self.curr_sc_state, &mut self.prev_sc_state);
 
+        // Mass properties at the start of the step, held over it
+        let mass_props = self
+            .prev_sc_state
+            .truth_actuator_bus
+            .mass_properties(&self.sc_param_bus.sc_mass);
+
         // Read Actuators
-        self.curr_sc_state.truth_actuator_bus = TruthActuatorBus::process(
+        self.curr_sc_state.truth_actuator_bus.process(
             // Curr State
             actuator_commands,
+            &mass_props,
             // Prev State
+            &self.prev_sc_state.truth_attitude.signal,
+            &self.prev_sc_state.truth_ephemeris.signal,
             &self.prev_sc_state.truth_actuator_bus,
         );
 
+        // Dynamical time [TT s past J2000] at the start of this step
+        let time = self.epoch.seconds_since_j2000(TimeScale::TT);
+
         // Update Dynamics
-        // // Update Ephemeris Dynamics
-        self.curr_sc_state.truth_ephemeris.process(
-            // Curr State
-            &self.curr_sc_state.truth_actuator_bus,
-            // Prev State
-            &self.prev_sc_state.truth_ephemeris,
-        );
+        // Order: actuators -> attitude -> ephemeris -> multibody
+        // // Environmental torques at the start of the step
+        let env_torques = self
+            .prev_sc_state
+            .truth_ephemeris
+            .srp_torque(&mass_props, &self.prev_sc_state.truth_attitude.signal);
 
         // // Update Attitude Dynamics
         self.curr_sc_state.truth_attitude.process(
             // Current State
+            time,
+            &mass_props,
             &self.curr_sc_state.truth_actuator_bus,
+            &env_torques,
             // Prev State
             &self.prev_sc_state.truth_attitude,
         );
 
-        // // Update Multibody Dynamics
-        self.curr_sc_state.truth_multibody = TruthMultibodyBus::process(
+        // // Update Ephemeris Dynamics
+        // Body-frame forces are held over the step at the attitude they were
+        // applied in, i.e. the attitude at the start of the step
+        self.curr_sc_state.truth_ephemeris.process(
             // Curr State
+            time,
+            &mass_props,
             &self.curr_sc_state.truth_actuator_bus,
             // Prev State
-            &self.prev_sc_state.truth_multibody,
+            &self.prev_sc_state.truth_attitude.signal,
+            &self.prev_sc_state.truth_ephemeris,
+        );
+        self.curr_sc_state
+            .truth_ephemeris
+            .signal
+            .update_body_vectors(&self.curr_sc_state.truth_attitude.signal);
+
+        // // Update Multibody Dynamics
+        self.curr_sc_state.truth_multibody.process(
+            // Curr State
+            &mass_props,
+            &self.curr_sc_state.truth_actuator_bus,
+            &self.curr_sc_state.truth_attitude.signal,
         );
 
         // Simulate Sensor Data
         self.curr_sc_state.truth_sensor_bus = TruthSensorBus::process(
             // Curr State
-            actuator_commands,
             &self.curr_sc_state.truth_actuator_bus,
             &self.curr_sc_state.truth_ephemeris,
             &self.curr_sc_state.truth_attitude,
-            &self.curr_sc_state.truth_multibody,
             // Prev State
             &self.prev_sc_state.truth_sensor_bus,
         );
 
         self.sim_time += self.ts;
+        self.epoch = self.epoch.advance(self.ts);
 
         // Send RawSensorBus
         self.curr_sc_state.truth_sensor_bus.to_raw_bus()
@@ -8,7 +8,7 @@ pub struct RK5(pub f64);
 
 pub trait Integrator {
     fn integrate<F>(
-        &self,
+        &mut self,
         d_func: &F,
         time: f64,
         state0: &Generic1D,
@@ -20,7 +20,7 @@ pub trait Integrator {
 
 impl Integrator for RK2 {
     fn integrate<F>(
-        &self,
+        &mut self,
         d_func: &F,
         time: f64,
         state0: &Generic1D,
@@ -39,7 +39,7 @@ impl Integrator for RK2 {
 
 impl Integrator for RK5 {
     fn integrate<F>(
-        &self,
+        &mut self,
         d_func: &F,
         time: f64,
         state0: &Generic1D,
@@ -59,7 +59,7 @@ impl Integrator for RK5 {
                 .zip(k2.iter())
                 .map(|(&a, &b)| 4. / 25. * a + 6. / 25. * b),
         );
-        let k3 = d_func(time + 2. * self.0 / 25., &(state0 + self.0 * kn), inputs);
+        let k3 = d_func(time + 2. / 5. * self.0, &(state0 + self.0 * kn), inputs);
 
         kn = Generic1D::from_iter(
             k1.iter()
@@ -82,3 +82,248 @@ impl Integrator for RK5 {
         state0 + self.0 * (23. / 192. * k1 + 125. / 192. * k3 - 27. / 64. * k5 + 125. / 192. * k6)
     }
 }
+
+/// Embedded Dormand-Prince 5(4) integrator with adaptive sub-stepping.
+///
+/// Each call to `integrate` advances the state across one full plant step
+/// (`ts`), taking as many internal sub-steps as the tolerances require. The
+/// last accepted sub-step size is carried over to the next plant step, and
+/// the statistics cover the most recent plant step only.
+#[derive(Debug, Clone)]
+pub struct DormandPrince45 {
+    pub ts: f64,
+    pub atol: f64,
+    pub rtol: f64,
+    pub min_step: f64,
+    pub max_substeps: usize,
+    step: f64,
+    stats: IntegratorStats,
+}
+
+/// Counters for one plant step of an adaptive integrator.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IntegratorStats {
+    pub accepted_steps: usize,
+    pub rejected_steps: usize,
+    pub function_evals: usize,
+}
+
+// Dormand-Prince 5(4) tableau
+const DP_C: [f64; 6] = [1. / 5., 3. / 10., 4. / 5., 8. / 9., 1., 1.];
+const DP_A: [&[f64]; 5] = [
+    &[1. / 5.],
+    &[3. / 40., 9. / 40.],
+    &[44. / 45., -56. / 15., 32. / 9.],
+    &[
+        19372. / 6561.,
+        -25360. / 2187.,
+        64448. / 6561.,
+        -212. / 729.,
+    ],
+    &[
+        9017. / 3168.,
+        -355. / 33.,
+        46732. / 5247.,
+        49. / 176.,
+        -5103. / 18656.,
+    ],
+];
+// 5th order weights (also the stage-7 row; FSAL)
+const DP_B: [f64; 6] = [
+    35. / 384.,
+    0.,
+    500. / 1113.,
+    125. / 192.,
+    -2187. / 6784.,
+    11. / 84.,
+];
+// 5th order minus embedded 4th order weights
+const DP_E: [f64; 7] = [
+    35. / 384. - 5179. / 57600.,
+    0.,
+    500. / 1113. - 7571. / 16695.,
+    125. / 192. - 393. / 640.,
+    -2187. / 6784. + 92097. / 339200.,
+    11. / 84. - 187. / 2100.,
+    -1. / 40.,
+];
+
+const DP_SAFETY: f64 = 0.9;
+const DP_MIN_FACTOR: f64 = 0.2;
+const DP_MAX_FACTOR: f64 = 5.;
+
+fn stage(state0: &Generic1D, dt: f64, coeffs: &[f64], ks: &[Generic1D]) -> Generic1D {
+    let mut inc = Generic1D::zeros(state0.len());
+    for (&a, k) in coeffs.iter().zip(ks.iter()) {
+        if a != 0. {
+            inc.scaled_add(a * dt, k);
+        }
+    }
+    state0 + &inc
+}
+
+impl DormandPrince45 {
+    pub fn new(ts: f64, atol: f64, rtol: f64) -> Self {
+        Self {
+            ts,
+            atol,
+            rtol,
+            min_step: 1e-9 * ts.abs(),
+            max_substeps: 100_000,
+            step: ts,
+            stats: IntegratorStats::default(),
+        }
+    }
+
+    pub fn stats(&self) -> IntegratorStats {
+        self.stats
+    }
+
+    fn error_norm(&self, state0: &Generic1D, state1: &Generic1D, err: &Generic1D) -> f64 {
+        let n = err.len().max(1) as f64;
+        let sum_sq: f64 = err
+            .iter()
+            .zip(state0.iter().zip(state1.iter()))
+            .map(|(&e, (&y0, &y1))| {
+                let sc = self.atol + self.rtol * y0.abs().max(y1.abs());
+                (e / sc).powi(2)
+            })
+            .sum();
+        (sum_sq / n).sqrt()
+    }
+}
+
+impl Integrator for DormandPrince45 {
+    fn integrate<F>(
+        &mut self,
+        d_func: &F,
+        time: f64,
+        state0: &Generic1D,
+        inputs: &Generic2D,
+    ) -> Generic1D
+    where
+        F: Fn(f64, &Generic1D, &Generic2D) -> Generic1D,
+    {
+        let mut stats = IntegratorStats::default();
+        let t_end = time + self.ts;
+        let mut t = time;
+        let mut state = state0.to_owned();
+        let mut dt = self.step.min(self.ts).max(self.min_step);
+
+        let mut k1 = d_func(t, &state, inputs);
+        stats.function_evals += 1;
+
+        let mut substeps = 0;
+        while t_end - t > 1e-12 * self.ts.abs().max(1.) {
+            // Do not step past the end of the plant step
+            let last = t + dt >= t_end;
+            let h = if last { t_end - t } else { dt };
+
+            let mut ks = vec![k1.clone()];
+            for (c, a_row) in DP_C.iter().zip(DP_A.iter()) {
+                ks.push(d_func(t + c * h, &stage(&state, h, a_row, &ks), inputs));
+            }
+            let nstate = stage(&state, h, &DP_B, &ks);
+            let k7 = d_func(t + DP_C[5] * h, &nstate, inputs);
+            ks.push(k7.clone());
+            stats.function_evals += 6;
+
+            let mut err = Generic1D::zeros(state.len());
+            for (&e, k) in DP_E.iter().zip(ks.iter()) {
+                err.scaled_add(e * h, k);
+            }
+            let err_norm = self.error_norm(&state, &nstate, &err);
+
+            let factor = if err_norm == 0. {
+                DP_MAX_FACTOR
+            } else {
+                (DP_SAFETY * err_norm.powf(-0.2)).clamp(DP_MIN_FACTOR, DP_MAX_FACTOR)
+            };
+
+            substeps += 1;
+            if err_norm <= 1. || h <= self.min_step || substeps >= self.max_substeps {
+                if err_norm > 1. {
+                    log::warn!(
+                        "DormandPrince45 accepting step at t = {} with error norm {}",
+                        t,
+                        err_norm
+                    );
+                }
+                t += h;
+                state = nstate;
+                k1 = k7;
+                stats.accepted_steps += 1;
+                // A truncated final sub-step should not shrink the carried-over step
+                dt = if last {
+                    dt.max(h * factor)
+                } else {
+                    (h * factor).max(self.min_step)
+                };
+            } else {
+                stats.rejected_steps += 1;
+                dt = (h * factor).max(self.min_step);
+            }
+        }
+
+        self.step = dt;
+        self.stats = stats;
+        state
+    }
+}
+
+/// Integration method selectable from the spacecraft architecture.
+#[derive(Debug, Clone, Default)]
+pub enum Method {
+    RK2,
+    #[default]
+    RK5,
+    DormandPrince45 {
+        atol: f64,
+        rtol: f64,
+    },
+}
+
+/// Concrete integrator held by the truth buses.
+#[derive(Debug, Clone)]
+pub enum Solver {
+    RK2(RK2),
+    RK5(RK5),
+    DormandPrince45(DormandPrince45),
+}
+impl Solver {
+    pub fn initialize(method: &Method, ts: f64) -> Self {
+        match *method {
+            Method::RK2 => Self::RK2(RK2(ts)),
+            Method::RK5 => Self::RK5(RK5(ts)),
+            Method::DormandPrince45 { atol, rtol } => {
+                Self::DormandPrince45(DormandPrince45::new(ts, atol, rtol))
+            }
+        }
+    }
+
+    pub fn stats(&self) -> Option<IntegratorStats> {
+        match self {
+            Self::DormandPrince45(dp) => Some(dp.stats()),
+            _ => None,
+        }
+    }
+}
+
+impl Integrator for Solver {
+    fn integrate<F>(
+        &mut self,
+        d_func: &F,
+        time: f64,
+        state0: &Generic1D,
+        inputs: &Generic2D,
+    ) -> Generic1D
+    where
+        F: Fn(f64, &Generic1D, &Generic2D) -> Generic1D,
+    {
+        match self {
+            Self::RK2(rk) => rk.integrate(d_func, time, state0, inputs),
+            Self::RK5(rk) => rk.integrate(d_func, time, state0, inputs),
+            Self::DormandPrince45(dp) => dp.integrate(d_func, time, state0, inputs),
+        }
+    }
+}
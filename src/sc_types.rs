@@ -1,14 +1,43 @@
 use crate::{
-    actuators::types::TruthActuatorBus,
-    attitude::types::{TruthAttitudeBus, TruthMultibodyBus},
-    ephemeris::{consts, types::TruthEphemerisBus},
-    sensors::types::TruthSensorBus,
+    actuators::{
+        cmg::ControlMomentGyroCluster,
+        magnetorquers::MagnetorquerAssembly,
+        reaction_wheels::ReactionWheelAssembly,
+        thrusters::ThrusterAssembly,
+        types::{ActuatorError, TruthActuatorBus},
+    },
+    attitude::{
+        kinedynamics::sym_eigenvalues_3x3,
+        types::{TruthAttitudeBus, TruthMultibodyBus},
+    },
+    ephemeris::{
+        atmosphere::SpaceWeather,
+        celestial::ShadowModel,
+        consts,
+        magnetic::MagneticFieldModel,
+        srp::SrpModel,
+        types::{
+            DragModel, ForceModelError, GravityModel, InitialOrbit, InitialOrbitError,
+            OrbitPropagator, ThirdBodyModel, TruthEphemerisBus,
+        },
+    },
+    frames::types::FrameModel,
+    ode,
+    sensors::{
+        gyro::Gyro,
+        magnetometer::Magnetometer,
+        star_tracker::StarTracker,
+        sun_sensors::{CoarseSunSensorArray, FineSunSensorArray},
+        types::{SensorError, TruthSensorBus},
+    },
+    time::types::{Epoch, TimeScale},
 };
 use ndarray::array;
+use std::fmt;
 
-use altai_rs::meta::types::{Quaternion4, Vector3};
+use altai_rs::meta::types::{Generic2D, Quaternion4, Vector3};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct SpacecraftState {
     pub truth_actuator_bus: TruthActuatorBus,
     pub truth_ephemeris: TruthEphemerisBus,
@@ -18,7 +47,7 @@ pub struct SpacecraftState {
 }
 impl SpacecraftState {
     pub fn initialize(
-        _SC_Ts: f64,
+        _sc_ts: f64,
         initial_actuator: Option<TruthActuatorBus>,
         initial_ephem: Option<TruthEphemerisBus>,
         initial_attitude: Option<TruthAttitudeBus>,
@@ -26,23 +55,11 @@ impl SpacecraftState {
         initial_sensor: Option<TruthSensorBus>,
     ) -> Self {
         Self {
-            truth_actuator_bus: initial_actuator.unwrap_or(TruthActuatorBus::default()),
-            truth_ephemeris: initial_ephem.unwrap_or(TruthEphemerisBus::default()),
-            truth_attitude: initial_attitude.unwrap_or(TruthAttitudeBus::default()),
-            truth_multibody: initial_multibody.unwrap_or(TruthMultibodyBus::default()),
-            truth_sensor_bus: initial_sensor.unwrap_or(TruthSensorBus::default()),
-        }
-    }
-}
-
-impl Default for SpacecraftState {
-    fn default() -> Self {
-        Self {
-            truth_actuator_bus: TruthActuatorBus::default(),
-            truth_ephemeris: TruthEphemerisBus::default(),
-            truth_attitude: TruthAttitudeBus::default(),
-            truth_multibody: TruthMultibodyBus::default(),
-            truth_sensor_bus: TruthSensorBus::default(),
+            truth_actuator_bus: initial_actuator.unwrap_or_default(),
+            truth_ephemeris: initial_ephem.unwrap_or_default(),
+            truth_attitude: initial_attitude.unwrap_or_default(),
+            truth_multibody: initial_multibody.unwrap_or_default(),
+            truth_sensor_bus: initial_sensor.unwrap_or_default(),
         }
     }
 }
@@ -50,55 +67,262 @@ impl Default for SpacecraftState {
 pub trait SpacecraftParam {}
 #[derive(Clone, Default, Debug)]
 pub struct SpacecraftParamBus {
+    // Simulation start epoch
+    pub epoch: Epoch,
+    pub sc_mass: SpacecraftMassArchitecture,
     pub sc_actuators: SpacecraftActuatorArchitecture,
     pub sc_ephemeris: SpacecraftEphemerisArchitecture,
     pub sc_attitude: SpacecraftAttitudeArchitecture,
-    pub sc_multibody: SpacecraftMultibodyArchitecture,
     pub sc_sensors: SpacecraftSensorArchitecture,
 }
 
 impl SpacecraftParamBus {
     pub fn initialize(
+        sc_mass: SpacecraftMassArchitecture,
         sc_actuators: SpacecraftActuatorArchitecture,
         sc_ephemeris: SpacecraftEphemerisArchitecture,
         sc_attitude: SpacecraftAttitudeArchitecture,
-        sc_multibody: SpacecraftMultibodyArchitecture,
         sc_sensors: SpacecraftSensorArchitecture,
     ) -> Self {
         Self {
+            epoch: Epoch::default(),
+            sc_mass,
             sc_actuators,
             sc_ephemeris,
             sc_attitude,
-            sc_multibody,
             sc_sensors,
         }
     }
+
+    pub fn with_epoch(mut self, epoch: Epoch) -> Self {
+        self.epoch = epoch;
+        self
+    }
+
+    /// Dynamical time [TT s past J2000] for a given simulation time.
+    pub fn epoch_time(&self, sim_time: f64) -> f64 {
+        self.epoch
+            .advance(sim_time)
+            .seconds_since_j2000(TimeScale::TT)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MassPropertiesError {
+    NonPositiveMass(f64),
+    NotSymmetric,
+    NotPositiveDefinite([f64; 3]),
+    TriangleInequality([f64; 3]),
+}
+impl fmt::Display for MassPropertiesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonPositiveMass(m) => write!(f, "mass {m} kg must be positive"),
+            Self::NotSymmetric => write!(f, "inertia tensor is not symmetric"),
+            Self::NotPositiveDefinite(ev) => {
+                write!(f, "inertia tensor is not positive definite: {ev:?}")
+            }
+            Self::TriangleInequality(ev) => write!(
+                f,
+                "principal moments {ev:?} violate the triangle inequality"
+            ),
+        }
+    }
+}
+impl std::error::Error for MassPropertiesError {}
+
+/// Reason a spacecraft configuration was rejected at initialization.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigurationError {
+    MassProperties(MassPropertiesError),
+    Actuators(ActuatorError),
+    Sensors(SensorError),
+    InitialOrbit(InitialOrbitError),
+    ForceModel(ForceModelError),
+}
+impl fmt::Display for ConfigurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MassProperties(err) => write!(f, "invalid mass properties: {err}"),
+            Self::Actuators(err) => write!(f, "invalid actuator configuration: {err}"),
+            Self::Sensors(err) => write!(f, "invalid sensor configuration: {err}"),
+            Self::InitialOrbit(err) => write!(f, "invalid initial orbit: {err}"),
+            Self::ForceModel(err) => write!(f, "invalid force model: {err}"),
+        }
+    }
+}
+impl std::error::Error for ConfigurationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MassProperties(err) => Some(err),
+            Self::Actuators(err) => Some(err),
+            Self::Sensors(err) => Some(err),
+            Self::InitialOrbit(err) => Some(err),
+            Self::ForceModel(err) => Some(err),
+        }
+    }
+}
+impl From<MassPropertiesError> for ConfigurationError {
+    fn from(err: MassPropertiesError) -> Self {
+        Self::MassProperties(err)
+    }
+}
+impl From<ActuatorError> for ConfigurationError {
+    fn from(err: ActuatorError) -> Self {
+        Self::Actuators(err)
+    }
+}
+impl From<SensorError> for ConfigurationError {
+    fn from(err: SensorError) -> Self {
+        Self::Sensors(err)
+    }
+}
+impl From<InitialOrbitError> for ConfigurationError {
+    fn from(err: InitialOrbitError) -> Self {
+        Self::InitialOrbit(err)
+    }
+}
+impl From<ForceModelError> for ConfigurationError {
+    fn from(err: ForceModelError) -> Self {
+        Self::ForceModel(err)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SpacecraftMassArchitecture {
+    pub mass: f64,          // Total mass [kg]
+    pub j_sc: Generic2D,    // Inertia tensor about the CoM, body frame [kg m2]
+    pub r_cm_body: Vector3, // Center of mass in the body frame [m]
+}
+impl SpacecraftParam for SpacecraftMassArchitecture {}
+impl SpacecraftMassArchitecture {
+    pub fn initialize(
+        mass: f64,
+        j_sc: Generic2D,
+        r_cm_body: Vector3,
+    ) -> Result<Self, MassPropertiesError> {
+        let props = Self {
+            mass,
+            j_sc,
+            r_cm_body,
+        };
+        props.validate()?;
+        Ok(props)
+    }
+
+    pub fn validate(&self) -> Result<(), MassPropertiesError> {
+        if self.mass.is_nan() || self.mass <= 0. {
+            return Err(MassPropertiesError::NonPositiveMass(self.mass));
+        }
+
+        let tol = 1e-9 * self.j_sc.iter().fold(0., |acc: f64, x| acc.max(x.abs()));
+        for (r, c) in [(0, 1), (0, 2), (1, 2)] {
+            if (self.j_sc[[r, c]] - self.j_sc[[c, r]]).abs() > tol {
+                return Err(MassPropertiesError::NotSymmetric);
+            }
+        }
+
+        let ev = sym_eigenvalues_3x3(&self.j_sc);
+        if ev.iter().any(|&x| x <= 0.) {
+            return Err(MassPropertiesError::NotPositiveDefinite(ev));
+        }
+        // Largest principal moment cannot exceed the sum of the other two
+        if ev[0] > ev[1] + ev[2] + tol {
+            return Err(MassPropertiesError::TriangleInequality(ev));
+        }
+        Ok(())
+    }
+}
+impl Default for SpacecraftMassArchitecture {
+    fn default() -> Self {
+        Self {
+            mass: 100.,
+            j_sc: array![[10., 0., 0.], [0., 20., 0.], [0., 0., 30.]],
+            r_cm_body: array![[0.], [0.], [0.]],
+        }
+    }
 }
 
 #[derive(Clone, Default, Debug)]
-pub struct SpacecraftActuatorArchitecture {}
+pub struct SpacecraftActuatorArchitecture {
+    pub reaction_wheels: ReactionWheelAssembly,
+    pub magnetorquers: MagnetorquerAssembly,
+    pub thrusters: ThrusterAssembly,
+    pub control_moment_gyros: ControlMomentGyroCluster,
+}
 impl SpacecraftParam for SpacecraftActuatorArchitecture {}
+impl SpacecraftActuatorArchitecture {
+    pub fn validate(&self, mass_props: &SpacecraftMassArchitecture) -> Result<(), ActuatorError> {
+        self.reaction_wheels.validate()?;
+        self.magnetorquers.validate()?;
+        self.thrusters.validate(mass_props.mass)?;
+        self.control_moment_gyros.validate()
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct SpacecraftEphemerisArchitecture {
-    pub r_sc_eci: Vector3,
-    pub v_sc_eci: Vector3,
+    pub initial_orbit: InitialOrbit,
+    pub gravity: GravityModel,
+    pub drag: Option<DragModel>,
+    pub space_weather: SpaceWeather,
+    pub srp: Option<SrpModel>,
+    pub shadow: ShadowModel,
+    pub third_body: ThirdBodyModel,
+    pub frames: FrameModel,
+    pub magnetic_field: MagneticFieldModel,
+    pub altitude_floor: f64, // Geodetic altitude that flags re-entry [m]
+    pub propagator: OrbitPropagator,
+    pub integrator: ode::Method,
 }
 impl SpacecraftParam for SpacecraftEphemerisArchitecture {}
 impl SpacecraftEphemerisArchitecture {
     pub fn initialize(r_sc: Vector3, v_sc: Vector3) -> Self {
-        Self {
+        Self::from_initial_orbit(InitialOrbit::Cartesian {
             r_sc_eci: r_sc,
             v_sc_eci: v_sc,
+        })
+    }
+
+    pub fn from_initial_orbit(initial_orbit: InitialOrbit) -> Self {
+        Self {
+            initial_orbit,
+            ..Default::default()
         }
     }
+
+    pub fn validate(&self) -> Result<(), ConfigurationError> {
+        if self.propagator == OrbitPropagator::Sgp4
+            && !matches!(self.initial_orbit, InitialOrbit::Tle(_))
+        {
+            return Err(InitialOrbitError::MissingElementSet.into());
+        }
+        self.gravity.validate()?;
+        self.drag.as_ref().map_or(Ok(()), DragModel::validate)?;
+        self.srp.as_ref().map_or(Ok(()), SrpModel::validate)?;
+        self.space_weather.validate()?;
+        Ok(())
+    }
 }
 impl Default for SpacecraftEphemerisArchitecture {
     fn default() -> Self {
         let a_sc = consts::RE + 500e3;
         Self {
-            r_sc_eci: array![[a_sc], [0.], [0.]],
-            v_sc_eci: array![[0.], [(consts::MU / a_sc).sqrt()], [0.]],
+            initial_orbit: InitialOrbit::Cartesian {
+                r_sc_eci: array![[a_sc], [0.], [0.]],
+                v_sc_eci: array![[0.], [(consts::MU / a_sc).sqrt()], [0.]],
+            },
+            gravity: GravityModel::default(),
+            drag: None,
+            space_weather: SpaceWeather::default(),
+            srp: None,
+            shadow: ShadowModel::default(),
+            third_body: ThirdBodyModel::default(),
+            frames: FrameModel::default(),
+            magnetic_field: MagneticFieldModel::default(),
+            altitude_floor: 100e3,
+            propagator: OrbitPropagator::default(),
+            integrator: ode::Method::default(),
         }
     }
 }
@@ -107,7 +331,7 @@ impl Default for SpacecraftEphemerisArchitecture {
 pub struct SpacecraftAttitudeArchitecture {
     pub q_sc_eci: Quaternion4,
     pub omega_sc: Vector3,
-    pub alpha_sc: Vector3,
+    pub integrator: ode::Method,
 }
 impl SpacecraftParam for SpacecraftAttitudeArchitecture {}
 impl SpacecraftAttitudeArchitecture {
@@ -124,23 +348,34 @@ impl Default for SpacecraftAttitudeArchitecture {
         Self {
             q_sc_eci: array![[0.], [0.], [0.], [1.]],
             omega_sc: array![[0.], [0.], [0.]],
-            alpha_sc: array![[0.], [0.], [0.]],
+            integrator: ode::Method::default(),
         }
     }
 }
 
 #[derive(Clone, Default, Debug)]
-pub struct SpacecraftMultibodyArchitecture {
-    pub j_multibody: Vector3,
+pub struct SpacecraftSensorArchitecture {
+    pub gyro: Option<Gyro>,
+    pub star_tracker: Option<StarTracker>,
+    pub coarse_sun_sensors: Option<CoarseSunSensorArray>,
+    pub fine_sun_sensors: Option<FineSunSensorArray>,
+    pub magnetometer: Option<Magnetometer>,
 }
-impl SpacecraftParam for SpacecraftMultibodyArchitecture {}
-
-impl SpacecraftMultibodyArchitecture {
-    pub fn initialize(j_multibody: Vector3) -> Self {
-        Self { j_multibody }
+impl SpacecraftParam for SpacecraftSensorArchitecture {}
+impl SpacecraftSensorArchitecture {
+    pub fn validate(&self) -> Result<(), SensorError> {
+        self.gyro.as_ref().map_or(Ok(()), Gyro::validate)?;
+        self.star_tracker
+            .as_ref()
+            .map_or(Ok(()), StarTracker::validate)?;
+        self.coarse_sun_sensors
+            .as_ref()
+            .map_or(Ok(()), CoarseSunSensorArray::validate)?;
+        self.fine_sun_sensors
+            .as_ref()
+            .map_or(Ok(()), FineSunSensorArray::validate)?;
+        self.magnetometer
+            .as_ref()
+            .map_or(Ok(()), Magnetometer::validate)
     }
 }
-
-#[derive(Clone, Default, Debug)]
-pub struct SpacecraftSensorArchitecture {}
-impl SpacecraftParam for SpacecraftSensorArchitecture {}
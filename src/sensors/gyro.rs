@@ -0,0 +1,141 @@
+use altai_rs::meta::types::Vector3;
+
+use crate::sensors::noise::NoiseSource;
+use crate::sensors::types::{SampleClock, SensorError};
+
+/// Three-axis rate gyro. Error terms follow the Allan variance
+/// parameterization of IEEE Std 952.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gyro {
+    pub sample_rate: f64,            // [Hz]
+    pub angle_random_walk: f64,      // N [rad/s^0.5]
+    pub rate_random_walk: f64,       // K [rad/s^1.5]
+    pub bias_instability: f64,       // Gauss-Markov steady-state sigma [rad/s]
+    pub bias_correlation_time: f64,  // [s]
+    pub initial_bias: [f64; 3],      // [rad/s]
+    pub scale_factor: [f64; 3],      // Scale factor error per axis [-]
+    pub misalignment: [[f64; 3]; 3], // Cross-axis coupling, off-diagonal only [rad]
+    pub resolution: f64,             // Output LSB; 0 for unquantized [rad/s]
+    pub max_rate: f64,               // Measurement range per axis [rad/s]
+    pub seed: u64,
+}
+impl Default for Gyro {
+    fn default() -> Self {
+        // Tactical-grade MEMS: 0.15 deg/rt-hr ARW, 3 deg/hr bias instability
+        Self {
+            sample_rate: 10.,
+            angle_random_walk: 4.4e-5,
+            rate_random_walk: 1e-7,
+            bias_instability: 1.5e-5,
+            bias_correlation_time: 300.,
+            initial_bias: [0., 0., 0.],
+            scale_factor: [0., 0., 0.],
+            misalignment: [[0.; 3]; 3],
+            resolution: 1e-6,
+            max_rate: 5.,
+            seed: 1,
+        }
+    }
+}
+impl Gyro {
+    pub fn validate(&self) -> Result<(), SensorError> {
+        for (field, value) in [
+            ("sample_rate", self.sample_rate),
+            ("max_rate", self.max_rate),
+            ("bias_correlation_time", self.bias_correlation_time),
+        ] {
+            SensorError::check_positive("gyro", field, value)?;
+        }
+        for (field, value) in [
+            ("angle_random_walk", self.angle_random_walk),
+            ("rate_random_walk", self.rate_random_walk),
+            ("bias_instability", self.bias_instability),
+            ("resolution", self.resolution),
+        ] {
+            SensorError::check_non_negative("gyro", field, value)?;
+        }
+        Ok(())
+    }
+
+    /// Rate seen by the sensing axes before bias and noise (Body) [rad/s].
+    pub fn sensed_rate(&self, omega: [f64; 3]) -> [f64; 3] {
+        [0, 1, 2].map(|i| {
+            (0..3)
+                .map(|j| {
+                    let gain = if i == j {
+                        1. + self.scale_factor[i]
+                    } else {
+                        self.misalignment[i][j]
+                    };
+                    gain * omega[j]
+                })
+                .sum()
+        })
+    }
+}
+
+/// Latest gyro sample, held between samples.
+#[derive(Debug, Clone)]
+pub struct GyroSignal {
+    pub rate: Vector3,     // Measured rate (Body) [rad/s]
+    pub bias: Vector3,     // Truth bias at the sample [rad/s]
+    pub saturated: bool,   // Any axis beyond range
+    pub valid: bool,       // A sample has been taken
+    pub sample_time: f64,  // [s since start]
+    bias_walk: [f64; 3],   // Rate random walk state [rad/s]
+    bias_markov: [f64; 3], // Bias instability state [rad/s]
+    clock: SampleClock,
+    noise: NoiseSource,
+}
+impl GyroSignal {
+    pub fn initialize(gyro: &Gyro) -> Self {
+        Self {
+            rate: Vector3::zeros((3, 1)),
+            bias: Vector3::from_shape_fn((3, 1), |(i, _)| gyro.initial_bias[i]),
+            saturated: false,
+            valid: false,
+            sample_time: 0.,
+            bias_walk: [0.; 3],
+            bias_markov: [0.; 3],
+            clock: SampleClock::initialize(gyro.sample_rate),
+            noise: NoiseSource::new(gyro.seed),
+        }
+    }
+
+    /// Take every sample due by `time` against the truth rate (Body) [rad/s].
+    pub fn update(&mut self, gyro: &Gyro, omega: &Vector3, time: f64) {
+        let omega = [omega[[0, 0]], omega[[1, 0]], omega[[2, 0]]];
+        for sample_time in self.clock.due(time) {
+            self.sample(gyro, omega, sample_time);
+        }
+    }
+
+    fn sample(&mut self, gyro: &Gyro, omega: [f64; 3], sample_time: f64) {
+        let dt = self.clock.period();
+        // Bias: random walk plus first-order Gauss-Markov
+        let phi = (-dt / gyro.bias_correlation_time).exp();
+        let markov_sigma = gyro.bias_instability * (1. - phi * phi).sqrt();
+        let walk_sigma = gyro.rate_random_walk * dt.sqrt();
+        // White rate noise averaged over the sample: N / sqrt(dt)
+        let white_sigma = gyro.angle_random_walk / dt.sqrt();
+
+        self.saturated = false;
+        for (i, sensed) in gyro.sensed_rate(omega).into_iter().enumerate() {
+            self.bias_walk[i] += walk_sigma * self.noise.gaussian();
+            self.bias_markov[i] = phi * self.bias_markov[i] + markov_sigma * self.noise.gaussian();
+            let bias = gyro.initial_bias[i] + self.bias_walk[i] + self.bias_markov[i];
+            let mut rate = sensed + bias + white_sigma * self.noise.gaussian();
+            if rate.abs() > gyro.max_rate {
+                rate = gyro.max_rate.copysign(rate);
+                self.saturated = true;
+            }
+            if gyro.resolution > 0. {
+                rate = (rate / gyro.resolution).round() * gyro.resolution;
+            }
+            self.bias[[i, 0]] = bias;
+            self.rate[[i, 0]] = rate;
+        }
+        self.valid = true;
+        self.sample_time = sample_time;
+    }
+}
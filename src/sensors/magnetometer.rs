@@ -0,0 +1,118 @@
+use altai_rs::meta::types::Vector3;
+
+use crate::sensors::noise::NoiseSource;
+use crate::sensors::types::{SampleClock, SensorError};
+
+/// Three-axis magnetometer measuring along the body axes:
+/// B_meas = (I + S)(B + C m) + b_hard + b + noise, with S the soft-iron
+/// distortion, C the magnetorquer coupling and m the net torquer dipole.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Magnetometer {
+    pub sample_rate: f64,            // [Hz]
+    pub noise: f64,                  // 1-sigma per axis [T]
+    pub bias: [f64; 3],              // Sensor offset [T]
+    pub hard_iron: [f64; 3],         // Spacecraft residual field at the sensor (Body) [T]
+    pub soft_iron: [[f64; 3]; 3],    // Deviation of the distortion matrix from identity [-]
+    pub interference: [[f64; 3]; 3], // Field at the sensor per unit torquer dipole [T/(A m2)]
+    pub max_field: f64,              // Measurement range per axis [T]
+    pub resolution: f64,             // Output LSB; 0 for unquantized [T]
+    pub seed: u64,
+}
+impl Default for Magnetometer {
+    fn default() -> Self {
+        // Small-satellite fluxgate/AMR class: 100 nT noise, +-100 uT range
+        Self {
+            sample_rate: 10.,
+            noise: 1e-7,
+            bias: [0., 0., 0.],
+            hard_iron: [0., 0., 0.],
+            soft_iron: [[0.; 3]; 3],
+            interference: [[0.; 3]; 3],
+            max_field: 1e-4,
+            resolution: 1e-8,
+            seed: 5,
+        }
+    }
+}
+impl Magnetometer {
+    pub fn validate(&self) -> Result<(), SensorError> {
+        for (field, value) in [
+            ("sample_rate", self.sample_rate),
+            ("max_field", self.max_field),
+        ] {
+            SensorError::check_positive("magnetometer", field, value)?;
+        }
+        for (field, value) in [("noise", self.noise), ("resolution", self.resolution)] {
+            SensorError::check_non_negative("magnetometer", field, value)?;
+        }
+        Ok(())
+    }
+
+    /// Field seen by the sensing axes before bias and noise (Body) [T].
+    pub fn sensed_field(&self, b_body: [f64; 3], m_body: [f64; 3]) -> [f64; 3] {
+        let local: [f64; 3] = [0, 1, 2].map(|i| {
+            b_body[i]
+                + (0..3)
+                    .map(|j| self.interference[i][j] * m_body[j])
+                    .sum::<f64>()
+        });
+        [0, 1, 2].map(|i| {
+            local[i]
+                + (0..3).map(|j| self.soft_iron[i][j] * local[j]).sum::<f64>()
+                + self.hard_iron[i]
+        })
+    }
+}
+
+/// Latest magnetometer sample, held between samples.
+#[derive(Debug, Clone)]
+pub struct MagnetometerSignal {
+    pub field: Vector3,   // Measured field (Body) [T]
+    pub saturated: bool,  // Any axis beyond range
+    pub valid: bool,      // A sample has been taken
+    pub sample_time: f64, // [s since start]
+    clock: SampleClock,
+    noise: NoiseSource,
+}
+impl MagnetometerSignal {
+    pub fn initialize(magnetometer: &Magnetometer) -> Self {
+        Self {
+            field: Vector3::zeros((3, 1)),
+            saturated: false,
+            valid: false,
+            sample_time: 0.,
+            clock: SampleClock::initialize(magnetometer.sample_rate),
+            noise: NoiseSource::new(magnetometer.seed),
+        }
+    }
+
+    /// Take every sample due by `time` against the truth field (Body) [T]
+    /// and the net magnetorquer dipole (Body) [A m2].
+    pub fn update(
+        &mut self,
+        magnetometer: &Magnetometer,
+        b_body: &Vector3,
+        m_body: &Vector3,
+        time: f64,
+    ) {
+        let b = [0, 1, 2].map(|i| b_body[[i, 0]]);
+        let m = [0, 1, 2].map(|i| m_body[[i, 0]]);
+        for sample_time in self.clock.due(time) {
+            self.saturated = false;
+            for (i, sensed) in magnetometer.sensed_field(b, m).into_iter().enumerate() {
+                let mut field =
+                    sensed + magnetometer.bias[i] + magnetometer.noise * self.noise.gaussian();
+                if field.abs() > magnetometer.max_field {
+                    field = magnetometer.max_field.copysign(field);
+                    self.saturated = true;
+                }
+                if magnetometer.resolution > 0. {
+                    field = (field / magnetometer.resolution).round() * magnetometer.resolution;
+                }
+                self.field[[i, 0]] = field;
+            }
+            self.valid = true;
+            self.sample_time = sample_time;
+        }
+    }
+}
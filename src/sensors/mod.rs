@@ -1 +1,6 @@
+pub mod gyro;
+pub mod magnetometer;
+pub mod noise;
+pub mod star_tracker;
+pub mod sun_sensors;
 pub mod types;
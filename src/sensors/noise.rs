@@ -0,0 +1,59 @@
+/// Seeded pseudo-random source for sensor noise: xoshiro256** (Blackman &
+/// Vigna) seeded through SplitMix64, with Box-Muller normals. A given seed
+/// always reproduces the same sequence.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoiseSource {
+    state: [u64; 4],
+    spare: Option<f64>,
+}
+impl NoiseSource {
+    pub fn new(seed: u64) -> Self {
+        let mut x = seed;
+        let mut splitmix = || {
+            x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        };
+        Self {
+            state: [splitmix(), splitmix(), splitmix(), splitmix()],
+            spare: None,
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// Uniform on [0, 1).
+    pub fn uniform(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Standard normal.
+    pub fn gaussian(&mut self) -> f64 {
+        if let Some(z) = self.spare.take() {
+            return z;
+        }
+        // 1 - u keeps the log argument in (0, 1]
+        let r = (-2. * (1. - self.uniform()).ln()).sqrt();
+        let theta = 2. * std::f64::consts::PI * self.uniform();
+        self.spare = Some(r * theta.sin());
+        r * theta.cos()
+    }
+
+    /// Independent standard normals per axis.
+    pub fn gaussian3(&mut self) -> [f64; 3] {
+        [self.gaussian(), self.gaussian(), self.gaussian()]
+    }
+}
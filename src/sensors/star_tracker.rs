@@ -0,0 +1,261 @@
+use std::collections::VecDeque;
+
+use altai_rs::meta::types::{Generic2D, Quaternion4};
+use ndarray::array;
+
+use crate::attitude::kinedynamics::{quat_multiply, quat_to_dcm};
+use crate::attitude::types::TruthAttitudeSignal;
+use crate::ephemeris::consts::RE;
+use crate::ephemeris::types::TruthEphemerisSignal;
+use crate::sensors::noise::NoiseSource;
+use crate::sensors::types::{sensor_to_body, validate_sensor_axes, SampleClock, SensorError};
+
+/// Star tracker reporting the body attitude. Errors are applied in the
+/// sensor frame: z along the boresight, x along the roll reference.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StarTracker {
+    pub boresight_body: [f64; 3],      // Unit boresight (Body) [-]
+    pub roll_reference_body: [f64; 3], // Sensor x axis, orthogonal to the boresight (Body) [-]
+    pub cross_boresight_noise: f64,    // 1-sigma per axis [rad]
+    pub roll_noise: f64,               // 1-sigma [rad]
+    pub spatial_error_cross: f64,      // Low-frequency spatial error, 1-sigma per axis [rad]
+    pub spatial_error_roll: f64,       // [rad]
+    pub spatial_correlation: f64,      // Boresight travel that decorrelates the spatial error [rad]
+    pub update_rate: f64,              // [Hz]
+    pub latency: f64,                  // Image time to output [s]
+    pub sun_exclusion: f64,            // Half-angle about the Sun [rad]
+    pub earth_exclusion: f64,          // Half-angle above the Earth limb [rad]
+    pub moon_exclusion: f64,           // Half-angle about the Moon [rad]
+    pub max_rate: f64,                 // Tracking limit; lock is lost above it [rad/s]
+    pub seed: u64,
+}
+impl Default for StarTracker {
+    fn default() -> Self {
+        // Small-satellite tracker: 5 arcsec cross-boresight, 30 arcsec roll
+        let arcsec = std::f64::consts::PI / 648000.;
+        let deg = std::f64::consts::PI / 180.;
+        Self {
+            boresight_body: [0., 0., 1.],
+            roll_reference_body: [1., 0., 0.],
+            cross_boresight_noise: 5. * arcsec,
+            roll_noise: 30. * arcsec,
+            spatial_error_cross: 2. * arcsec,
+            spatial_error_roll: 10. * arcsec,
+            spatial_correlation: 1. * deg,
+            update_rate: 5.,
+            latency: 0.1,
+            sun_exclusion: 30. * deg,
+            earth_exclusion: 20. * deg,
+            moon_exclusion: 10. * deg,
+            max_rate: 2. * deg,
+            seed: 2,
+        }
+    }
+}
+
+/// Star tracker output state.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum StarTrackerStatus {
+    #[default]
+    NoSample,
+    Tracking,
+    SunExcluded,
+    EarthExcluded,
+    MoonExcluded,
+    LostLock,
+}
+
+impl StarTracker {
+    pub fn validate(&self) -> Result<(), SensorError> {
+        validate_sensor_axes(
+            "star tracker",
+            self.boresight_body,
+            self.roll_reference_body,
+        )?;
+        for (field, value) in [
+            ("update_rate", self.update_rate),
+            ("max_rate", self.max_rate),
+            ("spatial_correlation", self.spatial_correlation),
+        ] {
+            SensorError::check_positive("star tracker", field, value)?;
+        }
+        for (field, value) in [
+            ("cross_boresight_noise", self.cross_boresight_noise),
+            ("roll_noise", self.roll_noise),
+            ("spatial_error_cross", self.spatial_error_cross),
+            ("spatial_error_roll", self.spatial_error_roll),
+            ("latency", self.latency),
+        ] {
+            SensorError::check_non_negative("star tracker", field, value)?;
+        }
+        Ok(())
+    }
+
+    /// Sensor to body rotation; columns are the sensor axes (Body) [-].
+    pub fn sensor_to_body(&self) -> Generic2D {
+        sensor_to_body(self.boresight_body, self.roll_reference_body)
+    }
+
+    /// First exclusion zone the boresight falls in, if any.
+    pub fn exclusion(
+        &self,
+        boresight_eci: &[f64; 3],
+        ephemeris: &TruthEphemerisSignal,
+    ) -> Option<StarTrackerStatus> {
+        let r_sc = ephemeris.r_sc_eci.column(0);
+        let angle_to = |target: [f64; 3]| {
+            let norm = target.iter().map(|v| v * v).sum::<f64>().sqrt();
+            let cos = (0..3).map(|k| boresight_eci[k] * target[k]).sum::<f64>() / norm;
+            (cos.clamp(-1., 1.).acos(), norm)
+        };
+
+        // The Sun only blinds the sensor while it is above the limb
+        let (sun_angle, _) = angle_to([0, 1, 2].map(|k| ephemeris.r_sun_eci[[k, 0]] - r_sc[k]));
+        if ephemeris.shadow_fraction > 0. && sun_angle < self.sun_exclusion {
+            return Some(StarTrackerStatus::SunExcluded);
+        }
+        let (nadir_angle, dist) = angle_to([0, 1, 2].map(|k| -r_sc[k]));
+        let limb_radius = (RE / dist).min(1.).asin();
+        if nadir_angle - limb_radius < self.earth_exclusion {
+            return Some(StarTrackerStatus::EarthExcluded);
+        }
+        let (moon_angle, _) = angle_to([0, 1, 2].map(|k| ephemeris.r_moon_eci[[k, 0]] - r_sc[k]));
+        if moon_angle < self.moon_exclusion {
+            return Some(StarTrackerStatus::MoonExcluded);
+        }
+        None
+    }
+}
+
+/// Latest star tracker output, held between updates.
+#[derive(Debug, Clone)]
+pub struct StarTrackerSignal {
+    pub q_sc_eci: Quaternion4, // Measured body attitude
+    pub valid: bool,
+    pub status: StarTrackerStatus,
+    pub image_time: f64,  // Attitude timestamp [s since start]
+    pub sample_time: f64, // Output time [s since start]
+    spatial: [f64; 3],    // Low-frequency error state (Sensor) [rad]
+    boresight_eci: Option<[f64; 3]>,
+    history: VecDeque<(f64, Quaternion4, f64)>, // Truth (time, attitude, rate magnitude)
+    clock: SampleClock,
+    noise: NoiseSource,
+}
+impl StarTrackerSignal {
+    pub fn initialize(tracker: &StarTracker, attitude: &TruthAttitudeSignal) -> Self {
+        let mut noise = NoiseSource::new(tracker.seed);
+        let draw = noise.gaussian3();
+        let spatial = [
+            tracker.spatial_error_cross * draw[0],
+            tracker.spatial_error_cross * draw[1],
+            tracker.spatial_error_roll * draw[2],
+        ];
+        let mut signal = Self {
+            q_sc_eci: array![[0.], [0.], [0.], [1.]],
+            valid: false,
+            status: StarTrackerStatus::NoSample,
+            image_time: 0.,
+            sample_time: 0.,
+            spatial,
+            boresight_eci: None,
+            history: VecDeque::new(),
+            clock: SampleClock::initialize(tracker.update_rate),
+            noise,
+        };
+        signal.record(attitude, 0., tracker.latency);
+        signal
+    }
+
+    fn record(&mut self, attitude: &TruthAttitudeSignal, time: f64, latency: f64) {
+        let rate = attitude.omega_sc.iter().map(|w| w * w).sum::<f64>().sqrt();
+        self.history
+            .push_back((time, attitude.q_sc_eci.clone(), rate));
+        // Keep one entry at or before the oldest image time still to come
+        while self.history.len() > 2 && self.history[1].0 <= time - latency {
+            self.history.pop_front();
+        }
+    }
+
+    /// Truth attitude and rate magnitude at `time`, interpolated over the history.
+    fn truth_at(&self, time: f64) -> (Quaternion4, f64) {
+        let after = self
+            .history
+            .iter()
+            .position(|(t, _, _)| *t >= time)
+            .unwrap_or(self.history.len() - 1);
+        let (t1, q1, w1) = &self.history[after];
+        if after == 0 || *t1 <= time {
+            return (q1.clone(), *w1);
+        }
+        let (t0, q0, w0) = &self.history[after - 1];
+        let frac = (time - t0) / (t1 - t0);
+        // Normalized linear interpolation on the shorter arc
+        let sign = if (q0 * q1).sum() < 0. { -1. } else { 1. };
+        let q = (1. - frac) * q0 + frac * sign * q1;
+        let norm = q.iter().map(|v| v * v).sum::<f64>().sqrt();
+        (q / norm, (1. - frac) * w0 + frac * w1)
+    }
+
+    /// Record the truth attitude at `time` and produce every output due by then.
+    pub fn update(
+        &mut self,
+        tracker: &StarTracker,
+        attitude: &TruthAttitudeSignal,
+        ephemeris: &TruthEphemerisSignal,
+        time: f64,
+    ) {
+        self.record(attitude, time, tracker.latency);
+        for sample_time in self.clock.due(time) {
+            self.sample(tracker, ephemeris, sample_time);
+        }
+    }
+
+    fn sample(&mut self, tracker: &StarTracker, ephemeris: &TruthEphemerisSignal, time: f64) {
+        let image_time = (time - tracker.latency).max(0.);
+        let (q_true, rate) = self.truth_at(image_time);
+        let sensor_to_body = tracker.sensor_to_body();
+        let boresight = quat_to_dcm(&q_true).t().dot(&sensor_to_body.column(2));
+        let boresight = [boresight[0], boresight[1], boresight[2]];
+
+        // Spatial error decorrelates as the field of view moves across the sky
+        if let Some(prev) = self.boresight_eci {
+            let cos = (0..3).map(|k| prev[k] * boresight[k]).sum::<f64>();
+            let phi = (-cos.clamp(-1., 1.).acos() / tracker.spatial_correlation).exp();
+            let draw = self.noise.gaussian3();
+            let scale = (1. - phi * phi).sqrt();
+            let sigma = [
+                tracker.spatial_error_cross,
+                tracker.spatial_error_cross,
+                tracker.spatial_error_roll,
+            ];
+            for k in 0..3 {
+                self.spatial[k] = phi * self.spatial[k] + scale * sigma[k] * draw[k];
+            }
+        }
+        self.boresight_eci = Some(boresight);
+
+        let draw = self.noise.gaussian3();
+        let error_sensor = array![
+            [tracker.cross_boresight_noise * draw[0] + self.spatial[0]],
+            [tracker.cross_boresight_noise * draw[1] + self.spatial[1]],
+            [tracker.roll_noise * draw[2] + self.spatial[2]]
+        ];
+        // q_meas = dq(e) * q_true with A(dq) = I - [e x] to first order
+        let e = sensor_to_body.dot(&error_sensor);
+        let dq = array![[e[[0, 0]] / 2.], [e[[1, 0]] / 2.], [e[[2, 0]] / 2.], [1.]];
+        let q_meas = quat_multiply(&dq, &q_true);
+        let norm = q_meas.iter().map(|v| v * v).sum::<f64>().sqrt();
+
+        self.status = if rate > tracker.max_rate {
+            StarTrackerStatus::LostLock
+        } else {
+            tracker
+                .exclusion(&boresight, ephemeris)
+                .unwrap_or(StarTrackerStatus::Tracking)
+        };
+        self.valid = self.status == StarTrackerStatus::Tracking;
+        self.q_sc_eci = q_meas / norm;
+        self.image_time = image_time;
+        self.sample_time = time;
+    }
+}
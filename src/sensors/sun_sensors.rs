@@ -0,0 +1,305 @@
+use altai_rs::meta::types::{Generic1D, Vector3};
+
+use crate::ephemeris::types::TruthEphemerisSignal;
+use crate::sensors::noise::NoiseSource;
+use crate::sensors::types::{sensor_to_body, validate_sensor_axes, SampleClock, SensorError};
+
+/// Cosine-law photodiode; output is normalized to 1 for full sunlight at
+/// 1 AU on the normal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoarseSunSensor {
+    pub normal_body: [f64; 3], // Unit normal (Body) [-]
+    pub half_fov: f64,         // [rad]
+    pub scale_factor: f64,     // Gain error [-]
+    pub bias: f64,             // [-]
+}
+impl Default for CoarseSunSensor {
+    fn default() -> Self {
+        Self {
+            normal_body: [0., 0., 1.],
+            half_fov: 80. * std::f64::consts::PI / 180.,
+            scale_factor: 0.,
+            bias: 0.,
+        }
+    }
+}
+impl CoarseSunSensor {
+    /// Noise-free output for a source of relative irradiance `irradiance`
+    /// along `direction` (Body) [-].
+    pub fn response(&self, direction: &Vector3, irradiance: f64) -> f64 {
+        let cos = (0..3)
+            .map(|k| self.normal_body[k] * direction[[k, 0]])
+            .sum::<f64>();
+        if cos < self.half_fov.cos() || cos <= 0. {
+            return 0.;
+        }
+        (1. + self.scale_factor) * irradiance * cos
+    }
+}
+
+/// Widest ADC the count model supports [bits].
+pub const MAX_ADC_BITS: u32 = 32;
+
+/// Coarse sun sensors sharing one sampled ADC.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoarseSunSensorArray {
+    pub sensors: Vec<CoarseSunSensor>,
+    pub noise: f64,       // 1-sigma per sensor [-]
+    pub full_scale: f64,  // ADC input range [-]
+    pub adc_bits: u32,    // At most `MAX_ADC_BITS`
+    pub sample_rate: f64, // [Hz]
+    pub seed: u64,
+}
+impl Default for CoarseSunSensorArray {
+    fn default() -> Self {
+        // One photodiode per body face
+        let faces = [
+            [1., 0., 0.],
+            [-1., 0., 0.],
+            [0., 1., 0.],
+            [0., -1., 0.],
+            [0., 0., 1.],
+            [0., 0., -1.],
+        ];
+        Self {
+            sensors: faces
+                .into_iter()
+                .map(|normal_body| CoarseSunSensor {
+                    normal_body,
+                    ..Default::default()
+                })
+                .collect(),
+            noise: 5e-3,
+            full_scale: 1.3,
+            adc_bits: 12,
+            sample_rate: 10.,
+            seed: 3,
+        }
+    }
+}
+impl CoarseSunSensorArray {
+    pub fn validate(&self) -> Result<(), SensorError> {
+        for sensor in &self.sensors {
+            let norm = sensor.normal_body.iter().map(|v| v * v).sum::<f64>().sqrt();
+            if (norm - 1.).abs() > 1e-6 {
+                return Err(SensorError::AxisNotUnit {
+                    device: "coarse sun sensor",
+                    norm,
+                });
+            }
+            SensorError::check_positive("coarse sun sensor", "half_fov", sensor.half_fov)?;
+        }
+        for (field, value) in [
+            ("full_scale", self.full_scale),
+            ("sample_rate", self.sample_rate),
+            ("adc_bits", self.adc_bits as f64),
+        ] {
+            SensorError::check_positive("coarse sun sensor", field, value)?;
+        }
+        SensorError::check_non_negative("coarse sun sensor", "noise", self.noise)?;
+        if self.adc_bits > MAX_ADC_BITS {
+            return Err(SensorError::AboveLimit {
+                device: "coarse sun sensor",
+                field: "adc_bits",
+                value: self.adc_bits as f64,
+                limit: MAX_ADC_BITS as f64,
+            });
+        }
+        Ok(())
+    }
+
+    /// ADC counts for an output, clipped to the input range.
+    pub fn quantize(&self, output: f64) -> f64 {
+        let max_count = ((1u64 << self.adc_bits) - 1) as f64;
+        (output / self.full_scale * max_count)
+            .round()
+            .clamp(0., max_count)
+    }
+}
+
+/// Latest coarse sun sensor sample, held between samples.
+#[derive(Debug, Clone)]
+pub struct CoarseSunSensorSignal {
+    pub counts: Generic1D, // ADC output per sensor [counts]
+    pub valid: bool,       // A sample has been taken
+    pub sample_time: f64,  // [s since start]
+    clock: SampleClock,
+    noise: NoiseSource,
+}
+impl CoarseSunSensorSignal {
+    pub fn initialize(array: &CoarseSunSensorArray) -> Self {
+        Self {
+            counts: Generic1D::zeros(array.sensors.len()),
+            valid: false,
+            sample_time: 0.,
+            clock: SampleClock::initialize(array.sample_rate),
+            noise: NoiseSource::new(array.seed),
+        }
+    }
+
+    /// Take every sample due by `time`.
+    pub fn update(
+        &mut self,
+        array: &CoarseSunSensorArray,
+        ephemeris: &TruthEphemerisSignal,
+        time: f64,
+    ) {
+        let direct = ephemeris.solar_irradiance();
+        let albedo = ephemeris.albedo_fraction;
+        for sample_time in self.clock.due(time) {
+            for (i, sensor) in array.sensors.iter().enumerate() {
+                // Earth albedo enters as a diffuse source along the nadir
+                let output = sensor.response(&ephemeris.sun_body, direct)
+                    + sensor.response(&ephemeris.nadir_body, albedo)
+                    + sensor.bias
+                    + array.noise * self.noise.gaussian();
+                self.counts[i] = array.quantize(output);
+            }
+            self.valid = true;
+            self.sample_time = sample_time;
+        }
+    }
+}
+
+/// Two-axis fine sun sensor. Angles are measured in the sensor frame (z
+/// along the boresight, x along the reference axis) as
+/// alpha = atan(s_x / s_z) and beta = atan(s_y / s_z).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FineSunSensor {
+    pub boresight_body: [f64; 3], // Unit boresight (Body) [-]
+    pub reference_body: [f64; 3], // Sensor x axis, orthogonal to the boresight (Body) [-]
+    pub half_fov: f64,            // Conical [rad]
+    pub noise: f64,               // 1-sigma per axis [rad]
+    pub bias: [f64; 2],           // Alpha, beta [rad]
+    pub resolution: f64,          // Output LSB; 0 for unquantized [rad]
+    pub threshold: f64,           // Minimum detected irradiance for a valid output [-]
+}
+impl Default for FineSunSensor {
+    fn default() -> Self {
+        let deg = std::f64::consts::PI / 180.;
+        Self {
+            boresight_body: [0., 0., 1.],
+            reference_body: [1., 0., 0.],
+            half_fov: 60. * deg,
+            noise: 0.05 * deg,
+            bias: [0., 0.],
+            resolution: 0.01 * deg,
+            threshold: 0.5,
+        }
+    }
+}
+impl FineSunSensor {
+    /// Noise-free alpha and beta [rad], or None below the detection threshold.
+    /// Albedo in the field of view pulls the spot toward the nadir.
+    pub fn angles(&self, ephemeris: &TruthEphemerisSignal) -> Option<[f64; 2]> {
+        let body_to_sensor =
+            sensor_to_body(self.boresight_body, self.reference_body).reversed_axes();
+        let sources = [
+            (&ephemeris.sun_body, ephemeris.solar_irradiance()),
+            (&ephemeris.nadir_body, ephemeris.albedo_fraction),
+        ];
+        // Spot centroid weighted by the irradiance on the detector
+        let (mut tan_x, mut tan_y, mut total) = (0., 0., 0.);
+        for (direction, irradiance) in sources {
+            let d = body_to_sensor.dot(direction);
+            let (x, y, z) = (d[[0, 0]], d[[1, 0]], d[[2, 0]]);
+            if z <= 0. || z < self.half_fov.cos() {
+                continue;
+            }
+            let weight = irradiance * z;
+            tan_x += weight * x / z;
+            tan_y += weight * y / z;
+            total += weight;
+        }
+        if total < self.threshold || total <= 0. {
+            return None;
+        }
+        Some([(tan_x / total).atan(), (tan_y / total).atan()])
+    }
+}
+
+/// Fine sun sensor heads sampled together.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FineSunSensorArray {
+    pub heads: Vec<FineSunSensor>,
+    pub sample_rate: f64, // [Hz]
+    pub seed: u64,
+}
+impl Default for FineSunSensorArray {
+    fn default() -> Self {
+        Self {
+            heads: vec![FineSunSensor::default()],
+            sample_rate: 10.,
+            seed: 4,
+        }
+    }
+}
+impl FineSunSensorArray {
+    pub fn validate(&self) -> Result<(), SensorError> {
+        SensorError::check_positive("fine sun sensor", "sample_rate", self.sample_rate)?;
+        for head in &self.heads {
+            validate_sensor_axes("fine sun sensor", head.boresight_body, head.reference_body)?;
+            SensorError::check_positive("fine sun sensor", "half_fov", head.half_fov)?;
+            for (field, value) in [
+                ("noise", head.noise),
+                ("resolution", head.resolution),
+                ("threshold", head.threshold),
+            ] {
+                SensorError::check_non_negative("fine sun sensor", field, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Latest fine sun sensor sample, held between samples.
+#[derive(Debug, Clone)]
+pub struct FineSunSensorSignal {
+    pub alpha: Generic1D, // Per head [rad]
+    pub beta: Generic1D,  // [rad]
+    pub valid: Vec<bool>, // Sun detected in the field of view
+    pub sample_time: f64, // [s since start]
+    clock: SampleClock,
+    noise: NoiseSource,
+}
+impl FineSunSensorSignal {
+    pub fn initialize(array: &FineSunSensorArray) -> Self {
+        Self {
+            alpha: Generic1D::zeros(array.heads.len()),
+            beta: Generic1D::zeros(array.heads.len()),
+            valid: vec![false; array.heads.len()],
+            sample_time: 0.,
+            clock: SampleClock::initialize(array.sample_rate),
+            noise: NoiseSource::new(array.seed),
+        }
+    }
+
+    /// Take every sample due by `time`.
+    pub fn update(
+        &mut self,
+        array: &FineSunSensorArray,
+        ephemeris: &TruthEphemerisSignal,
+        time: f64,
+    ) {
+        for sample_time in self.clock.due(time) {
+            for (i, head) in array.heads.iter().enumerate() {
+                let Some(angles) = head.angles(ephemeris) else {
+                    self.valid[i] = false;
+                    continue;
+                };
+                let [alpha, beta] = [0, 1].map(|k| {
+                    let angle = angles[k] + head.bias[k] + head.noise * self.noise.gaussian();
+                    if head.resolution > 0. {
+                        (angle / head.resolution).round() * head.resolution
+                    } else {
+                        angle
+                    }
+                });
+                self.alpha[i] = alpha;
+                self.beta[i] = beta;
+                self.valid[i] = true;
+            }
+            self.sample_time = sample_time;
+        }
+    }
+}
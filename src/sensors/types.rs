@@ -1,24 +1,290 @@
+use std::fmt;
+
+use altai_rs::meta::types::Generic2D;
+
 use crate::actuators::types::TruthActuatorBus;
-use crate::attitude::types::{TruthAttitudeBus, TruthMultibodyBus};
+use crate::attitude::types::{TruthAttitudeBus, TruthAttitudeSignal};
 use crate::ephemeris::types::TruthEphemerisBus;
-use polaris_fsw::actuators::types::ActuatorBus;
+use crate::sc_types::SpacecraftSensorArchitecture;
+use crate::sensors::gyro::GyroSignal;
+use crate::sensors::magnetometer::MagnetometerSignal;
+use crate::sensors::star_tracker::StarTrackerSignal;
+use crate::sensors::sun_sensors::{CoarseSunSensorSignal, FineSunSensorSignal};
 use polaris_fsw::sensors::types::RawSensorBus;
 
-#[derive(Clone, Debug, Default)]
-pub struct TruthSensorBus {}
+#[derive(Debug, Clone, PartialEq)]
+pub enum SensorError {
+    AxisNotUnit {
+        device: &'static str,
+        norm: f64,
+    },
+    AxesNotOrthogonal {
+        device: &'static str,
+        cos: f64,
+    },
+    NonPositive {
+        device: &'static str,
+        field: &'static str,
+        value: f64,
+    },
+    Negative {
+        device: &'static str,
+        field: &'static str,
+        value: f64,
+    },
+    AboveLimit {
+        device: &'static str,
+        field: &'static str,
+        value: f64,
+        limit: f64,
+    },
+}
+impl fmt::Display for SensorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AxisNotUnit { device, norm } => {
+                write!(f, "{device}: axis norm {norm} is not unit")
+            }
+            Self::AxesNotOrthogonal { device, cos } => {
+                write!(f, "{device}: axes are not orthogonal (cos = {cos})")
+            }
+            Self::NonPositive {
+                device,
+                field,
+                value,
+            } => write!(f, "{device}: `{field}` = {value} must be positive"),
+            Self::Negative {
+                device,
+                field,
+                value,
+            } => write!(f, "{device}: `{field}` = {value} must not be negative"),
+            Self::AboveLimit {
+                device,
+                field,
+                value,
+                limit,
+            } => write!(f, "{device}: `{field}` = {value} must not exceed {limit}"),
+        }
+    }
+}
+impl std::error::Error for SensorError {}
+
+impl SensorError {
+    /// Rejects zero, negative and NaN values.
+    pub(crate) fn check_positive(
+        device: &'static str,
+        field: &'static str,
+        value: f64,
+    ) -> Result<(), Self> {
+        if value.is_nan() || value <= 0. {
+            return Err(Self::NonPositive {
+                device,
+                field,
+                value,
+            });
+        }
+        Ok(())
+    }
+
+    /// Rejects negative and NaN values.
+    pub(crate) fn check_non_negative(
+        device: &'static str,
+        field: &'static str,
+        value: f64,
+    ) -> Result<(), Self> {
+        if value.is_nan() || value < 0. {
+            return Err(Self::Negative {
+                device,
+                field,
+                value,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Check a boresight (sensor z) and reference (sensor x) pair is unit and orthogonal.
+pub fn validate_sensor_axes(
+    device: &'static str,
+    boresight: [f64; 3],
+    reference: [f64; 3],
+) -> Result<(), SensorError> {
+    for axis in [boresight, reference] {
+        let norm = axis.iter().map(|v| v * v).sum::<f64>().sqrt();
+        if (norm - 1.).abs() > 1e-6 {
+            return Err(SensorError::AxisNotUnit { device, norm });
+        }
+    }
+    let cos = (0..3).map(|k| boresight[k] * reference[k]).sum::<f64>();
+    if cos.abs() > 1e-6 {
+        return Err(SensorError::AxesNotOrthogonal { device, cos });
+    }
+    Ok(())
+}
+
+/// Sensor to body rotation; columns are the sensor axes (Body) [-].
+pub fn sensor_to_body(boresight: [f64; 3], reference: [f64; 3]) -> Generic2D {
+    let (z, x) = (boresight, reference);
+    let y = [
+        z[1] * x[2] - z[2] * x[1],
+        z[2] * x[0] - z[0] * x[2],
+        z[0] * x[1] - z[1] * x[0],
+    ];
+    Generic2D::from_shape_fn((3, 3), |(r, c)| [x, y, z][c][r])
+}
+
+/// Fixed-rate sample schedule on the simulation clock; the first sample
+/// falls one period after the start.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleClock {
+    period: f64, // [s]
+    count: u64,  // Samples taken
+}
+impl SampleClock {
+    pub fn initialize(rate: f64) -> Self {
+        Self {
+            period: 1. / rate,
+            count: 0,
+        }
+    }
+
+    pub fn period(&self) -> f64 {
+        self.period
+    }
+
+    /// Sample times [s since start] that fall due by `time`.
+    pub fn due(&mut self, time: f64) -> Vec<f64> {
+        let mut times = Vec::new();
+        // Tolerate round-off where the sample and plant clocks coincide
+        while (self.count + 1) as f64 * self.period <= time + 1e-9 * self.period {
+            self.count += 1;
+            times.push(self.count as f64 * self.period);
+        }
+        times
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TruthSensorBus {
+    pub gyro: Option<GyroSignal>,
+    pub star_tracker: Option<StarTrackerSignal>,
+    pub coarse_sun_sensors: Option<CoarseSunSensorSignal>,
+    pub fine_sun_sensors: Option<FineSunSensorSignal>,
+    pub magnetometer: Option<MagnetometerSignal>,
+    sensors: SpacecraftSensorArchitecture,
+    time: f64, // [s since start]
+    ts: f64,
+}
+impl Default for TruthSensorBus {
+    fn default() -> Self {
+        Self {
+            gyro: None,
+            star_tracker: None,
+            coarse_sun_sensors: None,
+            fine_sun_sensors: None,
+            magnetometer: None,
+            sensors: SpacecraftSensorArchitecture::default(),
+            time: 0.,
+            ts: 0.1,
+        }
+    }
+}
 impl TruthSensorBus {
+    pub fn initialize(
+        sc_ts: f64,
+        sensor_params: SpacecraftSensorArchitecture,
+        attitude: &TruthAttitudeSignal,
+    ) -> Self {
+        Self {
+            gyro: sensor_params.gyro.as_ref().map(GyroSignal::initialize),
+            star_tracker: sensor_params
+                .star_tracker
+                .as_ref()
+                .map(|st| StarTrackerSignal::initialize(st, attitude)),
+            coarse_sun_sensors: sensor_params
+                .coarse_sun_sensors
+                .as_ref()
+                .map(CoarseSunSensorSignal::initialize),
+            fine_sun_sensors: sensor_params
+                .fine_sun_sensors
+                .as_ref()
+                .map(FineSunSensorSignal::initialize),
+            magnetometer: sensor_params
+                .magnetometer
+                .as_ref()
+                .map(MagnetometerSignal::initialize),
+            sensors: sensor_params,
+            time: 0.,
+            ts: sc_ts,
+        }
+    }
+
     pub fn process(
-        actuator_cmd: &ActuatorBus,
         actuator_dynamics: &TruthActuatorBus,
         ephemeris_bus: &TruthEphemerisBus,
         attitude_bus: &TruthAttitudeBus,
-        multibody_bud: &TruthMultibodyBus,
         prev_sensor: &Self,
     ) -> Self {
-        Self {}
+        // Sensor states carry over; samples are taken at the end of the step
+        let mut bus = prev_sensor.clone();
+        bus.time += bus.ts;
+
+        if let (Some(gyro), Some(signal)) = (&bus.sensors.gyro, &mut bus.gyro) {
+            signal.update(gyro, &attitude_bus.signal.omega_sc, bus.time);
+        }
+        if let (Some(tracker), Some(signal)) = (&bus.sensors.star_tracker, &mut bus.star_tracker) {
+            signal.update(
+                tracker,
+                &attitude_bus.signal,
+                &ephemeris_bus.signal,
+                bus.time,
+            );
+        }
+        if let (Some(array), Some(signal)) =
+            (&bus.sensors.coarse_sun_sensors, &mut bus.coarse_sun_sensors)
+        {
+            signal.update(array, &ephemeris_bus.signal, bus.time);
+        }
+        if let (Some(array), Some(signal)) =
+            (&bus.sensors.fine_sun_sensors, &mut bus.fine_sun_sensors)
+        {
+            signal.update(array, &ephemeris_bus.signal, bus.time);
+        }
+        if let (Some(magnetometer), Some(signal)) =
+            (&bus.sensors.magnetometer, &mut bus.magnetometer)
+        {
+            signal.update(
+                magnetometer,
+                &ephemeris_bus.signal.b_body,
+                &actuator_dynamics.magnetorquers.m_body,
+                bus.time,
+            );
+        }
+        bus
     }
 
     pub fn to_raw_bus(&self) -> RawSensorBus {
-        RawSensorBus::default()
+        let gyro = self.gyro.as_ref();
+        let tracker = self.star_tracker.as_ref();
+        let css = self.coarse_sun_sensors.as_ref();
+        let fss = self.fine_sun_sensors.as_ref();
+        let mag = self.magnetometer.as_ref();
+        RawSensorBus {
+            gyro_rate: gyro.map_or_else(Default::default, |g| g.rate.column(0).to_owned()),
+            gyro_valid: gyro.is_some_and(|g| g.valid),
+            star_tracker_q: tracker
+                .map_or_else(Default::default, |s| s.q_sc_eci.column(0).to_owned()),
+            star_tracker_valid: tracker.is_some_and(|s| s.valid),
+            star_tracker_time: tracker.map_or(0., |s| s.image_time),
+            // Flight software sees the status as its discriminant
+            star_tracker_status: tracker.map_or(0, |s| s.status as u8),
+            css_counts: css.map_or_else(Default::default, |c| c.counts.clone()),
+            css_valid: css.is_some_and(|c| c.valid),
+            fss_alpha: fss.map_or_else(Default::default, |f| f.alpha.clone()),
+            fss_beta: fss.map_or_else(Default::default, |f| f.beta.clone()),
+            fss_valid: fss.map_or_else(Vec::new, |f| f.valid.clone()),
+            mag_field: mag.map_or_else(Default::default, |m| m.field.column(0).to_owned()),
+            mag_valid: mag.is_some_and(|m| m.valid),
+        }
     }
 }
@@ -0,0 +1,43 @@
+// Julian date of J2000.0 (2000-01-01 12:00:00 TT)
+pub const JD_J2000: f64 = 2451545.0;
+// Offset between Julian and modified Julian dates
+pub const MJD_OFFSET: f64 = 2400000.5;
+pub const SEC_PER_DAY: f64 = 86400.;
+
+// TT - TAI [s]
+pub const TT_TAI: f64 = 32.184;
+// TAI - GPS [s]
+pub const TAI_GPS: f64 = 19.;
+
+// TAI - UTC from 1972 on, keyed by the UTC date it takes effect (year, month)
+// IERS Bulletin C; update when a new leap second is announced
+pub const LEAP_SECONDS: [(i32, u32, f64); 28] = [
+    (1972, 1, 10.),
+    (1972, 7, 11.),
+    (1973, 1, 12.),
+    (1974, 1, 13.),
+    (1975, 1, 14.),
+    (1976, 1, 15.),
+    (1977, 1, 16.),
+    (1978, 1, 17.),
+    (1979, 1, 18.),
+    (1980, 1, 19.),
+    (1981, 7, 20.),
+    (1982, 7, 21.),
+    (1983, 7, 22.),
+    (1985, 7, 23.),
+    (1988, 1, 24.),
+    (1990, 1, 25.),
+    (1991, 1, 26.),
+    (1992, 7, 27.),
+    (1993, 7, 28.),
+    (1994, 7, 29.),
+    (1996, 1, 30.),
+    (1997, 7, 31.),
+    (1999, 1, 32.),
+    (2006, 1, 33.),
+    (2009, 1, 34.),
+    (2012, 7, 35.),
+    (2015, 7, 36.),
+    (2017, 1, 37.),
+];
@@ -0,0 +1,194 @@
+use std::fmt;
+
+use crate::time::consts::{JD_J2000, LEAP_SECONDS, MJD_OFFSET, SEC_PER_DAY, TAI_GPS, TT_TAI};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeScale {
+    UTC,
+    TAI,
+    TT,
+    GPS,
+    UT1,
+}
+
+pub fn gregorian_to_jd(year: i32, month: u32, day: u32, day_fraction: f64) -> f64 {
+    // Meeus, Astronomical Algorithms Ch. 7; Gregorian calendar
+    let (y, m) = if month <= 2 {
+        (year - 1, month + 12)
+    } else {
+        (year, month)
+    };
+    let a = (y as f64 / 100.).floor();
+    let b = 2. - a + (a / 4.).floor();
+    (365.25 * (y as f64 + 4716.)).floor() + (30.6001 * (m as f64 + 1.)).floor() + day as f64
+        - 1524.5
+        + b
+        + day_fraction
+}
+
+pub fn jd_to_gregorian(jd: f64) -> (i32, u32, u32, f64) {
+    // Meeus, Astronomical Algorithms Ch. 7; returns (year, month, day, day fraction)
+    let jd = jd + 0.5;
+    let z = jd.floor();
+    let f = jd - z;
+    let alpha = ((z - 1867216.25) / 36524.25).floor();
+    let a = z + 1. + alpha - (alpha / 4.).floor();
+    let b = a + 1524.;
+    let c = ((b - 122.1) / 365.25).floor();
+    let d = (365.25 * c).floor();
+    let e = ((b - d) / 30.6001).floor();
+
+    let day = (b - d - (30.6001 * e).floor()) as u32;
+    let month = if e < 14. { e - 1. } else { e - 13. } as u32;
+    let year = if month > 2 { c - 4716. } else { c - 4715. } as i32;
+    (year, month, day, f)
+}
+
+fn leap_epoch_tai(year: i32, month: u32, tai_utc: f64) -> f64 {
+    // TAI seconds past J2000 at which a leap second table entry takes effect
+    (gregorian_to_jd(year, month, 1, 0.) - JD_J2000) * SEC_PER_DAY + tai_utc
+}
+
+/// TAI - UTC [s] at a TAI instant. Before 1972 the 1972 offset is held.
+pub fn tai_utc_at_tai(tai: f64) -> f64 {
+    LEAP_SECONDS
+        .iter()
+        .rev()
+        .find(|&&(year, month, dat)| tai >= leap_epoch_tai(year, month, dat))
+        .map_or(LEAP_SECONDS[0].2, |entry| entry.2)
+}
+
+/// TAI - UTC [s] at a UTC instant.
+pub fn tai_utc_at_utc(utc: f64) -> f64 {
+    LEAP_SECONDS
+        .iter()
+        .rev()
+        .find(|&&(year, month, _)| utc >= leap_epoch_tai(year, month, 0.))
+        .map_or(LEAP_SECONDS[0].2, |entry| entry.2)
+}
+
+/// Absolute epoch, stored as TT seconds past J2000.0.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Epoch {
+    tt: f64,
+    // UT1 - UTC [s]
+    pub dut1: f64,
+}
+impl Default for Epoch {
+    fn default() -> Self {
+        Self { tt: 0., dut1: 0. }
+    }
+}
+impl fmt::Debug for Epoch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (y, mo, d, h, mi, s) = self.to_gregorian(TimeScale::UTC);
+        write!(
+            f,
+            "Epoch({y:04}-{mo:02}-{d:02}T{h:02}:{mi:02}:{s:06.3} UTC)"
+        )
+    }
+}
+
+impl Epoch {
+    /// Seconds past J2000.0 counted in `scale`.
+    pub fn from_j2000_seconds(scale: TimeScale, seconds: f64) -> Self {
+        let tai = match scale {
+            TimeScale::TT => seconds - TT_TAI,
+            TimeScale::TAI => seconds,
+            TimeScale::GPS => seconds + TAI_GPS,
+            TimeScale::UTC => seconds + tai_utc_at_utc(seconds),
+            // UT1 taken as UTC until a DUT1 is supplied
+            TimeScale::UT1 => seconds + tai_utc_at_utc(seconds),
+        };
+        Self {
+            tt: tai + TT_TAI,
+            dut1: 0.,
+        }
+    }
+
+    pub fn from_jd(scale: TimeScale, jd: f64) -> Self {
+        Self::from_j2000_seconds(scale, (jd - JD_J2000) * SEC_PER_DAY)
+    }
+
+    pub fn from_mjd(scale: TimeScale, mjd: f64) -> Self {
+        Self::from_jd(scale, mjd + MJD_OFFSET)
+    }
+
+    pub fn from_gregorian(
+        scale: TimeScale,
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        minute: u32,
+        second: f64,
+    ) -> Self {
+        // A UTC leap second, 23:59:60, runs on from 23:59:59
+        if scale == TimeScale::UTC && second >= 60. {
+            return Self::from_gregorian(scale, year, month, day, hour, minute, 59.)
+                .advance(second - 59.);
+        }
+
+        // Whole days and time of day kept apart to preserve precision
+        let days = gregorian_to_jd(year, month, day, 0.) - JD_J2000;
+        let tod = hour as f64 * 3600. + minute as f64 * 60. + second;
+        Self::from_j2000_seconds(scale, days * SEC_PER_DAY + tod)
+    }
+
+    pub fn with_dut1(mut self, dut1: f64) -> Self {
+        self.dut1 = dut1;
+        self
+    }
+
+    pub fn seconds_since_j2000(&self, scale: TimeScale) -> f64 {
+        let tai = self.tt - TT_TAI;
+        match scale {
+            TimeScale::TT => self.tt,
+            TimeScale::TAI => tai,
+            TimeScale::GPS => tai - TAI_GPS,
+            TimeScale::UTC => tai - tai_utc_at_tai(tai),
+            TimeScale::UT1 => tai - tai_utc_at_tai(tai) + self.dut1,
+        }
+    }
+
+    pub fn jd(&self, scale: TimeScale) -> f64 {
+        JD_J2000 + self.seconds_since_j2000(scale) / SEC_PER_DAY
+    }
+
+    pub fn mjd(&self, scale: TimeScale) -> f64 {
+        self.jd(scale) - MJD_OFFSET
+    }
+
+    /// Julian centuries past J2000.0 in `scale`.
+    pub fn centuries_since_j2000(&self, scale: TimeScale) -> f64 {
+        self.seconds_since_j2000(scale) / (36525. * SEC_PER_DAY)
+    }
+
+    /// Calendar date and time of day in `scale`.
+    pub fn to_gregorian(&self, scale: TimeScale) -> (i32, u32, u32, u32, u32, f64) {
+        // Within a leap second UTC reads 23:59:60
+        let tai = self.tt - TT_TAI;
+        if scale == TimeScale::UTC && tai_utc_at_tai(tai + 1.) > tai_utc_at_tai(tai) {
+            let (year, month, day, hour, minute, second) = self.advance(-1.).to_gregorian(scale);
+            return (year, month, day, hour, minute, second + 1.);
+        }
+
+        let seconds = self.seconds_since_j2000(scale);
+        // J2000.0 is at noon; shift to a midnight day boundary
+        let days = ((seconds + 0.5 * SEC_PER_DAY) / SEC_PER_DAY).floor();
+        let tod = seconds + 0.5 * SEC_PER_DAY - days * SEC_PER_DAY;
+        let (year, month, day, _) = jd_to_gregorian(JD_J2000 - 0.5 + days);
+        let hour = (tod / 3600.).floor();
+        let minute = ((tod - 3600. * hour) / 60.).floor();
+        let second = tod - 3600. * hour - 60. * minute;
+        (year, month, day, hour as u32, minute as u32, second)
+    }
+
+    /// Epoch `dt` seconds later; uniform time, so leap seconds are respected.
+    pub fn advance(&self, dt: f64) -> Self {
+        Self {
+            tt: self.tt + dt,
+            dut1: self.dut1,
+        }
+    }
+}
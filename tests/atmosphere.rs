@@ -0,0 +1,230 @@
+use ndarray::{array, Array1};
+use polaris_plant::ephemeris::atmosphere::{
+    AtmosphereModel, BatesAtmosphere, ExponentialAtmosphere, HarrisPriesterAtmosphere, SpaceWeather,
+};
+use polaris_plant::ephemeris::celestial::sun_position_eci;
+use polaris_plant::ephemeris::consts::RE;
+use polaris_plant::ephemeris::types::{DragArea, DragModel, DragPlate, ForceModelError};
+use polaris_plant::sc_types::{
+    ConfigurationError, SpacecraftEphemerisArchitecture, SpacecraftParamBus,
+};
+use polaris_plant::Spacecraft;
+
+fn at_altitude(h_km: f64, direction: [f64; 3]) -> Array1<f64> {
+    let norm = (direction[0].powi(2) + direction[1].powi(2) + direction[2].powi(2)).sqrt();
+    let r = RE + h_km * 1e3;
+    array![
+        r * direction[0] / norm,
+        r * direction[1] / norm,
+        r * direction[2] / norm
+    ]
+}
+
+#[test]
+fn exponential_matches_vallado_table() {
+    let weather = SpaceWeather::default();
+    // Vallado Table 8-4 nominal densities at the base altitudes
+    for (h_km, rho) in [
+        (0., 1.225),
+        (100., 5.297e-7),
+        (200., 2.789e-10),
+        (400., 3.725e-12),
+        (500., 6.967e-13),
+        (800., 1.170e-14),
+    ] {
+        let r = at_altitude(h_km, [0.3, -0.5, 0.8]);
+        let d = ExponentialAtmosphere.density(0., &r.view(), &weather);
+        assert!((d / rho - 1.).abs() < 1e-9, "{h_km} km: {d}");
+    }
+
+    // Within the 400-450 km band, scale height 58.515 km
+    let r = at_altitude(440., [1., 0., 0.]);
+    let d = ExponentialAtmosphere.density(0., &r.view(), &weather);
+    assert!((d / (3.725e-12 * (-40f64 / 58.515).exp()) - 1.).abs() < 1e-9);
+}
+
+#[test]
+fn harris_priester_bounds_match_montenbruck_gill_table() {
+    let weather = SpaceWeather::default();
+    let model = HarrisPriesterAtmosphere::default();
+    let time = 2.1e8;
+
+    // Apex of the diurnal bulge, 30 deg east of the Sun
+    let r_sun = sun_position_eci(time);
+    let ra = r_sun[1].atan2(r_sun[0]) + 30f64.to_radians();
+    let dec = (r_sun[2] / r_sun.dot(&r_sun).sqrt()).asin();
+    let apex = [dec.cos() * ra.cos(), dec.cos() * ra.sin(), dec.sin()];
+    let antapex = [-apex[0], -apex[1], -apex[2]];
+
+    // Montenbruck & Gill Table 3.8 [g/km3]
+    for (h_km, rho_min, rho_max) in [
+        (200., 255.7, 316.2),
+        (400., 2.249, 7.492),
+        (800., 0.007069, 0.08059),
+    ] {
+        let d_max = model.density(time, &at_altitude(h_km, apex).view(), &weather);
+        let d_min = model.density(time, &at_altitude(h_km, antapex).view(), &weather);
+        assert!(
+            (d_max / (rho_max * 1e-12) - 1.).abs() < 1e-9,
+            "{h_km} km: {d_max}"
+        );
+        assert!(
+            (d_min / (rho_min * 1e-12) - 1.).abs() < 1e-9,
+            "{h_km} km: {d_min}"
+        );
+    }
+
+    // Outside the table
+    let r = at_altitude(1100., apex);
+    assert_eq!(model.density(time, &r.view(), &weather), 0.);
+}
+
+#[test]
+fn bates_exospheric_temperature_follows_j71() {
+    let model = BatesAtmosphere;
+    // Near the March 2000 equinox the Sun is on the equator
+    let time = 6.81e6;
+    let r_sun = sun_position_eci(time);
+    let ra_sun = r_sun[1].atan2(r_sun[0]);
+    let quiet = SpaceWeather {
+        f107: 150.,
+        f107_avg: 150.,
+        kp: 0.,
+    };
+    // Nighttime minimum global temperature 379 + 3.24 F107avg
+    let t_c = 379. + 3.24 * 150.;
+
+    // Around the equator the diurnal maximum is 1.3 T_c
+    let t_max = (0..720)
+        .map(|k| {
+            let ra = ra_sun + (k as f64 * 0.5).to_radians();
+            let r = at_altitude(400., [ra.cos(), ra.sin(), 0.]);
+            model.exospheric_temperature(time, &r.view(), &quiet) - 0.03
+        })
+        .fold(f64::MIN, f64::max);
+    assert!((t_max / (1.3 * t_c) - 1.).abs() < 1e-3, "{t_max}");
+
+    // Geomagnetic heating 28 Kp + 0.03 exp(Kp)
+    let r = at_altitude(400., [1., 0., 0.]);
+    let storm = SpaceWeather { kp: 6., ..quiet };
+    let dt = model.exospheric_temperature(time, &r.view(), &storm)
+        - model.exospheric_temperature(time, &r.view(), &quiet);
+    assert!((dt - (28. * 6. + 0.03 * (6f64.exp() - 1.))).abs() < 1e-9);
+}
+
+#[test]
+fn bates_density_trends() {
+    let model = BatesAtmosphere;
+    let weather = SpaceWeather::default();
+    let time = 2.1e8;
+
+    // Continuous with the exponential table at the 120 km boundary
+    let below = model.density(time, &at_altitude(119.999, [1., 0., 0.]).view(), &weather);
+    let above = model.density(time, &at_altitude(120., [1., 0., 0.]).view(), &weather);
+    assert!((above / below - 1.).abs() < 0.05, "{below} {above}");
+
+    // Decreasing with altitude
+    let mut prev = above;
+    for h_km in [200., 300., 400., 600., 800., 1000.] {
+        let d = model.density(time, &at_altitude(h_km, [1., 0., 0.]).view(), &weather);
+        assert!(d > 0. && d < prev, "{h_km} km: {d}");
+        prev = d;
+    }
+
+    // Solar and geomagnetic activity heat and expand the thermosphere
+    let r = at_altitude(400., [1., 0., 0.]);
+    let low = SpaceWeather {
+        f107: 70.,
+        f107_avg: 70.,
+        kp: 1.,
+    };
+    let high = SpaceWeather {
+        f107: 250.,
+        f107_avg: 250.,
+        kp: 1.,
+    };
+    let storm = SpaceWeather {
+        kp: 7.,
+        ..low.clone()
+    };
+    let d_low = model.density(time, &r.view(), &low);
+    assert!(model.density(time, &r.view(), &high) > 3. * d_low);
+    assert!(model.density(time, &r.view(), &storm) > d_low);
+}
+
+#[test]
+fn rejects_invalid_drag_and_space_weather() {
+    let with_drag = |drag: DragModel, space_weather: SpaceWeather| SpacecraftParamBus {
+        sc_ephemeris: SpacecraftEphemerisArchitecture {
+            drag: Some(drag),
+            space_weather,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let plate = |normal_body: [f64; 3], area: f64| DragPlate { normal_body, area };
+    let cases = [
+        DragModel {
+            cd: -2.2,
+            ..Default::default()
+        },
+        DragModel {
+            cd: f64::NAN,
+            ..Default::default()
+        },
+        DragModel {
+            area: DragArea::Reference(f64::NAN),
+            ..Default::default()
+        },
+        DragModel {
+            area: DragArea::Plates(vec![plate([1., 0., 0.], 1.), plate([0., 1., 0.], 0.)]),
+            ..Default::default()
+        },
+        DragModel {
+            area: DragArea::Plates(vec![plate([1., 1., 0.], 1.)]),
+            ..Default::default()
+        },
+    ];
+    for drag in cases {
+        assert!(
+            matches!(
+                Spacecraft::initialize(1., with_drag(drag.clone(), SpaceWeather::default())),
+                Err(ConfigurationError::ForceModel(_))
+            ),
+            "{drag:?}"
+        );
+    }
+
+    for weather in [
+        SpaceWeather {
+            f107: 0.,
+            ..Default::default()
+        },
+        SpaceWeather {
+            f107_avg: f64::NAN,
+            ..Default::default()
+        },
+        SpaceWeather {
+            kp: 9.5,
+            ..Default::default()
+        },
+        SpaceWeather {
+            kp: -1.,
+            ..Default::default()
+        },
+    ] {
+        assert!(matches!(
+            Spacecraft::initialize(1., with_drag(DragModel::default(), weather)),
+            Err(ConfigurationError::ForceModel(
+                ForceModelError::NonPositive { .. } | ForceModelError::OutOfRange { .. }
+            ))
+        ));
+    }
+
+    // A valid plate model passes
+    let drag = DragModel {
+        area: DragArea::Plates(vec![plate([1., 0., 0.], 1.), plate([-1., 0., 0.], 1.)]),
+        ..Default::default()
+    };
+    assert!(Spacecraft::initialize(1., with_drag(drag, SpaceWeather::default())).is_ok());
+}
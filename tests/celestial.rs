@@ -0,0 +1,94 @@
+use ndarray::{array, Array1};
+use polaris_plant::ephemeris::celestial::{
+    moon_position_eci, sun_position_eci, third_body_acceleration, MU_MOON, MU_SUN,
+};
+use polaris_plant::ephemeris::consts::RE;
+use polaris_plant::frames::rotations::{gcrf_to_mod, SEC_PER_CENTURY};
+use polaris_plant::time::types::{Epoch, TimeScale};
+
+fn norm(v: &Array1<f64>) -> f64 {
+    v.dot(v).sqrt()
+}
+
+fn angle(a: &Array1<f64>, b: &Array1<f64>) -> f64 {
+    (a.dot(b) / (norm(a) * norm(b))).clamp(-1., 1.).acos()
+}
+
+fn tt_seconds(scale: TimeScale, date: (i32, u32, u32, u32, u32, f64)) -> f64 {
+    let (year, month, day, hour, minute, second) = date;
+    Epoch::from_gregorian(scale, year, month, day, hour, minute, second)
+        .seconds_since_j2000(TimeScale::TT)
+}
+
+#[test]
+fn sun_matches_vallado_example_5_1() {
+    // 2 April 2006 00:00 UTC; mean equator and equinox of date [km]
+    let t = tt_seconds(TimeScale::UTC, (2006, 4, 2, 0, 0, 0.));
+    let expected = array![146186178., 28788976., 12481063.] * 1e3;
+
+    let r_j2000 = sun_position_eci(t);
+    let r_mod = gcrf_to_mod(t / SEC_PER_CENTURY).dot(&r_j2000);
+    assert!(angle(&r_mod, &expected) < 0.01f64.to_radians());
+    assert!((norm(&r_mod) / norm(&expected) - 1.).abs() < 1e-4);
+
+    // Six years of precession separate the two frames
+    assert!(angle(&r_j2000, &expected) > 0.05f64.to_radians());
+}
+
+#[test]
+fn moon_matches_vallado_example_5_3() {
+    // 28 April 1994 00:00 UT; Vallado's Alg. 31 is good to ~0.3 deg and
+    // ~1300 km, the bound on this comparison [km]
+    let t = tt_seconds(TimeScale::UTC, (1994, 4, 28, 0, 0, 0.));
+    let expected = array![-134240.626, -311571.590, -126693.785] * 1e3;
+
+    let r_mod = gcrf_to_mod(t / SEC_PER_CENTURY).dot(&moon_position_eci(t));
+    assert!(angle(&r_mod, &expected) < 0.3f64.to_radians());
+    assert!((norm(&r_mod) - norm(&expected)).abs() < 1300e3);
+}
+
+#[test]
+fn sun_and_moon_share_a_frame_at_eclipse() {
+    // Greatest eclipse [TT] and gamma, the distance of the shadow axis from
+    // the Earth's center [Earth radii]; NASA eclipse predictions. A frame
+    // mismatch of one precession offset moves the axis by ~0.3 RE.
+    for (date, gamma) in [
+        ((2017, 8, 21, 18, 26, 40.), 0.4367),
+        ((2024, 4, 8, 18, 17, 16.), 0.3431),
+    ] {
+        let t = tt_seconds(TimeScale::TT, date);
+        let r_sun = sun_position_eci(t);
+        let r_moon = moon_position_eci(t);
+
+        let axis = &r_sun - &r_moon;
+        let axis = &axis / norm(&axis);
+        let offset = &r_moon - &(r_moon.dot(&axis) * &axis);
+        assert!((norm(&offset) / RE - gamma).abs() < 0.02, "{date:?}");
+    }
+}
+
+#[test]
+fn third_body_matches_direct_difference() {
+    let r_moon = array![384400e3, 0., 0.];
+
+    // GEO on the Earth-Moon line: mu (1/(d - r)^2 - 1/d^2) toward the Moon
+    let r_geo = array![42164e3, 0., 0.];
+    let a = third_body_acceleration(&r_geo.view(), &r_moon.view(), MU_MOON);
+    let expected = MU_MOON * (1. / (384400e3 - 42164e3f64).powi(2) - 1. / 384400e3f64.powi(2));
+    assert!((a[0] / expected - 1.).abs() < 1e-12, "{a}");
+    assert!(a[1] == 0. && a[2] == 0.);
+
+    // General geometry against the direct and indirect terms
+    let r_sun = sun_position_eci(2.1e8);
+    for rsc in [
+        array![7000e3, -1200e3, 300e3],
+        array![-20000e3, 30000e3, 1e6],
+    ] {
+        for (r_body, mu) in [(&r_moon, MU_MOON), (&r_sun, MU_SUN)] {
+            let d = r_body - &rsc;
+            let direct = mu * (&d / norm(&d).powi(3) - r_body / norm(r_body).powi(3));
+            let a = third_body_acceleration(&rsc.view(), &r_body.view(), mu);
+            assert!(norm(&(&a - &direct)) < 1e-9 * norm(&direct), "{a} {direct}");
+        }
+    }
+}
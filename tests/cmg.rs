@@ -0,0 +1,151 @@
+use std::f64::consts::FRAC_PI_2;
+
+use ndarray::{array, Array1};
+use polaris_fsw::actuators::types::ActuatorBus;
+use polaris_plant::actuators::cmg::{ControlMomentGyro, ControlMomentGyroCluster};
+use polaris_plant::sc_types::{
+    ConfigurationError, SpacecraftActuatorArchitecture, SpacecraftParamBus,
+};
+use polaris_plant::Spacecraft;
+
+fn pyramid() -> ControlMomentGyroCluster {
+    // Four gimbals canted 54.74 deg from the body z axis, rotors tangential
+    let beta = (1. / 3f64.sqrt()).acos();
+    let gyros = (0..4)
+        .map(|i| {
+            let psi = i as f64 * FRAC_PI_2;
+            ControlMomentGyro {
+                gimbal_axis_body: [psi.cos() * beta.sin(), psi.sin() * beta.sin(), beta.cos()],
+                spin_axis_body: [-psi.sin(), psi.cos(), 0.],
+                ..Default::default()
+            }
+        })
+        .collect();
+    ControlMomentGyroCluster { gyros }
+}
+
+#[test]
+fn gimbal_rate_loop_and_limit() {
+    let cmg = ControlMomentGyro {
+        gimbal_time_constant: 0.,
+        ..Default::default()
+    };
+    let step = cmg.step(0.1, 0., 0.5, 0.2);
+    assert_eq!(step.rate, 0.5);
+    assert!((step.angle - 0.2).abs() < 1e-15);
+    assert!(!step.saturated);
+
+    let step = cmg.step(0., 0., -3., 0.2);
+    assert_eq!(step.rate, -cmg.max_gimbal_rate);
+    assert!(step.saturated);
+
+    // Lagged loop: rate approaches the command, travel trails the ideal
+    let lagged = ControlMomentGyro::default();
+    let tau = lagged.gimbal_time_constant;
+    let step = lagged.step(0., 0., 0.5, tau);
+    assert!((step.rate - 0.5 * (1. - (-1f64).exp())).abs() < 1e-15);
+    assert!((step.angle - 0.5 * tau * (-1f64).exp()).abs() < 1e-15);
+}
+
+#[test]
+fn jacobian_and_singularity_measure() {
+    // Torque directions x, y, z at zero gimbal angle
+    let triad = ControlMomentGyroCluster {
+        gyros: vec![
+            ControlMomentGyro {
+                gimbal_axis_body: [0., 0., 1.],
+                spin_axis_body: [0., -1., 0.],
+                ..Default::default()
+            },
+            ControlMomentGyro {
+                gimbal_axis_body: [1., 0., 0.],
+                spin_axis_body: [0., 0., -1.],
+                ..Default::default()
+            },
+            ControlMomentGyro {
+                gimbal_axis_body: [0., 1., 0.],
+                spin_axis_body: [-1., 0., 0.],
+                ..Default::default()
+            },
+        ],
+    };
+    let zero = array![0., 0., 0.];
+    assert!((&triad.jacobian(&zero) - &ndarray::Array2::<f64>::eye(3))
+        .iter()
+        .all(|x| x.abs() < 1e-15));
+    assert!((triad.singularity_measure(&zero) - 1.).abs() < 1e-15);
+
+    // Parallel gimbals only torque in their common plane
+    let parallel = ControlMomentGyroCluster {
+        gyros: vec![ControlMomentGyro::default(); 3],
+    };
+    assert!(parallel.singularity_measure(&array![0.1, 0.7, -1.2]).abs() < 1e-15);
+
+    // Pyramid at zero: no net momentum, away from singularity
+    let cluster = pyramid();
+    let angles = array![0., 0., 0., 0.];
+    assert!(cluster
+        .momentum_body(&angles)
+        .iter()
+        .all(|x| x.abs() < 1e-15));
+    assert!(cluster.singularity_measure(&angles) > 0.5);
+
+    // Saturation singularity: every rotor's momentum at its extreme along z
+    let beta = (1. / 3f64.sqrt()).acos();
+    let angles = Array1::from_elem(4, FRAC_PI_2);
+    let h = cluster.momentum_body(&angles);
+    assert!((h[[2, 0]] - 4. * beta.sin()).abs() < 1e-12, "{h}");
+    assert!(cluster.singularity_measure(&angles).abs() < 1e-12);
+}
+
+#[test]
+fn gimballing_slews_and_conserves_momentum() {
+    let params = SpacecraftParamBus {
+        sc_actuators: SpacecraftActuatorArchitecture {
+            control_moment_gyros: pyramid(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut sc = Spacecraft::initialize(0.1, params).unwrap();
+    // Commanded from the flight software bus
+    let bus = ActuatorBus {
+        cmg_rate_cmd: array![0.2, 0., -0.2, 0.],
+        ..Default::default()
+    };
+    for _ in 0..50 {
+        sc.simulate_plant(&bus);
+    }
+
+    // Opposed gimbals 1 and 3 trade momentum onto the body
+    let multibody = &sc.curr_sc_state.truth_multibody;
+    let h_cmg: Array1<f64> = multibody.h_cmg.column(0).to_owned();
+    assert!(h_cmg.dot(&h_cmg).sqrt() > 0.5, "{h_cmg}");
+    let omega = &sc.curr_sc_state.truth_attitude.signal.omega_sc;
+    assert!(omega.iter().any(|w| w.abs() > 1e-2), "{omega}");
+    assert!(multibody.h_total_eci.iter().all(|x| x.abs() < 1e-9));
+
+    let signal = &sc.curr_sc_state.truth_actuator_bus.control_moment_gyros;
+    assert!(signal.singularity > 0. && signal.singularity < 1.);
+    assert!(signal.gimbal_rate[1] == 0. && (signal.gimbal_rate[0] - 0.2).abs() < 1e-12);
+}
+
+#[test]
+fn rejects_spin_along_gimbal() {
+    let params = SpacecraftParamBus {
+        sc_actuators: SpacecraftActuatorArchitecture {
+            control_moment_gyros: ControlMomentGyroCluster {
+                gyros: vec![ControlMomentGyro {
+                    spin_axis_body: [0., 0., 1.],
+                    ..Default::default()
+                }],
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert!(matches!(
+        Spacecraft::initialize(0.1, params),
+        Err(ConfigurationError::Actuators(_))
+    ));
+}
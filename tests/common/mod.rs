@@ -0,0 +1,28 @@
+// Scaffolding shared by the sensor tests; not every test binary uses all of it
+#![allow(dead_code)]
+
+use polaris_fsw::actuators::types::ActuatorBus;
+use polaris_fsw::sensors::types::RawSensorBus;
+use polaris_plant::sc_types::SpacecraftParamBus;
+use polaris_plant::Spacecraft;
+
+pub const TS: f64 = 0.1; // Plant step [s]
+
+pub fn std_dev(x: &[f64]) -> f64 {
+    let mean = x.iter().sum::<f64>() / x.len() as f64;
+    (x.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (x.len() - 1) as f64).sqrt()
+}
+
+pub fn spacecraft(params: SpacecraftParamBus) -> Spacecraft {
+    Spacecraft::initialize(TS, params).unwrap()
+}
+
+/// Flight software sensor bus after `steps` plant steps under a constant
+/// flight software command.
+pub fn step_plant(sc: &mut Spacecraft, bus: &ActuatorBus, steps: usize) -> RawSensorBus {
+    let mut raw = sc.initial_state();
+    for _ in 0..steps {
+        raw = sc.simulate_plant(bus);
+    }
+    raw
+}
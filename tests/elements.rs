@@ -0,0 +1,200 @@
+use altai_rs::meta::types::{Generic2D, Vector3};
+use ndarray::{array, concatenate, s, Axis};
+use polaris_plant::ephemeris::consts::MU;
+use polaris_plant::ephemeris::elements::{
+    ClassicalElements, EquinoctialElements, ModifiedEquinoctialElements,
+};
+use polaris_plant::ephemeris::kinedynamics::orbital_perturbed;
+use polaris_plant::ephemeris::mean_elements::{
+    brouwer_mean_to_osculating, brouwer_osculating_to_mean, MeanElementsError,
+};
+use polaris_plant::ephemeris::types::{ForceModel, GravityModel};
+use polaris_plant::ode::{DormandPrince45, Integrator};
+use polaris_plant::sc_types::SpacecraftParamBus;
+use polaris_plant::Spacecraft;
+
+fn assert_state_close(a: &(Vector3, Vector3), b: &(Vector3, Vector3)) {
+    assert!((&a.0 - &b.0).iter().all(|x| x.abs() < 1e-5), "{a:?} {b:?}");
+    assert!((&a.1 - &b.1).iter().all(|x| x.abs() < 1e-8), "{a:?} {b:?}");
+}
+
+fn round_trip(coe: ClassicalElements) {
+    let state = coe.to_cartesian(MU);
+    let (r, v) = (state.0.column(0), state.1.column(0));
+
+    let coe_back = ClassicalElements::from_cartesian(&r, &v, MU);
+    assert_state_close(&coe_back.to_cartesian(MU), &state);
+
+    let eq = EquinoctialElements::from_cartesian(&r, &v, MU);
+    assert_state_close(&eq.to_cartesian(MU), &state);
+    assert_state_close(&eq.to_classical().to_cartesian(MU), &state);
+
+    let mee = ModifiedEquinoctialElements::from_cartesian(&r, &v, MU);
+    assert_state_close(&mee.to_cartesian(MU), &state);
+    assert_state_close(&mee.to_classical().to_cartesian(MU), &state);
+    assert_state_close(
+        &ModifiedEquinoctialElements::from_classical(&coe_back).to_cartesian(MU),
+        &state,
+    );
+}
+
+#[test]
+fn conversions_round_trip() {
+    // General, circular, equatorial, circular equatorial, hyperbolic
+    let cases = [
+        (7000e3, 0.1, 0.9, 1.2, 2.3, 4.0),
+        (7000e3, 0., 0.9, 1.2, 0., 4.0),
+        (8000e3, 0.2, 0., 0., 2.3, 1.0),
+        (6900e3, 0., 0., 0., 0., 5.5),
+        (-20000e3, 1.5, 0.5, 3.0, 1.0, 0.8),
+        (-20000e3, 1.5, 0., 0., 1.0, -0.8),
+    ];
+    for (a, e, i, raan, argp, nu) in cases {
+        round_trip(ClassicalElements {
+            a,
+            e,
+            i,
+            raan,
+            argp,
+            nu,
+        });
+    }
+}
+
+#[test]
+fn degenerate_angles_follow_convention() {
+    let coe = ClassicalElements {
+        a: 7000e3,
+        e: 0.,
+        i: 0.,
+        raan: 0.,
+        argp: 0.,
+        nu: 1.3,
+    };
+    let (r, v) = coe.to_cartesian(MU);
+    let back = ClassicalElements::from_cartesian(&r.column(0), &v.column(0), MU);
+    assert_eq!((back.raan, back.argp), (0., 0.));
+    assert!((back.nu - 1.3).abs() < 1e-12);
+
+    // Retrograde equatorial: longitude of periapsis measured about -z
+    let coe = ClassicalElements {
+        a: 7000e3,
+        e: 0.1,
+        i: std::f64::consts::PI,
+        raan: 0.,
+        argp: 0.7,
+        nu: 0.2,
+    };
+    let (r, v) = coe.to_cartesian(MU);
+    let back = ClassicalElements::from_cartesian(&r.column(0), &v.column(0), MU);
+    assert!((back.argp - 0.7).abs() < 1e-9 && (back.nu - 0.2).abs() < 1e-9);
+}
+
+#[test]
+fn brouwer_mean_elements_remove_short_period_j2() {
+    let coe = ClassicalElements {
+        a: 7000e3,
+        e: 0.01,
+        i: 51.6f64.to_radians(),
+        raan: 0.5,
+        argp: 1.0,
+        nu: 0.3,
+    };
+    let (r, v) = coe.to_cartesian(MU);
+    let mut state = concatenate![Axis(0), r.column(0), v.column(0)];
+    let forces = ForceModel {
+        gravity: GravityModel::Zonal { degree: 2 },
+        ..Default::default()
+    };
+    let inputs: Generic2D = concatenate![
+        Axis(1),
+        array![[0.], [0.], [0.]],
+        array![[1.], [0.], [0.]],
+        Generic2D::eye(3)
+    ];
+    let mut solver = DormandPrince45::new(120., 1e-9, 1e-12);
+
+    let (mut osc_a, mut mean_a) = (vec![], vec![]);
+    for k in 0..50 {
+        let osc =
+            ClassicalElements::from_cartesian(&state.slice(s![0..3]), &state.slice(s![3..6]), MU);
+        let mean = brouwer_osculating_to_mean(&osc).unwrap();
+        if k == 0 {
+            let back = brouwer_mean_to_osculating(&mean).unwrap();
+            assert!((back.a - osc.a).abs() < 1e-6 && (back.e - osc.e).abs() < 1e-12);
+        }
+        osc_a.push(osc.a);
+        mean_a.push(mean.a);
+        state = solver.integrate(
+            &|t, x, u| orbital_perturbed(t, x, u, &forces),
+            k as f64 * 120.,
+            &state,
+            &inputs,
+        );
+    }
+    let spread = |x: &[f64]| {
+        x.iter().cloned().fold(f64::MIN, f64::max) - x.iter().cloned().fold(f64::MAX, f64::min)
+    };
+    // Kilometre-level osculating oscillation, O(J2^2) residual in the mean
+    assert!(spread(&osc_a) > 5e3);
+    assert!(spread(&mean_a) < 50.);
+}
+
+#[test]
+fn brouwer_rejects_singular_inclinations() {
+    let coe = ClassicalElements {
+        a: 7000e3,
+        e: 0.01,
+        i: 0.,
+        raan: 0.5,
+        argp: 1.0,
+        nu: 0.3,
+    };
+    assert_eq!(
+        brouwer_osculating_to_mean(&coe),
+        Err(MeanElementsError::Equatorial(0.))
+    );
+    let retrograde = ClassicalElements {
+        i: std::f64::consts::PI,
+        ..coe.clone()
+    };
+    assert!(matches!(
+        brouwer_mean_to_osculating(&retrograde),
+        Err(MeanElementsError::Equatorial(_))
+    ));
+
+    let critical = (1f64 / 5.).sqrt().acos();
+    for i in [critical, std::f64::consts::PI - critical] {
+        let coe = ClassicalElements { i, ..coe.clone() };
+        assert_eq!(
+            brouwer_osculating_to_mean(&coe),
+            Err(MeanElementsError::CriticalInclination(i))
+        );
+    }
+
+    let hyperbolic = ClassicalElements {
+        a: -7000e3,
+        e: 1.5,
+        i: 1.,
+        ..coe.clone()
+    };
+    assert!(matches!(
+        brouwer_osculating_to_mean(&hyperbolic),
+        Err(MeanElementsError::NotElliptic(_))
+    ));
+
+    // Just clear of both singularities the result stays finite
+    for i in [0.01, critical + 0.01] {
+        let mean = brouwer_osculating_to_mean(&ClassicalElements { i, ..coe.clone() }).unwrap();
+        assert!(mean.a.is_finite() && mean.i.is_finite() && mean.argp.is_finite());
+    }
+}
+
+#[test]
+fn default_equatorial_orbit_has_no_mean_elements() {
+    let sc = Spacecraft::initialize(0.1, SpacecraftParamBus::default()).unwrap();
+    assert!(matches!(
+        sc.curr_sc_state.truth_ephemeris.signal.mean_elements(),
+        Err(MeanElementsError::Equatorial(_))
+    ));
+}
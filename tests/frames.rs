@@ -0,0 +1,198 @@
+use std::sync::Arc;
+
+use ndarray::array;
+use polaris_fsw::actuators::types::ActuatorBus;
+use polaris_plant::ephemeris::consts::{MU, RE};
+use polaris_plant::frames::eop::EopData;
+use polaris_plant::frames::geodetic::{
+    ecef_to_enu, ecef_to_geodetic, ecef_to_ned, geodetic_to_ecef, Geodetic,
+};
+use polaris_plant::frames::types::{EarthRotationModel, FrameModel};
+use polaris_plant::sc_types::{SpacecraftEphemerisArchitecture, SpacecraftParamBus};
+use polaris_plant::time::types::{Epoch, TimeScale};
+use polaris_plant::Spacecraft;
+
+// Vallado Example 3-15, 2004 April 6 07:51:28.386009 UTC
+const EOP_CSV: &str = "DATE,MJD,X,Y,UT1-UTC,LOD,DPSI,DEPS
+2004-04-06,53101,-0.140682,0.333309,-0.4399619,0.0015563,-0.052195,-0.003875
+2004-04-07,53102,-0.140682,0.333309,-0.4399619,0.0015563,-0.052195,-0.003875
+";
+
+fn vallado_epoch() -> Epoch {
+    Epoch::from_gregorian(TimeScale::UTC, 2004, 4, 6, 7, 51, 28.386009)
+}
+
+#[test]
+fn eop_csv_is_interpolated() {
+    let eop = EopData::from_csv_str(EOP_CSV).unwrap();
+    let rec = eop.at(53101.5).unwrap();
+    assert!((rec.dut1 + 0.4399619).abs() < 1e-12);
+    assert!((rec.xp.to_degrees() * 3600. + 0.140682).abs() < 1e-9);
+    assert!(EopData::from_csv_str("MJD,X,Y\n53101,0,0\n").is_err());
+}
+
+#[test]
+fn fk5_matches_vallado_itrf() {
+    let frames = FrameModel {
+        rotation: EarthRotationModel::Iau76Fk5,
+        eop: Some(Arc::new(EopData::from_csv_str(EOP_CSV).unwrap())),
+        ..Default::default()
+    };
+    // J2000 state without the IERS nutation corrections
+    let r_eci = array![5102.5096e3, 6123.01152e3, 6378.1363e3];
+    let v_eci = array![-4.7432196e3, 0.7905366e3, 5.53375619e3];
+    let time = vallado_epoch().seconds_since_j2000(TimeScale::TT);
+    let (r_ecef, v_ecef) = frames.rv_eci_to_ecef(time, &r_eci.view(), &v_eci.view());
+
+    let r_ref = array![-1033.4793830e3, 7901.2952754e3, 6380.3565958e3];
+    let v_ref = array![-3.225636520e3, -2.872451450e3, 5.531924446e3];
+    // Truncated nutation series: decimetre level
+    assert!((&r_ecef - &r_ref).iter().all(|x| x.abs() < 0.5));
+    assert!((&v_ecef - &v_ref).iter().all(|x| x.abs() < 1e-3));
+}
+
+#[test]
+fn configured_dut1_rotates_itrf() {
+    let time = vallado_epoch().seconds_since_j2000(TimeScale::TT);
+    for rotation in [EarthRotationModel::GmstOnly, EarthRotationModel::Iau76Fk5] {
+        let m0 = FrameModel {
+            rotation,
+            ..Default::default()
+        }
+        .eci_to_ecef(time);
+        let m1 = FrameModel {
+            rotation,
+            dut1: 0.5,
+            ..Default::default()
+        }
+        .eci_to_ecef(time);
+        // Half a UT1 second turns the Earth eastward about its pole
+        let angle = 0.5 * 1.0027378119113546 * 2. * std::f64::consts::PI / 86400.;
+        let d = m1.dot(&m0.t());
+        assert!((d[[0, 1]] - angle.sin()).abs() < 1e-12, "{rotation:?}");
+        assert!((d[[1, 0]] + angle.sin()).abs() < 1e-12);
+        assert!((d[[2, 2]] - 1.).abs() < 1e-15);
+    }
+
+    // Without a table, Vallado's DUT1 leaves only the polar motion to
+    // separate the result from the EOP solution
+    let r_eci = array![5102.5096e3, 6123.01152e3, 6378.1363e3];
+    let eop = FrameModel {
+        rotation: EarthRotationModel::Iau76Fk5,
+        eop: Some(Arc::new(EopData::from_csv_str(EOP_CSV).unwrap())),
+        ..Default::default()
+    };
+    let configured = FrameModel {
+        rotation: EarthRotationModel::Iau76Fk5,
+        dut1: -0.4399619,
+        ..Default::default()
+    };
+    let r_eop = eop.eci_to_ecef(time).dot(&r_eci);
+    let r_dut1 = configured.eci_to_ecef(time).dot(&r_eci);
+    let r_utc = FrameModel {
+        rotation: EarthRotationModel::Iau76Fk5,
+        ..Default::default()
+    }
+    .eci_to_ecef(time)
+    .dot(&r_eci);
+    let miss = |r: &ndarray::Array1<f64>| (r - &r_eop).dot(&(r - &r_eop)).sqrt();
+    assert!(miss(&r_dut1) < 20., "{}", miss(&r_dut1));
+    assert!(miss(&r_utc) > 100., "{}", miss(&r_utc));
+}
+
+#[test]
+fn gmst_only_is_a_z_rotation() {
+    let frames = FrameModel::default();
+    let time = vallado_epoch().seconds_since_j2000(TimeScale::TT);
+    let m = frames.eci_to_ecef(time);
+    assert!((m[[2, 2]] - 1.).abs() < 1e-15);
+    assert!((&m.dot(&m.t()) - &ndarray::Array2::<f64>::eye(3))
+        .iter()
+        .all(|x| x.abs() < 1e-14));
+}
+
+#[test]
+fn geodetic_matches_vallado_example() {
+    // Vallado Example 3-3
+    let r_ecef = array![6524.834e3, 6862.875e3, 6448.296e3];
+    let geo = ecef_to_geodetic(&r_ecef.view());
+    assert!((geo.latitude.to_degrees() - 34.352496).abs() < 1e-5);
+    assert!((geo.longitude.to_degrees() - 46.4464).abs() < 1e-4);
+    assert!((geo.altitude - 5085.22e3).abs() < 10.);
+
+    let back = geodetic_to_ecef(&geo);
+    assert!((&back - &r_ecef).iter().all(|x| x.abs() < 1e-6));
+}
+
+#[test]
+fn geodetic_round_trip() {
+    // Below the ellipsoid to beyond GEO, including the poles
+    for latitude in [-90., -89.999, -60., -1e-7, 0., 35., 89.9, 90.] {
+        for altitude in [-5e3, 0., 400e3, 20200e3, 35786e3, 400000e3] {
+            let geo = Geodetic {
+                latitude: f64::to_radians(latitude),
+                longitude: 2.1,
+                altitude,
+            };
+            let r = geodetic_to_ecef(&geo);
+            let back = ecef_to_geodetic(&r.view());
+            // Sub-millimetre at the surface
+            let arc = (back.latitude - geo.latitude).abs() * (RE + altitude);
+            assert!(arc < 1e-4, "{latitude} deg, {altitude} m: {arc}");
+            assert!(
+                (back.altitude - altitude).abs() < 1e-4,
+                "{latitude} deg, {altitude} m"
+            );
+            if latitude.abs() < 90. {
+                assert!((back.longitude - 2.1).abs() < 1e-12);
+            }
+        }
+    }
+}
+
+#[test]
+fn reentry_flag_latches() {
+    // Equatorial orbit from a 300 km apogee down to an 80 km perigee, below
+    // the 100 km floor, and back up
+    let (ra, rp) = (RE + 300e3, RE + 80e3);
+    let v_apogee = (2. * MU * rp / (ra * (ra + rp))).sqrt();
+    let params = SpacecraftParamBus {
+        sc_ephemeris: SpacecraftEphemerisArchitecture::initialize(
+            array![[ra], [0.], [0.]],
+            array![[0.], [v_apogee], [0.]],
+        ),
+        ..Default::default()
+    };
+    let mut sc = Spacecraft::initialize(10., params).unwrap();
+    assert!(!sc.curr_sc_state.truth_ephemeris.signal.reentered);
+
+    let cmd = ActuatorBus::default();
+    let mut below = false;
+    let mut climbed_back = false;
+    for _ in 0..600 {
+        sc.simulate_plant(&cmd);
+        let signal = &sc.curr_sc_state.truth_ephemeris.signal;
+        if signal.geodetic.altitude < 100e3 {
+            below = true;
+        }
+        assert_eq!(signal.reentered, below);
+        climbed_back |= below && signal.geodetic.altitude > 250e3;
+    }
+    assert!(below && climbed_back);
+}
+
+#[test]
+fn local_frames_are_orthonormal() {
+    let geo = Geodetic {
+        latitude: -0.7,
+        longitude: 2.9,
+        altitude: 0.,
+    };
+    let enu = ecef_to_enu(&geo);
+    let ned = ecef_to_ned(&geo);
+    let eye = ndarray::Array2::<f64>::eye(3);
+    assert!((&enu.dot(&enu.t()) - &eye).iter().all(|x| x.abs() < 1e-14));
+    // Down is minus up, north and east shared
+    assert!((&ned.row(2) + &enu.row(2)).iter().all(|x| x.abs() < 1e-15));
+    assert!((&ned.row(0) - &enu.row(1)).iter().all(|x| x.abs() < 1e-15));
+}
@@ -0,0 +1,162 @@
+use altai_rs::meta::types::Generic1D;
+use ndarray::array;
+use polaris_plant::ephemeris::consts::J2;
+use polaris_plant::ephemeris::geopotential::{GravityField, GravityFieldError};
+
+// WGS 84 normal field; NIMA TR8350.2 Tables 3.1 and 3.4
+const WGS84_GM: f64 = 3.986004418e14;
+const WGS84_A: f64 = 6378137.;
+const WGS84_B: f64 = 6356752.3142;
+const WGS84_OMEGA: f64 = 7.292115e-5;
+const WGS84_GAMMA_E: f64 = 9.7803253359;
+const WGS84_GAMMA_P: f64 = 9.8321849378;
+
+fn wgs84_normal_field() -> GravityField {
+    let egm = "\
+        2 0 -0.484166774985D-03 0.0\n\
+        4 0  0.790303733511D-06 0.0\n\
+        6 0 -0.168724961151D-08 0.0\n\
+        8 0  0.346052468394D-11 0.0\n\
+        10 0 -0.265002225767D-14 0.0\n";
+    GravityField::from_egm_str(egm, WGS84_GM, WGS84_A, 10).unwrap()
+}
+
+fn norm(v: &Generic1D) -> f64 {
+    v.dot(v).sqrt()
+}
+
+#[test]
+fn icgem_header_and_records() {
+    let icgem = "\
+product_type              gravity_field
+modelname                 TEST
+earth_gravity_constant    0.3986004415E+15
+radius                    0.6378136300E+07
+max_degree                3
+norm                      fully_normalized
+key   L    M      C                    S                  sigma C    sigma S
+end_of_head
+gfc   0    0    1.000000000000E+00   0.000000000000E+00  0.0 0.0
+gfc   2    0   -4.841651437908E-04   0.000000000000E+00  0.0 0.0
+gfc   2    2    2.439383573283D-06  -1.400273703859D-06  0.0 0.0
+gfct  3    0    9.571612070934E-07   0.000000000000E+00  0.0 0.0 20000101
+trnd  3    0    1.0E-11              0.0                 0.0 0.0
+gfc   4    0    5.399658666390E-07   0.000000000000E+00  0.0 0.0
+";
+    let field = GravityField::from_icgem_str(icgem, 3).unwrap();
+    assert_eq!(field.gm, 3.986004415e14);
+    assert_eq!(field.radius, 6378136.3);
+    assert_eq!(field.max_degree, 3);
+    assert_eq!(field.cnm(2, 0), -4.841651437908e-4);
+    assert_eq!(field.cnm(2, 2), 2.439383573283e-6);
+    assert_eq!(field.snm(2, 2), -1.400273703859e-6);
+    // Trend records are skipped, degrees above the truncation ignored
+    assert_eq!(field.cnm(3, 0), 9.571612070934e-7);
+
+    let missing = icgem.replace("radius ", "radius_x ");
+    assert!(matches!(
+        GravityField::from_icgem_str(&missing, 3),
+        Err(GravityFieldError::MissingHeader("radius"))
+    ));
+    let bad_order = icgem.replace("gfc   2    2", "gfc   2    3");
+    assert!(matches!(
+        GravityField::from_icgem_str(&bad_order, 3),
+        Err(GravityFieldError::Parse { line: 11, .. })
+    ));
+}
+
+#[test]
+fn unnormalized_icgem_is_normalized_on_load() {
+    let icgem = format!(
+        "earth_gravity_constant 3.986004418e14\n\
+         radius 6378137.0\n\
+         norm unnormalized\n\
+         end_of_head\n\
+         gfc 2 0 {} 0.0\n",
+        -J2
+    );
+    let field = GravityField::from_icgem_str(&icgem, 2).unwrap();
+    assert!((field.cnm(2, 0) + J2 / 5f64.sqrt()).abs() < 1e-18);
+}
+
+#[test]
+fn j2_matches_closed_form() {
+    let egm = format!("2 0 {:e} 0.0\n", -J2 / 5f64.sqrt());
+    let field = GravityField::from_egm_str(&egm, WGS84_GM, WGS84_A, 2).unwrap();
+
+    for r in [
+        array![4000e3, -3000e3, 5000e3],
+        array![-6800e3, 100e3, -20e3],
+        // Over the pole, where the longitude is undefined
+        array![0., 0., 7000e3],
+    ] {
+        let rmag = norm(&r);
+        let u2 = (r[2] / rmag).powi(2);
+        let fac = -1.5 * J2 * WGS84_GM * WGS84_A.powi(2) / rmag.powi(5);
+        let expected = array![
+            fac * r[0] * (1. - 5. * u2),
+            fac * r[1] * (1. - 5. * u2),
+            fac * r[2] * (3. - 5. * u2)
+        ];
+        let a = field.acceleration(&r.view(), 2, 0, false);
+        assert!(
+            norm(&(&a - &expected)) < 1e-12 * norm(&expected),
+            "{a} vs {expected}"
+        );
+    }
+}
+
+#[test]
+fn wgs84_normal_gravity_on_the_ellipsoid() {
+    let field = wgs84_normal_field();
+
+    // No centrifugal term at the pole
+    let a = field.acceleration(&array![0., 0., WGS84_B].view(), 10, 0, true);
+    assert!(a[0].abs() < 1e-12 && a[1].abs() < 1e-12);
+    assert!((-a[2] - WGS84_GAMMA_P).abs() < 1e-8, "{}", -a[2]);
+
+    // Attraction less the centrifugal acceleration on the equator
+    let a = field.acceleration(&array![WGS84_A, 0., 0.].view(), 10, 0, true);
+    let gamma = -a[0] - WGS84_OMEGA.powi(2) * WGS84_A;
+    assert!((gamma - WGS84_GAMMA_E).abs() < 1e-8, "{gamma}");
+    assert!(a[1].abs() < 1e-12 && a[2].abs() < 1e-12);
+}
+
+#[test]
+fn high_degree_gradient_matches_potential() {
+    // Kaula-rule magnitudes on every coefficient to degree 360
+    let nmax = 360;
+    let mut egm = String::new();
+    for n in 2..=nmax {
+        let sigma = 1e-5 / (n * n) as f64;
+        for m in 0..=n {
+            let phase = (n * 31 + m * 17) as f64;
+            egm.push_str(&format!(
+                "{n} {m} {:e} {:e}\n",
+                sigma * phase.sin(),
+                if m == 0 { 0. } else { sigma * phase.cos() }
+            ));
+        }
+    }
+    let field = GravityField::from_egm_str(&egm, WGS84_GM, WGS84_A, nmax).unwrap();
+
+    let h = 1.;
+    for r in [
+        array![4123e3, -2789e3, 4567e3],
+        // Within a metre of the pole
+        array![0.3, -0.4, 6900e3],
+    ] {
+        let a = field.acceleration(&r.view(), nmax, nmax, false);
+        assert!(a.iter().all(|x| x.is_finite()));
+        for k in 0..3 {
+            let mut rp = r.clone();
+            let mut rm = r.clone();
+            rp[k] += h;
+            rm[k] -= h;
+            let dv = (field.potential(&rp.view(), nmax, nmax, false)
+                - field.potential(&rm.view(), nmax, nmax, false))
+                / (2. * h);
+            assert!((a[k] - dv).abs() < 1e-9, "axis {k}: {} vs {dv}", a[k]);
+        }
+    }
+}
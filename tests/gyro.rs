@@ -0,0 +1,171 @@
+mod common;
+
+use common::{spacecraft, std_dev, step_plant};
+use ndarray::array;
+use polaris_fsw::actuators::types::ActuatorBus;
+use polaris_plant::sc_types::{
+    ConfigurationError, SpacecraftAttitudeArchitecture, SpacecraftParamBus,
+    SpacecraftSensorArchitecture,
+};
+use polaris_plant::sensors::gyro::{Gyro, GyroSignal};
+use polaris_plant::sensors::noise::NoiseSource;
+use polaris_plant::sensors::types::TruthSensorBus;
+use polaris_plant::Spacecraft;
+
+fn noiseless() -> Gyro {
+    Gyro {
+        angle_random_walk: 0.,
+        rate_random_walk: 0.,
+        bias_instability: 0.,
+        resolution: 0.,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn noise_source_is_seeded_standard_normal() {
+    let mut a = NoiseSource::new(7);
+    let mut b = NoiseSource::new(7);
+    let x: Vec<f64> = (0..100_000).map(|_| a.gaussian()).collect();
+    assert!(x.iter().all(|&v| v == b.gaussian()));
+    let mean = x.iter().sum::<f64>() / x.len() as f64;
+    assert!(mean.abs() < 0.01 && (std_dev(&x) - 1.).abs() < 0.01);
+    assert_ne!(NoiseSource::new(8).gaussian(), x[0]);
+}
+
+#[test]
+fn deterministic_errors_quantization_and_range() {
+    let mut misalignment = [[0.; 3]; 3];
+    misalignment[0][1] = 1e-3;
+    let gyro = Gyro {
+        initial_bias: [1e-4, 0., 0.],
+        scale_factor: [0.01, 0., 0.],
+        misalignment,
+        resolution: 1e-3,
+        max_rate: 0.5,
+        ..noiseless()
+    };
+    let mut signal = GyroSignal::initialize(&gyro);
+    assert!(!signal.valid);
+    signal.update(&gyro, &array![[0.1], [0.2], [-0.8]], 0.1);
+    assert!(signal.valid && signal.saturated);
+    // 1.01 * 0.1 + 1e-3 * 0.2 + 1e-4 = 0.1013, to a 1 mrad/s LSB
+    assert!(
+        (signal.rate[[0, 0]] - 0.101).abs() < 1e-12,
+        "{}",
+        signal.rate
+    );
+    assert!((signal.rate[[1, 0]] - 0.2).abs() < 1e-12);
+    assert_eq!(signal.rate[[2, 0]], -0.5);
+}
+
+#[test]
+fn allan_variance_terms() {
+    // White rate noise at 100 Hz: sigma = N / sqrt(dt)
+    let gyro = Gyro {
+        sample_rate: 100.,
+        angle_random_walk: 1e-4,
+        ..noiseless()
+    };
+    let mut signal = GyroSignal::initialize(&gyro);
+    let zero = array![[0.], [0.], [0.]];
+    let mut x = Vec::new();
+    for k in 1..=50_000 {
+        signal.update(&gyro, &zero, k as f64 * 0.01);
+        x.push(signal.rate[[0, 0]]);
+    }
+    assert!((std_dev(&x) - 1e-3).abs() < 3e-5, "{}", std_dev(&x));
+
+    // Bias instability settles to its steady-state sigma
+    let gyro = Gyro {
+        sample_rate: 10.,
+        bias_instability: 2e-5,
+        bias_correlation_time: 1.,
+        ..noiseless()
+    };
+    let mut signal = GyroSignal::initialize(&gyro);
+    let mut b = Vec::new();
+    for k in 1..=100_000 {
+        signal.update(&gyro, &zero, k as f64 * 0.1);
+        b.push(signal.bias[[1, 0]]);
+    }
+    assert!((std_dev(&b) - 2e-5).abs() < 1e-6, "{}", std_dev(&b));
+
+    // Rate random walk: bias variance grows as K^2 t across independent runs
+    let gyro = Gyro {
+        rate_random_walk: 1e-6,
+        ..noiseless()
+    };
+    let finals: Vec<f64> = (0..400)
+        .map(|seed| {
+            let gyro = Gyro {
+                seed,
+                ..gyro.clone()
+            };
+            let mut signal = GyroSignal::initialize(&gyro);
+            signal.update(&gyro, &zero, 100.);
+            signal.bias[[2, 0]]
+        })
+        .collect();
+    assert!(
+        (std_dev(&finals) - 1e-5).abs() < 1e-6,
+        "{}",
+        std_dev(&finals)
+    );
+}
+
+#[test]
+fn samples_published_on_the_sensor_bus() {
+    let params = SpacecraftParamBus {
+        sc_attitude: SpacecraftAttitudeArchitecture::initialize(
+            array![[0.], [0.], [0.], [1.]],
+            array![[0.01], [-0.02], [0.03]],
+        ),
+        sc_sensors: SpacecraftSensorArchitecture {
+            gyro: Some(Gyro {
+                sample_rate: 4.,
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut sc = spacecraft(params);
+    let gyro = |bus: &TruthSensorBus| bus.gyro.clone().unwrap();
+    assert!(!sc.initial_state().gyro_valid);
+
+    let cmd = ActuatorBus::default();
+    assert!(!step_plant(&mut sc, &cmd, 1).gyro_valid);
+    let raw = step_plant(&mut sc, &cmd, 9);
+    let sample = gyro(&sc.curr_sc_state.truth_sensor_bus);
+    assert!(sample.valid && raw.gyro_valid);
+    assert!((sample.sample_time - 1.).abs() < 1e-12);
+    let omega = &sc.curr_sc_state.truth_attitude.signal.omega_sc;
+    for i in 0..3 {
+        assert!((sample.rate[[i, 0]] - omega[[i, 0]]).abs() < 1e-3);
+        // The flight software reads the sampled rate, not the truth
+        assert_eq!(raw.gyro_rate[i], sample.rate[[i, 0]]);
+    }
+
+    // Held between samples
+    let held = step_plant(&mut sc, &cmd, 1);
+    assert_eq!(held.gyro_rate, raw.gyro_rate);
+}
+
+#[test]
+fn rejects_zero_sample_rate() {
+    let params = SpacecraftParamBus {
+        sc_sensors: SpacecraftSensorArchitecture {
+            gyro: Some(Gyro {
+                sample_rate: 0.,
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert!(matches!(
+        Spacecraft::initialize(0.1, params),
+        Err(ConfigurationError::Sensors(_))
+    ));
+}
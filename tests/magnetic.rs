@@ -0,0 +1,141 @@
+use ndarray::{array, Array1};
+use polaris_plant::ephemeris::magnetic::{
+    decimal_year, DipoleField, IgrfModel, MagneticFieldModel, IGRF_RADIUS,
+};
+use polaris_plant::sc_types::{SpacecraftAttitudeArchitecture, SpacecraftParamBus};
+use polaris_plant::Spacecraft;
+
+// Degree-2 excerpt of igrf13coeffs.txt
+const IGRF13_EXCERPT: &str = "\
+# 13th Generation International Geomagnetic Reference Field Schmidt semi-normalised spherical harmonic coefficients, degree n=1,13
+# in units nanoTesla for IGRF and definitive DGRF main-field models (degree n=1,8 nanoTesla/year for secular variation (SV))
+c/s deg ord DGRF IGRF SV
+g/h n m 2015.0 2020.0 2020-25
+g 1 0 -29441.46 -29404.8 5.7
+g 1 1 -1501.77 -1450.9 7.4
+h 1 1 4795.99 4652.5 -25.9
+g 2 0 -2445.88 -2499.6 -11.0
+g 2 1 3012.20 2982.0 -7.0
+h 2 1 -2845.41 -2991.6 -30.2
+g 2 2 1676.35 1677.0 -2.1
+h 2 2 -642.17 -734.6 -22.4
+";
+
+fn norm(v: &Array1<f64>) -> f64 {
+    v.dot(v).sqrt()
+}
+
+fn degree2_closed_form(r: &Array1<f64>, g: [f64; 3], h: [f64; 3]) -> Array1<f64> {
+    // Spherical components from the Schmidt functions written out by hand
+    let rmag = norm(r);
+    let (ct, st) = (r[2] / rmag, (r[0].powi(2) + r[1].powi(2)).sqrt() / rmag);
+    let phi = r[1].atan2(r[0]);
+    let s3 = 3f64.sqrt();
+    let p = [0.5 * (3. * ct * ct - 1.), s3 * ct * st, 0.5 * s3 * st * st];
+    let dp = [-3. * ct * st, s3 * (ct * ct - st * st), s3 * st * ct];
+    let scale = 1e-9 * (IGRF_RADIUS / rmag).powi(4);
+    let (mut br, mut bt, mut bp) = (0., 0., 0.);
+    for m in 0..3 {
+        let mf = m as f64;
+        let (cm, sm) = ((mf * phi).cos(), (mf * phi).sin());
+        br += 3. * scale * (g[m] * cm + h[m] * sm) * p[m];
+        bt -= scale * (g[m] * cm + h[m] * sm) * dp[m];
+        bp -= scale * mf * (-g[m] * sm + h[m] * cm) * p[m] / st;
+    }
+    let (cp, sp) = (phi.cos(), phi.sin());
+    array![
+        br * st * cp + bt * ct * cp - bp * sp,
+        br * st * sp + bt * ct * sp + bp * cp,
+        br * ct - bt * st
+    ]
+}
+
+#[test]
+fn igrf_coefficients_and_secular_variation() {
+    let model = IgrfModel::from_coeffs_str(IGRF13_EXCERPT).unwrap();
+    assert_eq!(model.max_degree, 2);
+    assert_eq!(model.epochs(), &[2015., 2020.]);
+
+    let (g, h) = model.coefficients(2017.5);
+    assert!((g[[1, 0]] - 0.5 * (-29441.46 - 29404.8)).abs() < 1e-9);
+    assert!((h[[2, 2]] - 0.5 * (-642.17 - 734.6)).abs() < 1e-9);
+    assert_eq!(g[[0, 0]], 0.);
+
+    let (g, h) = model.coefficients(2022.);
+    assert!((g[[1, 1]] - (-1450.9 + 2. * 7.4)).abs() < 1e-9);
+    assert!((h[[2, 1]] - (-2991.6 - 2. * 30.2)).abs() < 1e-9);
+
+    // A single epoch holds before the epoch and extrapolates after it
+    let model = IgrfModel::from_coeffs_str("g/h n m 2020.0 2020-25\ng 1 0 -29404.8 5.7\n").unwrap();
+    assert_eq!(model.coefficients(2015.).0[[1, 0]], -29404.8);
+    assert!((model.coefficients(2021.).0[[1, 0]] - (-29404.8 + 5.7)).abs() < 1e-9);
+
+    assert!(IgrfModel::from_coeffs_str("g 1 0 1 2\n").is_err());
+    assert!(IgrfModel::from_coeffs_str("g/h n m 2020.0 2020-25\nq 1 0 1 2\n").is_err());
+}
+
+#[test]
+fn igrf_matches_dipole_and_degree_two_closed_form() {
+    let model = IgrfModel::from_coeffs_str(IGRF13_EXCERPT).unwrap();
+    let dipole = DipoleField::default();
+    let points = [
+        array![7000e3, 0., 0.],
+        array![-2000e3, 4500e3, 5000e3],
+        array![1000e3, -6500e3, -3000e3],
+    ];
+    for r in points.iter() {
+        let b1 = model.field_ecef(&r.view(), 2020., 1);
+        let b_dip = dipole.field_ecef(&r.view());
+        assert!(norm(&(&b1 - &b_dip)) < 1e-15, "{b1} {b_dip}");
+
+        let b2 = model.field_ecef(&r.view(), 2020., 2) - &b1;
+        let expected = degree2_closed_form(r, [-2499.6, 2982.0, 1677.0], [0., -2991.6, -734.6]);
+        assert!(norm(&(&b2 - &expected)) < 1e-15, "{b2} {expected}");
+    }
+}
+
+#[test]
+fn dipole_surface_magnitudes() {
+    let dipole = DipoleField::default();
+    let moment = (dipole.g10.powi(2) + dipole.g11.powi(2) + dipole.h11.powi(2)).sqrt();
+    // Geomagnetic pole along -m, equator perpendicular to it
+    let m_hat = array![dipole.g11, dipole.h11, dipole.g10] / moment;
+    let pole = -IGRF_RADIUS * &m_hat;
+    let b_pole = dipole.field_ecef(&pole.view());
+    assert!((norm(&b_pole) - 2e-9 * moment).abs() < 1e-12);
+    assert!(b_pole.dot(&m_hat) > 0.);
+
+    let eq = IGRF_RADIUS * array![m_hat[2], 0., -m_hat[0]] / (m_hat[0].hypot(m_hat[2]));
+    assert!((norm(&dipole.field_ecef(&eq.view())) - 1e-9 * moment).abs() < 1e-12);
+}
+
+#[test]
+fn field_published_on_the_ephemeris_signal() {
+    let year = decimal_year(0.);
+    assert!((year - 2000.).abs() < 1e-12);
+
+    let mut params = SpacecraftParamBus {
+        sc_attitude: SpacecraftAttitudeArchitecture::initialize(
+            // 90 deg about z
+            array![[0.], [0.], [0.5f64.sqrt()], [0.5f64.sqrt()]],
+            array![[0.], [0.], [0.]],
+        ),
+        ..Default::default()
+    };
+    params.sc_ephemeris.magnetic_field = MagneticFieldModel::Dipole(DipoleField::default());
+    let mut sc = Spacecraft::initialize(1., params).unwrap();
+    let cmd = Default::default();
+    for _ in 0..10 {
+        sc.simulate_plant(&cmd);
+    }
+    let signal = &sc.curr_sc_state.truth_ephemeris.signal;
+    let b_eci = signal.b_eci.column(0).to_owned();
+    let b_body = signal.b_body.column(0).to_owned();
+    // 500 km altitude: roughly 20-50 uT
+    assert!(norm(&b_eci) > 15e-6 && norm(&b_eci) < 55e-6, "{b_eci}");
+    assert!((norm(&b_body) - norm(&b_eci)).abs() < 1e-18);
+    assert!((b_body[0] - b_eci[1]).abs() < 1e-15 && (b_body[1] + b_eci[0]).abs() < 1e-15);
+
+    let b_ecef = signal.b_ecef.column(0).to_owned();
+    assert!((norm(&b_ecef) - norm(&b_eci)).abs() < 1e-18);
+}
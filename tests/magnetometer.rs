@@ -0,0 +1,154 @@
+mod common;
+
+use common::{spacecraft, std_dev, step_plant};
+use ndarray::array;
+use polaris_fsw::actuators::types::ActuatorBus;
+use polaris_plant::actuators::magnetorquers::{Magnetorquer, MagnetorquerAssembly};
+use polaris_plant::sc_types::{
+    ConfigurationError, SpacecraftActuatorArchitecture, SpacecraftParamBus,
+    SpacecraftSensorArchitecture,
+};
+use polaris_plant::sensors::magnetometer::{Magnetometer, MagnetometerSignal};
+use polaris_plant::Spacecraft;
+
+fn noiseless() -> Magnetometer {
+    Magnetometer {
+        noise: 0.,
+        resolution: 0.,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn hard_and_soft_iron_with_torquer_interference() {
+    let mut soft_iron = [[0.; 3]; 3];
+    soft_iron[0][0] = 0.02;
+    soft_iron[1][0] = -0.01;
+    let mut interference = [[0.; 3]; 3];
+    interference[0][2] = 1e-6;
+    let mag = Magnetometer {
+        hard_iron: [0., 2e-7, 0.],
+        bias: [0., 0., -1e-7],
+        soft_iron,
+        interference,
+        ..noiseless()
+    };
+    // Torquer z dipole of 10 A m2 adds 10 uT on x before soft iron
+    let sensed = mag.sensed_field([20e-6, 5e-6, -30e-6], [0., 0., 10.]);
+    let expected = [1.02 * 30e-6, 5e-6 - 0.01 * 30e-6 + 2e-7, -30e-6];
+    assert!(
+        (0..3).all(|i| (sensed[i] - expected[i]).abs() < 1e-18),
+        "{sensed:?}"
+    );
+
+    let mut signal = MagnetometerSignal::initialize(&mag);
+    assert!(!signal.valid);
+    signal.update(
+        &mag,
+        &array![[20e-6], [5e-6], [-30e-6]],
+        &array![[0.], [0.], [10.]],
+        0.1,
+    );
+    assert!(signal.valid && !signal.saturated);
+    assert!((signal.field[[2, 0]] - (-30e-6 - 1e-7)).abs() < 1e-18);
+}
+
+#[test]
+fn noise_range_and_quantization() {
+    let mag = Magnetometer {
+        noise: 1e-7,
+        resolution: 0.,
+        ..Default::default()
+    };
+    let b = array![[10e-6], [0.], [0.]];
+    let m = array![[0.], [0.], [0.]];
+    let mut signal = MagnetometerSignal::initialize(&mag);
+    let mut x = Vec::new();
+    for k in 1..=20_000 {
+        signal.update(&mag, &b, &m, k as f64 * 0.1);
+        x.push(signal.field[[1, 0]]);
+    }
+    assert!((std_dev(&x) - 1e-7).abs() < 3e-9, "{}", std_dev(&x));
+
+    let mag = Magnetometer {
+        max_field: 50e-6,
+        resolution: 1e-6,
+        ..noiseless()
+    };
+    let mut signal = MagnetometerSignal::initialize(&mag);
+    signal.update(&mag, &array![[12.4e-6], [-80e-6], [0.6e-6]], &m, 0.1);
+    assert!(signal.saturated);
+    assert!((signal.field[[0, 0]] - 12e-6).abs() < 1e-18);
+    assert!((signal.field[[1, 0]] + 50e-6).abs() < 1e-18);
+    assert!((signal.field[[2, 0]] - 1e-6).abs() < 1e-18);
+}
+
+#[test]
+fn samples_published_on_the_sensor_bus() {
+    let mut interference = [[0.; 3]; 3];
+    interference[1][1] = 2e-6;
+    let params = SpacecraftParamBus {
+        sc_actuators: SpacecraftActuatorArchitecture {
+            magnetorquers: MagnetorquerAssembly {
+                rods: vec![Magnetorquer {
+                    axis_body: [0., 1., 0.],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        sc_sensors: SpacecraftSensorArchitecture {
+            magnetometer: Some(Magnetometer {
+                interference,
+                ..noiseless()
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut sc = spacecraft(params);
+    assert!(!sc.initial_state().mag_valid);
+
+    let cmd = ActuatorBus {
+        mtq_cmd: array![0.1],
+        ..Default::default()
+    };
+    let raw = step_plant(&mut sc, &cmd, 1);
+    let sample = sc
+        .curr_sc_state
+        .truth_sensor_bus
+        .magnetometer
+        .clone()
+        .unwrap();
+    assert!(sample.valid && raw.mag_valid);
+    assert_eq!(raw.mag_field, sample.field.column(0));
+
+    // Truth field plus the torquer dipole active during the step
+    let b_body = &sc.curr_sc_state.truth_ephemeris.signal.b_body;
+    let m_body = &sc.curr_sc_state.truth_actuator_bus.magnetorquers.m_body;
+    assert!(m_body[[1, 0]] > 0.);
+    for i in 0..3 {
+        let expected = b_body[[i, 0]] + interference[i][i] * m_body[[i, 0]];
+        assert!((sample.field[[i, 0]] - expected).abs() < 1e-18);
+    }
+    assert!((sample.field[[1, 0]] - b_body[[1, 0]]).abs() > 1e-7);
+}
+
+#[test]
+fn rejects_zero_range() {
+    let params = SpacecraftParamBus {
+        sc_sensors: SpacecraftSensorArchitecture {
+            magnetometer: Some(Magnetometer {
+                max_field: 0.,
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert!(matches!(
+        Spacecraft::initialize(0.1, params),
+        Err(ConfigurationError::Sensors(_))
+    ));
+}
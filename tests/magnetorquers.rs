@@ -0,0 +1,137 @@
+use ndarray::{array, Array1};
+use polaris_fsw::actuators::types::ActuatorBus;
+use polaris_plant::actuators::magnetorquers::{
+    Magnetorquer, MagnetorquerAssembly, MagnetorquerCommand, MagnetorquerSignal,
+};
+use polaris_plant::sc_types::{
+    ConfigurationError, SpacecraftActuatorArchitecture, SpacecraftParamBus,
+};
+use polaris_plant::Spacecraft;
+
+fn rod(axis: [f64; 3]) -> Magnetorquer {
+    Magnetorquer {
+        axis_body: axis,
+        ..Default::default()
+    }
+}
+
+fn triad() -> MagnetorquerAssembly {
+    MagnetorquerAssembly {
+        rods: vec![rod([1., 0., 0.]), rod([0., 1., 0.]), rod([0., 0., 1.])],
+        command: MagnetorquerCommand::Dipole,
+    }
+}
+
+#[test]
+fn dipole_quantized_and_clipped() {
+    let mtq = Magnetorquer {
+        max_dipole: 0.2,
+        levels: 4,
+        residual_dipole: 1e-3,
+        ..rod([0., 0., 1.])
+    };
+    // 0.12 A m2 is drive 0.6, nearest step 0.5
+    let (m, sat) = mtq.dipole(0.12, MagnetorquerCommand::Dipole);
+    assert!((m - (0.1 + 1e-3)).abs() < 1e-15);
+    assert!(!sat);
+
+    let (m, sat) = mtq.dipole(-0.5, MagnetorquerCommand::Dipole);
+    assert!((m - (-0.2 + 1e-3)).abs() < 1e-15);
+    assert!(sat);
+
+    // Duty cycle is read as a fraction of full drive
+    let (m, _) = mtq.dipole(0.74, MagnetorquerCommand::DutyCycle);
+    assert!((m - (0.15 + 1e-3)).abs() < 1e-15);
+    let (m, sat) = mtq.dipole(1.2, MagnetorquerCommand::DutyCycle);
+    assert!((m - (0.2 + 1e-3)).abs() < 1e-15);
+    assert!(sat);
+
+    // Residual remains when off; continuous drive is not stepped
+    assert_eq!(mtq.dipole(0., MagnetorquerCommand::Dipole).0, 1e-3);
+    let continuous = Magnetorquer { levels: 0, ..mtq };
+    let (m, _) = continuous.dipole(0.123, MagnetorquerCommand::Dipole);
+    assert!((m - (0.123 + 1e-3)).abs() < 1e-15);
+}
+
+#[test]
+fn torque_is_dipole_cross_field() {
+    let assembly = MagnetorquerAssembly {
+        rods: vec![
+            Magnetorquer {
+                levels: 0,
+                ..rod([1., 0., 0.])
+            },
+            Magnetorquer {
+                levels: 0,
+                ..rod([0., 1., 0.])
+            },
+        ],
+        command: MagnetorquerCommand::Dipole,
+    };
+    let mut signal = MagnetorquerSignal::initialize(&assembly);
+    assert_eq!(signal.dipole, array![0., 0.]);
+
+    signal.step(&assembly, &array![0.1, -0.05]);
+    assert_eq!(signal.m_body, array![[0.1], [-0.05], [0.]]);
+    let b_body = array![[0.], [0.], [30e-6]];
+    let torque = signal.torque(&b_body);
+    let expected = array![[-0.05 * 30e-6], [-0.1 * 30e-6], [0.]];
+    assert!(
+        (&torque - &expected).iter().all(|x| x.abs() < 1e-20),
+        "{torque}"
+    );
+}
+
+#[test]
+fn torque_applied_in_the_field() {
+    let params = SpacecraftParamBus {
+        sc_actuators: SpacecraftActuatorArchitecture {
+            magnetorquers: triad(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut sc = Spacecraft::initialize(1., params).unwrap();
+    // Commanded from the flight software bus
+    let bus = ActuatorBus {
+        mtq_cmd: array![0.2, 0.2, 0.2],
+        ..Default::default()
+    };
+    for _ in 0..20 {
+        sc.simulate_plant(&bus);
+    }
+
+    // Dipole acts against the field at the start of the step
+    let act = &sc.curr_sc_state.truth_actuator_bus;
+    let b_body = &sc.prev_sc_state.truth_ephemeris.signal.b_body;
+    let torque: Array1<f64> = act.net_torques.column(0).to_owned();
+    let b: Array1<f64> = b_body.column(0).to_owned();
+    let m: Array1<f64> = act.magnetorquers.m_body.column(0).to_owned();
+    assert!(torque.dot(&torque).sqrt() > 1e-7, "{torque}");
+    assert!(torque.dot(&b).abs() < 1e-20 && torque.dot(&m).abs() < 1e-20);
+    assert_eq!(act.net_forces.sum(), 0.);
+
+    let omega = &sc.curr_sc_state.truth_attitude.signal.omega_sc;
+    assert!(omega.iter().any(|w| w.abs() > 0.));
+}
+
+#[test]
+fn rejects_non_positive_dipole() {
+    let params = SpacecraftParamBus {
+        sc_actuators: SpacecraftActuatorArchitecture {
+            magnetorquers: MagnetorquerAssembly {
+                rods: vec![Magnetorquer {
+                    max_dipole: 0.,
+                    ..rod([1., 0., 0.])
+                }],
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert!(matches!(
+        Spacecraft::initialize(1., params),
+        Err(ConfigurationError::Actuators(_))
+    ));
+}
@@ -0,0 +1,61 @@
+use ndarray::array;
+use polaris_plant::sc_types::{
+    ConfigurationError, MassPropertiesError, SpacecraftMassArchitecture, SpacecraftParamBus,
+};
+use polaris_plant::Spacecraft;
+
+#[test]
+fn rejects_invalid_mass_properties() {
+    let j = array![[10., 0., 0.], [0., 20., 0.], [0., 0., 30.]];
+    let r_cm = array![[0.], [0.], [0.]];
+    for mass in [0., -1., f64::NAN] {
+        assert!(matches!(
+            SpacecraftMassArchitecture::initialize(mass, j.clone(), r_cm.clone()),
+            Err(MassPropertiesError::NonPositiveMass(_))
+        ));
+    }
+    assert_eq!(
+        SpacecraftMassArchitecture::initialize(
+            1.,
+            array![[1., 0.1, 0.], [0., 2., 0.], [0., 0., 3.]],
+            r_cm.clone()
+        )
+        .unwrap_err(),
+        MassPropertiesError::NotSymmetric
+    );
+    // Symmetric, but with a negative principal moment: eigenvalues -1, 3, 3
+    for j in [
+        array![[1., 2., 0.], [2., 1., 0.], [0., 0., 3.]],
+        array![[-1., 0., 0.], [0., 3., 0.], [0., 0., 3.]],
+    ] {
+        assert!(matches!(
+            SpacecraftMassArchitecture::initialize(1., j, r_cm.clone()),
+            Err(MassPropertiesError::NotPositiveDefinite(_))
+        ));
+    }
+    assert!(matches!(
+        SpacecraftMassArchitecture::initialize(
+            1.,
+            array![[1., 0., 0.], [0., 1., 0.], [0., 0., 3.]],
+            r_cm
+        ),
+        Err(MassPropertiesError::TriangleInequality(_))
+    ));
+}
+
+#[test]
+fn initialization_returns_configuration_error() {
+    let mut params = SpacecraftParamBus::default();
+    params.sc_mass.mass = 0.;
+    let err = Spacecraft::initialize(0.1, params).unwrap_err();
+    assert_eq!(
+        err,
+        ConfigurationError::MassProperties(MassPropertiesError::NonPositiveMass(0.))
+    );
+    assert_eq!(
+        err.to_string(),
+        "invalid mass properties: mass 0 kg must be positive"
+    );
+
+    assert!(Spacecraft::initialize(0.1, SpacecraftParamBus::default()).is_ok());
+}
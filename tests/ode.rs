@@ -0,0 +1,88 @@
+use altai_rs::meta::types::{Generic1D, Generic2D};
+use ndarray::array;
+use polaris_plant::ode::{DormandPrince45, Integrator, IntegratorStats, Method, Solver};
+
+// y' = y cos t, y(0) = 1; y = exp(sin t)
+fn growth(t: f64, y: &Generic1D, _inputs: &Generic2D) -> Generic1D {
+    y * t.cos()
+}
+
+// Harmonic oscillator x'' = -x, x(0) = 1; x = cos t
+fn oscillator(_t: f64, y: &Generic1D, _inputs: &Generic2D) -> Generic1D {
+    array![y[1], -y[0]]
+}
+
+#[test]
+fn default_method_is_rk5() {
+    assert!(matches!(Method::default(), Method::RK5));
+}
+
+#[test]
+fn error_follows_tolerance() {
+    let inputs = Generic2D::zeros((0, 0));
+    let t_end = 10.;
+    let mut prev: Option<(f64, IntegratorStats)> = None;
+    for tol in [1e-4, 1e-6, 1e-8, 1e-10] {
+        let mut dp = DormandPrince45::new(t_end, tol, tol);
+        let y = dp.integrate(&growth, 0., &array![1.], &inputs);
+        let err = (y[0] - t_end.sin().exp()).abs();
+        assert!(err < 10. * tol, "tol {tol}: error {err}");
+
+        let stats = dp.stats();
+        // FSAL: one initial evaluation, then six per attempted sub-step
+        let attempts = stats.accepted_steps + stats.rejected_steps;
+        assert_eq!(stats.function_evals, 1 + 6 * attempts);
+
+        if let Some((prev_err, prev_stats)) = prev {
+            assert!(err < prev_err, "tol {tol}: {err} >= {prev_err}");
+            assert!(stats.accepted_steps > prev_stats.accepted_steps);
+        }
+        prev = Some((err, stats));
+    }
+
+    // Fifth order: two decades of tolerance cost about 10^(2/5) in steps
+    let mut coarse = DormandPrince45::new(t_end, 1e-6, 1e-6);
+    coarse.integrate(&oscillator, 0., &array![1., 0.], &inputs);
+    let mut fine = DormandPrince45::new(t_end, 1e-8, 1e-8);
+    let y = fine.integrate(&oscillator, 0., &array![1., 0.], &inputs);
+    let ratio = fine.stats().accepted_steps as f64 / coarse.stats().accepted_steps as f64;
+    assert!(ratio > 1.8 && ratio < 3.5, "{ratio}");
+    assert!((y[0] - t_end.cos()).abs() < 1e-7 && (y[1] + t_end.sin()).abs() < 1e-7);
+}
+
+#[test]
+fn oversized_first_step_is_rejected_and_carried_over() {
+    let inputs = Generic2D::zeros((0, 0));
+    let mut dp = DormandPrince45::new(10., 1e-9, 1e-9);
+    dp.integrate(&oscillator, 0., &array![1., 0.], &inputs);
+    let first = dp.stats();
+    // The first attempt spans the whole plant step
+    assert!(first.rejected_steps > 0);
+    assert!(first.accepted_steps > 1);
+
+    // The next plant step starts from the adapted sub-step size; the
+    // statistics restart with it
+    dp.integrate(&oscillator, 10., &array![1., 0.], &inputs);
+    let second = dp.stats();
+    assert!(second.rejected_steps < first.rejected_steps);
+    assert!(second.accepted_steps.abs_diff(first.accepted_steps) <= 2);
+}
+
+#[test]
+fn solver_reports_stats_only_when_adaptive() {
+    let inputs = Generic2D::zeros((0, 0));
+    let mut rk5 = Solver::initialize(&Method::RK5, 0.1);
+    let y = rk5.integrate(&growth, 0., &array![1.], &inputs);
+    assert!((y[0] - 0.1f64.sin().exp()).abs() < 1e-8);
+    assert!(rk5.stats().is_none());
+
+    let mut dp = Solver::initialize(
+        &Method::DormandPrince45 {
+            atol: 1e-10,
+            rtol: 1e-10,
+        },
+        0.1,
+    );
+    dp.integrate(&growth, 0., &array![1.], &inputs);
+    assert!(dp.stats().unwrap().accepted_steps > 0);
+}
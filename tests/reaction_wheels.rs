@@ -0,0 +1,178 @@
+use ndarray::array;
+use polaris_fsw::actuators::types::ActuatorBus;
+use polaris_plant::actuators::reaction_wheels::{
+    ReactionWheel, ReactionWheelAssembly, WheelFriction,
+};
+use polaris_plant::actuators::types::ActuatorCommands;
+use polaris_plant::sc_types::{
+    ConfigurationError, SpacecraftActuatorArchitecture, SpacecraftParamBus,
+};
+use polaris_plant::Spacecraft;
+
+fn wheel(axis: [f64; 3]) -> ReactionWheel {
+    ReactionWheel {
+        spin_axis_body: axis,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn command_limited_by_torque_and_current() {
+    let rw = ReactionWheel {
+        max_torque: 5e-3,
+        motor_constant: 2e-3,
+        max_current: 2.,
+        ..wheel([0., 0., 1.])
+    };
+    let step = rw.step(0., 1e-3, 0.1);
+    assert_eq!(step.motor_torque, 1e-3);
+    assert!(!step.saturated);
+    assert!((step.speed - 1e-3 / rw.inertia * 0.1).abs() < 1e-12);
+
+    // Current limit (4 mN m) binds before the torque limit
+    let step = rw.step(0., 1e-2, 0.1);
+    assert!((step.motor_torque - 4e-3).abs() < 1e-15);
+    assert!(step.saturated);
+}
+
+#[test]
+fn speed_held_at_cutoff() {
+    let rw = wheel([1., 0., 0.]);
+    let mut speed = rw.max_speed - 1.;
+    for _ in 0..100 {
+        let step = rw.step(speed, rw.max_torque, 0.1);
+        assert!(step.speed <= rw.max_speed + 1e-9);
+        speed = step.speed;
+    }
+    let step = rw.step(speed, rw.max_torque, 0.1);
+    assert!(step.saturated);
+    assert_eq!(step.motor_torque, 0.);
+
+    // Braking is still allowed
+    let step = rw.step(speed, -rw.max_torque, 0.1);
+    assert!(!step.saturated);
+    assert!(step.speed < speed);
+}
+
+#[test]
+fn friction_spins_down_and_sticks() {
+    let friction = WheelFriction {
+        coulomb: 1e-4,
+        stribeck: 5e-5,
+        stribeck_speed: 1.,
+        viscous: 1e-7,
+    };
+    let rw = ReactionWheel {
+        friction: friction.clone(),
+        ..wheel([0., 1., 0.])
+    };
+
+    // Coulomb plus viscous deceleration from 100 rad/s
+    let mut speed = 100.;
+    let mut t = 0.;
+    while speed > 0. {
+        speed = rw.step(speed, 0., 0.01).speed;
+        t += 0.01;
+    }
+    assert_eq!(speed, 0.);
+    let tau = rw.inertia / friction.viscous;
+    let stop_time = tau * (1. + 100. * friction.viscous / friction.coulomb).ln();
+    assert!((t - stop_time).abs() < 0.05 * stop_time, "{t} {stop_time}");
+
+    // Rests below breakaway, starts above it
+    let held = rw.step(0., 1.4e-4, 0.1);
+    assert_eq!(held.speed, 0.);
+    assert!((held.friction_torque + 1.4e-4).abs() < 1e-15);
+    assert!(rw.step(0., 1.6e-4, 0.1).speed > 0.);
+}
+
+#[test]
+fn assembly_momentum_and_reaction_torque() {
+    let params = SpacecraftParamBus {
+        sc_actuators: SpacecraftActuatorArchitecture {
+            reaction_wheels: ReactionWheelAssembly {
+                wheels: vec![
+                    wheel([1., 0., 0.]),
+                    wheel([0., 1., 0.]),
+                    wheel([0., 0., 1.]),
+                ],
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let j_z = params.sc_mass.j_sc[[2, 2]];
+    let mut sc = Spacecraft::initialize(0.1, params).unwrap();
+    let cmd = ActuatorCommands {
+        rw_torque_cmd: array![0., 0., 2e-3],
+        ..Default::default()
+    };
+    for _ in 0..100 {
+        sc.simulate_plant_commands(&cmd);
+    }
+
+    let act = &sc.curr_sc_state.truth_actuator_bus;
+    assert!((act.net_torques[[2, 0]] + 2e-3).abs() < 1e-15);
+    let speed = &sc.curr_sc_state.truth_attitude.signal.wheel_speed;
+    assert_eq!(speed[0], 0.);
+
+    // Spin about a principal axis: body plus wheel momentum is conserved
+    let h = &sc.curr_sc_state.truth_multibody.h_wheels;
+    let omega = &sc.curr_sc_state.truth_attitude.signal.omega_sc;
+    assert!(h[[2, 0]] > 0.);
+    assert!((j_z * omega[[2, 0]] + h[[2, 0]]).abs() < 1e-12);
+    // Body less rotor spin inertia absorbs -2 mN m over 10 s
+    let j_s = j_z - ReactionWheel::default().inertia;
+    assert!((j_s * omega[[2, 0]] + 2e-3 * 10.).abs() < 1e-12);
+}
+
+#[test]
+fn flight_software_torque_command_spins_wheels() {
+    let params = SpacecraftParamBus {
+        sc_actuators: SpacecraftActuatorArchitecture {
+            reaction_wheels: ReactionWheelAssembly {
+                wheels: vec![wheel([1., 0., 0.]), wheel([0., 0., 1.])],
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut fsw = Spacecraft::initialize(0.1, params.clone()).unwrap();
+    let mut plant = Spacecraft::initialize(0.1, params).unwrap();
+    let bus = ActuatorBus {
+        rw_torque_cmd: array![-1e-3, 2e-3],
+        ..Default::default()
+    };
+    let cmd = ActuatorCommands {
+        rw_torque_cmd: array![-1e-3, 2e-3],
+        ..Default::default()
+    };
+    for _ in 0..10 {
+        fsw.simulate_plant(&bus);
+        plant.simulate_plant_commands(&cmd);
+    }
+
+    let speed = &fsw.curr_sc_state.truth_attitude.signal.wheel_speed;
+    assert!(speed[0] < 0. && speed[1] > 0., "{speed}");
+    assert_eq!(
+        speed,
+        &plant.curr_sc_state.truth_attitude.signal.wheel_speed
+    );
+}
+
+#[test]
+fn rejects_non_unit_axis() {
+    let params = SpacecraftParamBus {
+        sc_actuators: SpacecraftActuatorArchitecture {
+            reaction_wheels: ReactionWheelAssembly {
+                wheels: vec![wheel([1., 1., 0.])],
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert!(matches!(
+        Spacecraft::initialize(0.1, params),
+        Err(ConfigurationError::Actuators(_))
+    ));
+}
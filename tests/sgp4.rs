@@ -0,0 +1,270 @@
+use std::sync::Arc;
+
+use polaris_plant::ephemeris::sgp4::Sgp4;
+use polaris_plant::ephemeris::tle::{Tle, TleError};
+use polaris_plant::ephemeris::types::{InitialOrbit, InitialOrbitError, OrbitPropagator};
+use polaris_plant::sc_types::{
+    ConfigurationError, SpacecraftEphemerisArchitecture, SpacecraftParamBus,
+};
+use polaris_plant::time::types::TimeScale;
+use polaris_plant::Spacecraft;
+
+// Verification element sets; Vallado et al. (2006), SGP4-VER.TLE
+const TLE_00005: (&str, &str) = (
+    "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753",
+    "2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667",
+);
+const TLE_88888: (&str, &str) = (
+    "1 88888U          80275.98708465  .00073094  13844-3  66816-4 0    8",
+    "2 88888  72.8435 115.9689 0086731  52.6988 110.5714 16.05824518  105",
+);
+const TLE_11801: (&str, &str) = (
+    "1 11801U          80230.29629788  .01431103  00000-0  14311-1      13",
+    "2 11801  46.7916 230.4354 7318036  47.4722  10.4117  2.28537848    13",
+);
+// Half-day (Molniya) resonance
+const TLE_09880: (&str, &str) = (
+    "1 09880U 77021A   06176.56157475  .00000421  00000-0  10000-3 0  9814",
+    "2 09880  64.5968 349.3786 7069051 270.0229  16.3320  2.00813614112380",
+);
+// One-day (geosynchronous) resonance
+const TLE_14128: (&str, &str) = (
+    "1 14128U 83058A   06176.02844893 -.00000158  00000-0  10000-3 0  9627",
+    "2 14128  11.4384  35.2134 0011562  26.4582 333.5652  0.98870114 46093",
+);
+
+fn assert_vector(lines: (&str, &str), tsince: f64, r_ref: [f64; 3], v_ref: [f64; 3]) {
+    let tle = Tle::from_two_lines(lines.0, lines.1).unwrap();
+    let (r, v) = Sgp4::initialize(&tle).unwrap().propagate(tsince).unwrap();
+    for i in 0..3 {
+        assert!((r[i] - r_ref[i]).abs() < 1e-6, "{r:?} {r_ref:?}");
+        assert!((v[i] - v_ref[i]).abs() < 1e-9, "{v:?} {v_ref:?}");
+    }
+}
+
+#[test]
+fn sgp4_near_earth_vectors() {
+    // Vallado et al. (2006), tcppver.out
+    assert_vector(
+        TLE_00005,
+        0.,
+        [7022.46529266, -1400.08296755, 0.03995155],
+        [1.893841015, 6.405893759, 4.534807250],
+    );
+    assert_vector(
+        TLE_00005,
+        360.,
+        [-7154.03120202, -3783.17682504, -3536.19412294],
+        [4.741887409, -4.151817765, -2.093935425],
+    );
+    assert_vector(
+        TLE_88888,
+        0.,
+        [2328.96975262, -5995.22051338, 1719.97297192],
+        [2.912073281, -0.983417956, -7.090816210],
+    );
+}
+
+#[test]
+fn sdp4_deep_space_vectors() {
+    // Vallado et al. (2006), tcppver.out
+    assert_vector(
+        TLE_11801,
+        0.,
+        [7473.37102491, 428.94748312, 5828.74846783],
+        [5.107155391, 6.444680305, -0.186133297],
+    );
+    assert_vector(
+        TLE_11801,
+        720.,
+        [14271.29083858, 24110.44309009, -4725.76320143],
+        [-0.320504528, 2.679841539, -2.084054355],
+    );
+    assert_vector(
+        TLE_14128,
+        0.,
+        [34747.57932696, 24502.37114079, -1.32832986],
+        [-1.731642662, 2.452772615, 0.608510081],
+    );
+}
+
+#[test]
+fn sdp4_resonance_steps_are_continuous() {
+    // The resonance terms are integrated in fixed 720 min steps from epoch;
+    // the state must not jump where one step hands over to the next
+    for lines in [TLE_09880, TLE_14128] {
+        let tle = Tle::from_two_lines(lines.0, lines.1).unwrap();
+        let sgp4 = Sgp4::initialize(&tle).unwrap();
+        for boundary in [-1440., -720., 720., 1440., 2160.] {
+            let dt = 1e-3;
+            let (r0, v0) = sgp4.propagate(boundary - dt).unwrap();
+            let (r1, v1) = sgp4.propagate(boundary + dt).unwrap();
+            for i in 0..3 {
+                // Motion over 2 dt [min]; the Taylor step leaves a sub-metre
+                // seam, a wrong hand-over kilometres
+                let predicted = r0[i] + 0.5 * (v0[i] + v1[i]) * 2. * dt * 60.;
+                assert!(
+                    (r1[i] - predicted).abs() < 1e-3,
+                    "{boundary}: {r0:?} {r1:?}"
+                );
+                assert!((v1[i] - v0[i]).abs() < 1e-3, "{boundary}: {v0:?} {v1:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn sdp4_resonant_orbits_stay_bound() {
+    // Geosynchronous (1-day) and Molniya (half-day) resonance over ten days:
+    // check continuity and the expected radius band
+    let base = Tle::from_two_lines(TLE_00005.0, TLE_00005.1).unwrap();
+    let cases = [
+        (1.0027, 0.0002, 0.1_f64, 41_000., 43_500.),
+        (2.0057, 0.72, 63.4, 6_500., 47_000.),
+    ];
+    for (rev_per_day, e, inc_deg, rmin, rmax) in cases {
+        let tle = Tle {
+            mean_motion: rev_per_day * 2. * std::f64::consts::PI / 1440.,
+            eccentricity: e,
+            inclination: inc_deg.to_radians(),
+            bstar: 0.,
+            mean_motion_dot: 0.,
+            mean_motion_ddot: 0.,
+            ..base.clone()
+        };
+        let sgp4 = Sgp4::initialize(&tle).unwrap();
+        let mut prev = sgp4.propagate(0.).unwrap().0;
+        for step in 1..=2880 {
+            let (r, _) = sgp4.propagate(step as f64 * 5.).unwrap();
+            let rmag = r.iter().map(|x| x * x).sum::<f64>().sqrt();
+            assert!(rmag > rmin && rmag < rmax, "{rmag}");
+            let jump = (0..3).map(|i| (r[i] - prev[i]).powi(2)).sum::<f64>().sqrt();
+            assert!(jump < 5. * 60. * 11., "{jump}");
+            prev = r;
+        }
+    }
+}
+
+#[test]
+fn tle_fields_and_checksum() {
+    let text = format!("0 VANGUARD 1\n{}\n{}\n", TLE_00005.0, TLE_00005.1);
+    let sets = Tle::from_str_multi(&text).unwrap();
+    assert_eq!(sets.len(), 1);
+    let tle = &sets[0];
+    assert_eq!(tle.name.as_deref(), Some("VANGUARD 1"));
+    assert_eq!(tle.catalog_number, 5);
+    assert_eq!(tle.revolution_number, 41366);
+    assert!((tle.eccentricity - 0.1859667).abs() < 1e-12);
+    assert!((tle.bstar - 0.28098e-4).abs() < 1e-15);
+    assert!((tle.inclination.to_degrees() - 34.2682).abs() < 1e-10);
+    let (y, mo, d, h, _, _) = tle.epoch.to_gregorian(TimeScale::UTC);
+    assert_eq!((y, mo, d, h), (2000, 6, 27, 18));
+
+    let corrupted = TLE_00005.0.replace("4753", "4754");
+    assert!(matches!(
+        Tle::from_two_lines(&corrupted, TLE_00005.1),
+        Err(TleError::Checksum { found: 4, .. })
+    ));
+}
+
+#[test]
+fn omm_kvn_and_xml_match_tle() {
+    let kvn = "CCSDS_OMM_VERS = 2.0
+OBJECT_NAME = VANGUARD 1
+NORAD_CAT_ID = 5
+MEAN_ELEMENT_THEORY = SGP4
+EPOCH = 2000-06-27T18:50:19.733568
+MEAN_MOTION = 10.82419157
+ECCENTRICITY = .1859667
+INCLINATION = 34.2682
+RA_OF_ASC_NODE = 348.7242
+ARG_OF_PERICENTER = 331.7664
+MEAN_ANOMALY = 19.3264
+BSTAR = .28098E-4
+MEAN_MOTION_DOT = .00000023
+MEAN_MOTION_DDOT = 0
+REV_AT_EPOCH = 41366
+";
+    let xml = "<omm><body><segment><data><meanElements>
+<EPOCH>2000-179T18:50:19.733568</EPOCH>
+<MEAN_MOTION>10.82419157</MEAN_MOTION>
+<ECCENTRICITY>.1859667</ECCENTRICITY>
+<INCLINATION>34.2682</INCLINATION>
+<RA_OF_ASC_NODE>348.7242</RA_OF_ASC_NODE>
+<ARG_OF_PERICENTER>331.7664</ARG_OF_PERICENTER>
+<MEAN_ANOMALY>19.3264</MEAN_ANOMALY>
+</meanElements><tleParameters>
+<NORAD_CAT_ID>5</NORAD_CAT_ID>
+<BSTAR>.28098E-4</BSTAR>
+<MEAN_MOTION_DOT>.00000023</MEAN_MOTION_DOT>
+</tleParameters></data></segment></body></omm>";
+
+    let tle = Tle::from_two_lines(TLE_00005.0, TLE_00005.1).unwrap();
+    let (r_ref, _) = Sgp4::initialize(&tle).unwrap().propagate(360.).unwrap();
+    for text in [kvn, xml] {
+        let omm = Tle::from_omm_str(text).unwrap();
+        assert_eq!(omm.catalog_number, 5);
+        let dt = omm.epoch.seconds_since_j2000(TimeScale::TT)
+            - tle.epoch.seconds_since_j2000(TimeScale::TT);
+        assert!(dt.abs() < 1e-3, "{dt}");
+        let (r, _) = Sgp4::initialize(&omm).unwrap().propagate(360.).unwrap();
+        for i in 0..3 {
+            assert!((r[i] - r_ref[i]).abs() < 1e-3, "{r:?} {r_ref:?}");
+        }
+    }
+    assert!(matches!(
+        Tle::from_omm_str("EPOCH = 2000-06-27T18:50:19"),
+        Err(TleError::MissingField("INCLINATION"))
+    ));
+}
+
+#[test]
+fn tle_initial_orbit_in_eci() {
+    let tle = Tle::from_two_lines(TLE_88888.0, TLE_88888.1).unwrap();
+    let sgp4 = Arc::new(Sgp4::initialize(&tle).unwrap());
+    let signal = InitialOrbit::Tle(sgp4.clone()).to_signal().unwrap();
+
+    // The TEME -> GCRF rotation preserves magnitudes
+    let (r_teme, v_teme) = sgp4.propagate(0.).unwrap();
+    let norm = |x: &[f64]| x.iter().map(|x| x * x).sum::<f64>().sqrt();
+    let r_eci: Vec<f64> = signal.r_sc_eci.iter().copied().collect();
+    let v_eci: Vec<f64> = signal.v_sc_eci.iter().copied().collect();
+    assert!((norm(&r_eci) - norm(&r_teme) * 1e3).abs() < 1e-6);
+    assert!((norm(&v_eci) - norm(&v_teme) * 1e3).abs() < 1e-9);
+
+    // 1980 is two decades from J2000; precession moves the vector noticeably
+    let offset = (0..3)
+        .map(|i| (r_eci[i] - r_teme[i] * 1e3).powi(2))
+        .sum::<f64>()
+        .sqrt();
+    assert!(offset > 1e3 && offset < 50e3, "{offset}");
+}
+
+#[test]
+fn sgp4_mode_requires_an_element_set() {
+    let params = SpacecraftParamBus {
+        sc_ephemeris: SpacecraftEphemerisArchitecture {
+            propagator: OrbitPropagator::Sgp4,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert_eq!(
+        Spacecraft::initialize(10., params).unwrap_err(),
+        ConfigurationError::InitialOrbit(InitialOrbitError::MissingElementSet)
+    );
+
+    let tle = Tle::from_two_lines(TLE_88888.0, TLE_88888.1).unwrap();
+    let params = SpacecraftParamBus {
+        sc_ephemeris: SpacecraftEphemerisArchitecture {
+            propagator: OrbitPropagator::Sgp4,
+            ..SpacecraftEphemerisArchitecture::from_initial_orbit(InitialOrbit::Tle(Arc::new(
+                Sgp4::initialize(&tle).unwrap(),
+            )))
+        },
+        ..Default::default()
+    }
+    .with_epoch(tle.epoch);
+    let mut sc = Spacecraft::initialize(10., params).unwrap();
+    sc.simulate_plant(&Default::default());
+}
@@ -0,0 +1,230 @@
+use ndarray::{array, Array1};
+use polaris_plant::ephemeris::celestial::{shadow_fraction, ShadowModel, AU, R_SUN};
+use polaris_plant::ephemeris::consts::RE;
+use polaris_plant::ephemeris::srp::{SrpModel, SrpPlate, P_SRP_AU};
+use polaris_plant::ephemeris::types::ForceModelError;
+use polaris_plant::sc_types::{
+    ConfigurationError, SpacecraftEphemerisArchitecture, SpacecraftParamBus,
+};
+use polaris_plant::Spacecraft;
+
+fn sun_at(r_sc: &Array1<f64>, angle: f64) -> Array1<f64> {
+    // Sun one AU from the spacecraft, `angle` from the Earth's center as seen
+    // from the spacecraft, in the x-z plane
+    let nadir = -r_sc / r_sc.dot(r_sc).sqrt();
+    let perp = array![-nadir[2], 0., nadir[0]];
+    r_sc + AU * (angle.cos() * nadir + angle.sin() * perp)
+}
+
+#[test]
+fn cylindrical_shadow() {
+    let r_sun = array![AU, 0., 0.];
+    let cases = [
+        (array![-7000e3, 0., 0.], 0.),
+        (array![-7000e3, 0., 0.99 * RE], 0.),
+        (array![-7000e3, 0., 1.01 * RE], 1.),
+        (array![7000e3, 0., 0.], 1.),
+        // Beside the Earth, not behind it
+        (array![0., 7000e3, 0.], 1.),
+    ];
+    for (r_sc, expected) in cases {
+        let nu = shadow_fraction(ShadowModel::Cylindrical, &r_sc.view(), &r_sun.view());
+        assert_eq!(nu, expected, "{r_sc}");
+    }
+}
+
+#[test]
+fn conical_umbra_penumbra_and_annular() {
+    let r_sc = array![-7000e3, 0., 0.];
+    let b = (RE / 7000e3).asin();
+    let a = (R_SUN / AU).asin();
+    let nu = |angle: f64| {
+        let r_sun = sun_at(&r_sc, angle);
+        shadow_fraction(ShadowModel::Conical, &r_sc.view(), &r_sun.view())
+    };
+
+    // Sun disk wholly behind or wholly clear of the Earth's limb
+    assert_eq!(nu(0.), 0.);
+    assert_eq!(nu(b - 1.01 * a), 0.);
+    assert_eq!(nu(b + 1.01 * a), 1.);
+    assert_eq!(nu(std::f64::consts::PI), 1.);
+
+    // Sun centered on the limb: half the disk is hidden
+    assert!((nu(b) - 0.5).abs() < 1e-3, "{}", nu(b));
+
+    // Monotonic across the penumbra
+    let mut prev = 0.;
+    for k in 0..=20 {
+        let f = nu(b - a + 2. * a * k as f64 / 20.);
+        assert!((0. ..=1.).contains(&f) && f >= prev, "{k}: {f}");
+        prev = f;
+    }
+
+    // Near Sun-Earth L2 the Earth is smaller than the Sun: an annular
+    // eclipse hiding the ratio of the disk areas
+    let r_sc = array![-1.5e9, 0., 0.];
+    let r_sun = array![AU, 0., 0.];
+    let nu = shadow_fraction(ShadowModel::Conical, &r_sc.view(), &r_sun.view());
+    let a = (R_SUN / (AU + 1.5e9)).asin();
+    let b = (RE / 1.5e9).asin();
+    assert!(b < a);
+    assert!((nu - (1. - (b / a).powi(2))).abs() < 1e-12, "{nu}");
+
+    // The cylinder puts the penumbra in full shadow
+    let r_sc = array![-7000e3, 0., 0.];
+    let b = (RE / 7000e3).asin();
+    let a = (R_SUN / AU).asin();
+    let r_sun = sun_at(&r_sc, b - 0.5 * a);
+    let cyl = shadow_fraction(ShadowModel::Cylindrical, &r_sc.view(), &r_sun.view());
+    let cone = shadow_fraction(ShadowModel::Conical, &r_sc.view(), &r_sun.view());
+    assert_eq!(cyl, 0.);
+    assert!(cone > 0. && cone < 0.5, "{cone}");
+}
+
+#[test]
+fn cannonball_force() {
+    let srp = SrpModel::Cannonball { cr: 1.3, area: 2. };
+    let sun_hat = array![0.6, 0., 0.8];
+    let r_cm = array![0.1, 0.2, 0.3];
+
+    let (force, torque) = srp.force_torque_body(&sun_hat.view(), AU, 1., &r_cm.view());
+    let expected = -P_SRP_AU * 1.3 * 2. * &sun_hat;
+    assert!((&force - &expected).iter().all(|x| x.abs() < 1e-20));
+    assert!(torque.iter().all(|&x| x == 0.));
+
+    // Inverse square in the Sun distance, scaled by the visible disk
+    let (far, _) = srp.force_torque_body(&sun_hat.view(), 2. * AU, 0.5, &r_cm.view());
+    assert!((&far - &(0.125 * &expected))
+        .iter()
+        .all(|x| x.abs() < 1e-20));
+
+    let (dark, _) = srp.force_torque_body(&sun_hat.view(), AU, 0., &r_cm.view());
+    assert!(dark.iter().all(|&x| x == 0.));
+}
+
+#[test]
+fn flat_plate_force_and_torque_at_60_deg_incidence() {
+    let plate = SrpPlate {
+        normal_body: [0., 0., 1.],
+        center_body: [1., 0., 0.],
+        area: 2.,
+        specular: 0.,
+        diffuse: 0.,
+    };
+    // cos(incidence) = 0.5
+    let sun_hat = array![60f64.to_radians().sin(), 0., 0.5];
+    let r_cm = array![0., 0., 0.];
+    let pa = P_SRP_AU * 2.;
+
+    // Black plate: absorbed momentum along the sunline over the projected area
+    let black = SrpModel::Plates(vec![plate.clone()]);
+    let (force, _) = black.force_torque_body(&sun_hat.view(), AU, 1., &r_cm.view());
+    let expected = -pa * 0.5 * &sun_hat;
+    assert!(
+        (&force - &expected).iter().all(|x| x.abs() < 1e-20),
+        "{force}"
+    );
+
+    // Mirror: purely normal, 2 P A cos^2
+    let mirror = SrpModel::Plates(vec![SrpPlate {
+        specular: 1.,
+        ..plate.clone()
+    }]);
+    let (force, torque) = mirror.force_torque_body(&sun_hat.view(), AU, 1., &r_cm.view());
+    assert!(force[0].abs() < 1e-20 && force[1].abs() < 1e-20);
+    assert!((force[2] + 2. * pa * 0.25).abs() < 1e-20);
+    // 1 m arm along x: torque = r x F about +y
+    assert!((torque[1] - 2. * pa * 0.25).abs() < 1e-20, "{torque}");
+    assert!(torque[0].abs() < 1e-20 && torque[2].abs() < 1e-20);
+
+    // Lambertian: absorbed, plus 2/3 rho_d re-emitted along the normal
+    let diffuse = SrpModel::Plates(vec![SrpPlate {
+        diffuse: 1.,
+        ..plate.clone()
+    }]);
+    let (force, _) = diffuse.force_torque_body(&sun_hat.view(), AU, 1., &r_cm.view());
+    let expected = -pa * 0.5 * (&sun_hat + &array![0., 0., 2. / 3.]);
+    assert!(
+        (&force - &expected).iter().all(|x| x.abs() < 1e-20),
+        "{force}"
+    );
+
+    // Torque is taken about the center of mass
+    let offset = array![1., 0., 0.];
+    let (_, torque) = mirror.force_torque_body(&sun_hat.view(), AU, 1., &offset.view());
+    assert!(torque.iter().all(|x| x.abs() < 1e-20));
+
+    // Back face unlit
+    let behind = array![sun_hat[0], 0., -0.5];
+    let (force, torque) = mirror.force_torque_body(&behind.view(), AU, 1., &r_cm.view());
+    assert!(force.iter().chain(torque.iter()).all(|&x| x == 0.));
+}
+
+#[test]
+fn rejects_invalid_srp_properties() {
+    let plate = SrpPlate {
+        normal_body: [0., 0., 1.],
+        center_body: [0., 0., 0.],
+        area: 1.,
+        specular: 0.3,
+        diffuse: 0.2,
+    };
+    let with_srp = |srp: SrpModel| SpacecraftParamBus {
+        sc_ephemeris: SpacecraftEphemerisArchitecture {
+            srp: Some(srp),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let cases = [
+        SrpModel::Cannonball { cr: 0.5, area: 1. },
+        SrpModel::Cannonball { cr: 2.5, area: 1. },
+        SrpModel::Cannonball {
+            cr: f64::NAN,
+            area: 1.,
+        },
+        SrpModel::Cannonball { cr: 1.3, area: 0. },
+        SrpModel::Plates(vec![SrpPlate {
+            area: f64::NAN,
+            ..plate.clone()
+        }]),
+        SrpModel::Plates(vec![SrpPlate {
+            normal_body: [0., 0., 2.],
+            ..plate.clone()
+        }]),
+        SrpModel::Plates(vec![SrpPlate {
+            specular: -0.1,
+            ..plate.clone()
+        }]),
+        SrpModel::Plates(vec![SrpPlate {
+            specular: 0.7,
+            diffuse: 0.4,
+            ..plate.clone()
+        }]),
+    ];
+    for srp in cases {
+        assert!(
+            matches!(
+                Spacecraft::initialize(1., with_srp(srp.clone())),
+                Err(ConfigurationError::ForceModel(_))
+            ),
+            "{srp:?}"
+        );
+    }
+    assert_eq!(
+        SrpModel::Plates(vec![SrpPlate {
+            specular: 0.7,
+            diffuse: 0.4,
+            ..plate.clone()
+        }])
+        .validate(),
+        Err(ForceModelError::ReflectivityAboveUnity {
+            specular: 0.7,
+            diffuse: 0.4
+        })
+    );
+
+    for srp in [SrpModel::default(), SrpModel::Plates(vec![plate.clone()])] {
+        assert!(Spacecraft::initialize(1., with_srp(srp)).is_ok());
+    }
+}
@@ -0,0 +1,239 @@
+mod common;
+
+use altai_rs::meta::types::Quaternion4;
+use common::{spacecraft, std_dev, step_plant};
+use ndarray::array;
+use polaris_fsw::actuators::types::ActuatorBus;
+use polaris_plant::attitude::kinedynamics::quat_multiply;
+use polaris_plant::attitude::types::TruthAttitudeSignal;
+use polaris_plant::ephemeris::types::TruthEphemerisSignal;
+use polaris_plant::sc_types::{
+    ConfigurationError, SpacecraftAttitudeArchitecture, SpacecraftParamBus,
+    SpacecraftSensorArchitecture,
+};
+use polaris_plant::sensors::star_tracker::{StarTracker, StarTrackerSignal, StarTrackerStatus};
+use polaris_plant::Spacecraft;
+
+const DEG: f64 = std::f64::consts::PI / 180.;
+
+fn noiseless() -> StarTracker {
+    StarTracker {
+        cross_boresight_noise: 0.,
+        roll_noise: 0.,
+        spatial_error_cross: 0.,
+        spatial_error_roll: 0.,
+        ..Default::default()
+    }
+}
+
+// Spacecraft on +x; Sun toward -z and Moon toward -y, both well clear of +z
+fn ephemeris() -> TruthEphemerisSignal {
+    TruthEphemerisSignal {
+        r_sun_eci: array![[0.], [0.], [-1.5e11]],
+        r_moon_eci: array![[0.], [-3.8e8], [0.]],
+        ..Default::default()
+    }
+}
+
+fn spin_z(rate: f64, t: f64) -> TruthAttitudeSignal {
+    TruthAttitudeSignal {
+        q_sc_eci: array![[0.], [0.], [(rate * t / 2.).sin()], [(rate * t / 2.).cos()]],
+        omega_sc: array![[0.], [0.], [rate]],
+        ..Default::default()
+    }
+}
+
+// Small-angle error vector of q_meas relative to q_true (Body) [rad]
+fn error(q_meas: &Quaternion4, q_true: &Quaternion4) -> [f64; 3] {
+    let inv = array![
+        [-q_true[[0, 0]]],
+        [-q_true[[1, 0]]],
+        [-q_true[[2, 0]]],
+        [q_true[[3, 0]]]
+    ];
+    let dq = quat_multiply(q_meas, &inv);
+    let sign = dq[[3, 0]].signum();
+    [0, 1, 2].map(|k| 2. * sign * dq[[k, 0]])
+}
+
+#[test]
+fn reports_attitude_at_image_time() {
+    // 5 Hz output, 0.15 s latency, truth recorded every 0.1 s
+    let tracker = StarTracker {
+        latency: 0.15,
+        ..noiseless()
+    };
+    let rate = 0.01;
+    let ephem = ephemeris();
+    let mut signal = StarTrackerSignal::initialize(&tracker, &spin_z(rate, 0.));
+    assert!(!signal.valid);
+    assert_eq!(signal.status, StarTrackerStatus::NoSample);
+    for k in 1..=10 {
+        let t = k as f64 * 0.1;
+        signal.update(&tracker, &spin_z(rate, t), &ephem, t);
+    }
+    assert!(signal.valid);
+    assert!((signal.sample_time - 1.).abs() < 1e-12);
+    assert!((signal.image_time - 0.85).abs() < 1e-12);
+    let e = error(&signal.q_sc_eci, &spin_z(rate, 0.85).q_sc_eci);
+    assert!(e.iter().all(|v| v.abs() < 1e-9), "{e:?}");
+}
+
+#[test]
+fn noise_in_sensor_axes_and_constant_spatial_error() {
+    // Boresight along body x, roll reference along body y
+    let tracker = StarTracker {
+        boresight_body: [1., 0., 0.],
+        roll_reference_body: [0., 1., 0.],
+        cross_boresight_noise: 1e-5,
+        roll_noise: 1e-4,
+        spatial_error_cross: 0.,
+        spatial_error_roll: 0.,
+        latency: 0.,
+        update_rate: 10.,
+        ..Default::default()
+    };
+    let ephem = ephemeris();
+    let truth = TruthAttitudeSignal::default();
+    let mut signal = StarTrackerSignal::initialize(&tracker, &truth);
+    let mut e = [Vec::new(), Vec::new(), Vec::new()];
+    for k in 1..=20_000 {
+        signal.update(&tracker, &truth, &ephem, k as f64 * 0.1);
+        assert!(signal.valid);
+        for (i, v) in error(&signal.q_sc_eci, &truth.q_sc_eci)
+            .into_iter()
+            .enumerate()
+        {
+            e[i].push(v);
+        }
+    }
+    // Roll about body x; cross-boresight on body y and z
+    assert!((std_dev(&e[0]) - 1e-4).abs() < 3e-6, "{}", std_dev(&e[0]));
+    assert!((std_dev(&e[1]) - 1e-5).abs() < 3e-7, "{}", std_dev(&e[1]));
+    assert!((std_dev(&e[2]) - 1e-5).abs() < 3e-7, "{}", std_dev(&e[2]));
+
+    // Spatial error holds while the boresight stays on the same stars
+    let tracker = StarTracker {
+        spatial_error_cross: 1e-5,
+        spatial_error_roll: 5e-5,
+        ..noiseless()
+    };
+    let mut signal = StarTrackerSignal::initialize(&tracker, &truth);
+    signal.update(&tracker, &truth, &ephem, 1.);
+    let first = error(&signal.q_sc_eci, &truth.q_sc_eci);
+    assert!(first.iter().any(|v| v.abs() > 1e-7));
+    signal.update(&tracker, &truth, &ephem, 2.);
+    let second = error(&signal.q_sc_eci, &truth.q_sc_eci);
+    assert!((0..3).all(|k| (first[k] - second[k]).abs() < 1e-15));
+}
+
+#[test]
+fn exclusion_zones() {
+    let tracker = StarTracker::default();
+    let mut ephem = ephemeris();
+    assert_eq!(tracker.exclusion(&[0., 0., 1.], &ephem), None);
+    assert_eq!(
+        tracker.exclusion(&[0., 0., -1.], &ephem),
+        Some(StarTrackerStatus::SunExcluded)
+    );
+    assert_eq!(
+        tracker.exclusion(&[0., -1., 0.], &ephem),
+        Some(StarTrackerStatus::MoonExcluded)
+    );
+
+    // Limb at asin(RE / r) ~ 68 deg off nadir; 20 deg margin above it
+    let limb = (6378.137e3_f64 / (6378.137e3 + 500e3)).asin();
+    let off_nadir = |angle: f64| [-angle.cos(), 0., angle.sin()];
+    assert_eq!(
+        tracker.exclusion(&off_nadir(limb + 15. * DEG), &ephem),
+        Some(StarTrackerStatus::EarthExcluded)
+    );
+    assert_eq!(
+        tracker.exclusion(&off_nadir(limb + 25. * DEG), &ephem),
+        None
+    );
+
+    // No Sun exclusion in umbra
+    ephem.shadow_fraction = 0.;
+    assert_eq!(tracker.exclusion(&[0., 0., -1.], &ephem), None);
+}
+
+#[test]
+fn loses_lock_above_tracking_rate() {
+    let tracker = StarTracker {
+        max_rate: 0.02,
+        latency: 0.,
+        ..noiseless()
+    };
+    let ephem = ephemeris();
+    let mut signal = StarTrackerSignal::initialize(&tracker, &spin_z(0.05, 0.));
+    signal.update(&tracker, &spin_z(0.05, 0.2), &ephem, 0.2);
+    assert_eq!(signal.status, StarTrackerStatus::LostLock);
+    assert!(!signal.valid);
+
+    signal.update(&tracker, &spin_z(0.01, 0.4), &ephem, 0.4);
+    assert_eq!(signal.status, StarTrackerStatus::Tracking);
+    assert!(signal.valid);
+}
+
+#[test]
+fn samples_published_on_the_sensor_bus() {
+    let params = SpacecraftParamBus {
+        sc_attitude: SpacecraftAttitudeArchitecture::initialize(
+            array![[0.], [0.], [0.], [1.]],
+            array![[0.], [0.], [0.001]],
+        ),
+        sc_sensors: SpacecraftSensorArchitecture {
+            star_tracker: Some(StarTracker {
+                sun_exclusion: 0.,
+                earth_exclusion: 0.,
+                moon_exclusion: 0.,
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut sc = spacecraft(params);
+    let initial = sc.initial_state();
+    assert!(!initial.star_tracker_valid);
+    assert_eq!(
+        initial.star_tracker_status,
+        StarTrackerStatus::NoSample as u8
+    );
+
+    let raw = step_plant(&mut sc, &ActuatorBus::default(), 10);
+    let sample = sc
+        .curr_sc_state
+        .truth_sensor_bus
+        .star_tracker
+        .clone()
+        .unwrap();
+    assert!(sample.valid && raw.star_tracker_valid);
+    assert!((raw.star_tracker_time - 0.9).abs() < 1e-12);
+    assert_eq!(raw.star_tracker_status, StarTrackerStatus::Tracking as u8);
+    let q = &sc.curr_sc_state.truth_attitude.signal.q_sc_eci;
+    // Within 1 mrad of truth; 0.1 s of 1 mrad/s motion plus noise
+    let q_raw = raw.star_tracker_q.into_shape_with_order((4, 1)).unwrap();
+    assert_eq!(q_raw, sample.q_sc_eci);
+    let e = error(&q_raw, q);
+    assert!(e.iter().all(|v| v.abs() < 1e-3), "{e:?}");
+}
+
+#[test]
+fn rejects_roll_reference_along_boresight() {
+    let params = SpacecraftParamBus {
+        sc_sensors: SpacecraftSensorArchitecture {
+            star_tracker: Some(StarTracker {
+                roll_reference_body: [0., 0., 1.],
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert!(matches!(
+        Spacecraft::initialize(0.1, params),
+        Err(ConfigurationError::Sensors(_))
+    ));
+}
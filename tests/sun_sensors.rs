@@ -0,0 +1,246 @@
+mod common;
+
+use common::{spacecraft, std_dev, step_plant};
+use ndarray::{array, Array1};
+use polaris_fsw::actuators::types::ActuatorBus;
+use polaris_plant::ephemeris::celestial::{albedo_fraction, AU, EARTH_ALBEDO};
+use polaris_plant::ephemeris::consts::RE;
+use polaris_plant::ephemeris::types::TruthEphemerisSignal;
+use polaris_plant::sc_types::{
+    ConfigurationError, SpacecraftParamBus, SpacecraftSensorArchitecture,
+};
+use polaris_plant::sensors::sun_sensors::{
+    CoarseSunSensorArray, CoarseSunSensorSignal, FineSunSensor, FineSunSensorArray,
+    FineSunSensorSignal, MAX_ADC_BITS,
+};
+use polaris_plant::sensors::types::SensorError;
+use polaris_plant::Spacecraft;
+
+const DEG: f64 = std::f64::consts::PI / 180.;
+
+// Sun 1 AU from the spacecraft along `sun_body`; identity attitude, no albedo
+fn ephemeris(sun_body: [f64; 3]) -> TruthEphemerisSignal {
+    let mut ephem = TruthEphemerisSignal::default();
+    let norm = sun_body.iter().map(|v| v * v).sum::<f64>().sqrt();
+    ephem.sun_body = array![[sun_body[0]], [sun_body[1]], [sun_body[2]]] / norm;
+    ephem.r_sun_eci = &ephem.r_sc_eci + AU * &ephem.sun_body;
+    ephem
+}
+
+fn noiseless_css() -> CoarseSunSensorArray {
+    CoarseSunSensorArray {
+        noise: 0.,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn albedo_follows_lambert_phase() {
+    let r = RE + 500e3;
+    let r_sc = Array1::from(vec![r, 0., 0.]);
+    let full = 2. / 3. * EARTH_ALBEDO * (RE / r).powi(2);
+    let at = |sun: [f64; 3]| albedo_fraction(&r_sc.view(), &Array1::from(sun.to_vec()).view());
+    assert!((at([AU, 0., 0.]) - full).abs() < 1e-12);
+    // Quarter phase: 1 / pi of full
+    assert!((at([0., AU, 0.]) - full / std::f64::consts::PI).abs() < 1e-12);
+    assert!(at([-AU, 0., 0.]).abs() < 1e-12);
+}
+
+#[test]
+fn coarse_cosine_law_fov_and_eclipse() {
+    let array = noiseless_css();
+    let max_count: f64 = 4095.;
+    let counts = |ephem: &TruthEphemerisSignal| {
+        let mut signal = CoarseSunSensorSignal::initialize(&array);
+        assert!(!signal.valid);
+        signal.update(&array, ephem, 0.1);
+        assert!(signal.valid);
+        signal.counts
+    };
+
+    // 60 deg off +z and 30 deg off +x
+    let c = counts(&ephemeris([(30. * DEG).cos(), 0., (60. * DEG).cos()]));
+    assert_eq!(c[4], (0.5 / 1.3 * max_count).round());
+    assert_eq!(c[0], ((30. * DEG).cos() / 1.3 * max_count).round());
+    assert!(c[1] == 0. && c[2] == 0. && c[3] == 0. && c[5] == 0.);
+
+    // Beyond the 80 deg half-angle
+    let c = counts(&ephemeris([(85. * DEG).sin(), 0., (85. * DEG).cos()]));
+    assert_eq!(c[4], 0.);
+
+    // Eclipse leaves only albedo on the nadir (-x) face
+    let mut ephem = ephemeris([0., 0., 1.]);
+    ephem.shadow_fraction = 0.;
+    ephem.albedo_fraction = 0.2;
+    let c = counts(&ephem);
+    assert_eq!(c[1], (0.2 / 1.3 * max_count).round());
+    assert_eq!(c.sum(), c[1]);
+}
+
+#[test]
+fn coarse_noise_bias_and_saturation() {
+    let mut array = CoarseSunSensorArray {
+        noise: 0.01,
+        ..Default::default()
+    };
+    array.sensors[4].bias = 0.1;
+    let ephem = ephemeris([0., (60. * DEG).sin(), (60. * DEG).cos()]);
+    let mut signal = CoarseSunSensorSignal::initialize(&array);
+    let mut x = Vec::new();
+    for k in 1..=20_000 {
+        signal.update(&array, &ephem, k as f64 * 0.1);
+        x.push(signal.counts[4]);
+    }
+    let lsb = 1.3 / 4095.;
+    let mean = x.iter().sum::<f64>() / x.len() as f64 * lsb;
+    assert!((mean - 0.6).abs() < 5e-4, "{mean}");
+    assert!((std_dev(&x) * lsb - 0.01).abs() < 3e-4);
+
+    assert_eq!(array.quantize(2.), 4095.);
+    assert_eq!(array.quantize(-0.1), 0.);
+}
+
+#[test]
+fn fine_angles_fov_and_albedo() {
+    let head = FineSunSensor {
+        noise: 0.,
+        resolution: 0.,
+        ..Default::default()
+    };
+    let (a, b) = (10. * DEG, -5. * DEG);
+    let mut ephem = ephemeris([a.tan(), b.tan(), 1.]);
+    let angles = head.angles(&ephem).unwrap();
+    assert!((angles[0] - a).abs() < 1e-12 && (angles[1] - b).abs() < 1e-12);
+
+    // Reference axis along body y swaps the axes
+    let rotated = FineSunSensor {
+        reference_body: [0., 1., 0.],
+        ..head.clone()
+    };
+    let angles = rotated.angles(&ephem).unwrap();
+    assert!((angles[0] - b).abs() < 1e-12 && (angles[1] + a).abs() < 1e-12);
+
+    // Albedo inside the field of view pulls alpha toward the nadir
+    ephem.nadir_body = array![[(40. * DEG).sin()], [0.], [(40. * DEG).cos()]];
+    ephem.albedo_fraction = 0.2;
+    let pulled = head.angles(&ephem).unwrap();
+    assert!(pulled[0] > a + 1. * DEG && pulled[0] < 40. * DEG);
+
+    // Outside the field of view and in eclipse
+    assert!(head.angles(&ephemeris([1., 0., 0.1])).is_none());
+    let mut dark = ephemeris([0., 0., 1.]);
+    dark.shadow_fraction = 0.;
+    assert!(head.angles(&dark).is_none());
+}
+
+#[test]
+fn fine_noise_and_quantization() {
+    let array = FineSunSensorArray {
+        heads: vec![FineSunSensor {
+            bias: [1e-3, 0.],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let head = &array.heads[0];
+    let ephem = ephemeris([0., 0., 1.]);
+    let mut signal = FineSunSensorSignal::initialize(&array);
+    let (mut alpha, mut beta) = (Vec::new(), Vec::new());
+    for k in 1..=20_000 {
+        signal.update(&array, &ephem, k as f64 * 0.1);
+        assert!(signal.valid[0]);
+        alpha.push(signal.alpha[0]);
+        beta.push(signal.beta[0]);
+        let steps = signal.alpha[0] / head.resolution;
+        assert!((steps - steps.round()).abs() < 1e-6);
+    }
+    let mean = alpha.iter().sum::<f64>() / alpha.len() as f64;
+    assert!((mean - 1e-3).abs() < 2e-5, "{mean}");
+    assert!(
+        (std_dev(&beta) - head.noise).abs() < 3e-5,
+        "{}",
+        std_dev(&beta)
+    );
+}
+
+#[test]
+fn samples_published_on_the_sensor_bus() {
+    let params = SpacecraftParamBus {
+        sc_sensors: SpacecraftSensorArchitecture {
+            coarse_sun_sensors: Some(CoarseSunSensorArray::default()),
+            fine_sun_sensors: Some(FineSunSensorArray::default()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut sc = spacecraft(params);
+    let initial = sc.initial_state();
+    assert!(!initial.css_valid);
+    assert_eq!(initial.fss_valid, vec![false]);
+
+    let raw = step_plant(&mut sc, &ActuatorBus::default(), 1);
+    let bus = &sc.curr_sc_state.truth_sensor_bus;
+    let css = bus.coarse_sun_sensors.clone().unwrap();
+    assert!(css.valid && raw.css_valid);
+    assert_eq!(raw.css_counts.len(), 6);
+    assert_eq!(raw.css_counts, css.counts);
+    let fss = bus.fine_sun_sensors.clone().unwrap();
+    assert_eq!(raw.fss_alpha, fss.alpha);
+    assert_eq!(raw.fss_beta, fss.beta);
+    assert_eq!(raw.fss_valid, fss.valid);
+
+    // The brightest face looks at the Sun when lit
+    let ephem = &sc.curr_sc_state.truth_ephemeris.signal;
+    let sun = &ephem.sun_body;
+    assert!((sun.iter().map(|v| v * v).sum::<f64>() - 1.).abs() < 1e-12);
+    if ephem.shadow_fraction == 1. {
+        let (axis, component) = (0..3)
+            .map(|k| (k, sun[[k, 0]]))
+            .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+            .unwrap();
+        let face = 2 * axis + usize::from(component < 0.);
+        let brightest = (0..6)
+            .max_by(|&i, &j| css.counts[i].total_cmp(&css.counts[j]))
+            .unwrap();
+        assert_eq!(brightest, face);
+    }
+}
+
+#[test]
+fn rejects_fine_sensor_reference_along_boresight() {
+    let params = SpacecraftParamBus {
+        sc_sensors: SpacecraftSensorArchitecture {
+            fine_sun_sensors: Some(FineSunSensorArray {
+                heads: vec![FineSunSensor {
+                    reference_body: [0., 0., 1.],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert!(matches!(
+        Spacecraft::initialize(0.1, params),
+        Err(ConfigurationError::Sensors(_))
+    ));
+}
+
+#[test]
+fn rejects_adc_wider_than_supported() {
+    let params = SpacecraftParamBus {
+        sc_sensors: SpacecraftSensorArchitecture {
+            coarse_sun_sensors: Some(CoarseSunSensorArray {
+                adc_bits: MAX_ADC_BITS + 1,
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert!(matches!(
+        Spacecraft::initialize(0.1, params),
+        Err(ConfigurationError::Sensors(SensorError::AboveLimit { .. }))
+    ));
+}
@@ -0,0 +1,180 @@
+use ndarray::{array, Array1};
+use polaris_fsw::actuators::types::ActuatorBus;
+use polaris_plant::actuators::thrusters::{Thruster, ThrusterAssembly, ThrusterSignal};
+use polaris_plant::actuators::types::ActuatorError;
+use polaris_plant::sc_types::{
+    ConfigurationError, SpacecraftActuatorArchitecture, SpacecraftMassArchitecture,
+    SpacecraftParamBus,
+};
+use polaris_plant::Spacecraft;
+
+const G0: f64 = 9.80665;
+
+fn ideal() -> Thruster {
+    Thruster {
+        rise_time: 0.,
+        fall_time: 0.,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn pulse_width_and_minimum_impulse_bit() {
+    let thr = ideal();
+    let pulse = thr.step(0., 0.3, 1.);
+    assert_eq!(pulse.burn_time, 0.3);
+    assert_eq!(pulse.level, 0.);
+    assert_eq!(thr.step(0., 1., 1.).level, 1.);
+
+    // 5 ms at 1 N is below the 10 mN s impulse bit
+    assert_eq!(thr.step(0., 5e-3, 1.).burn_time, 0.);
+
+    // With equal lags the tail returns what the rise withheld
+    let lagged = Thruster {
+        rise_time: 0.05,
+        fall_time: 0.05,
+        ..Default::default()
+    };
+    let first = lagged.step(0., 0.2, 0.25);
+    assert!(first.burn_time < 0.2 && first.level > 0.);
+    let mut total = first.burn_time;
+    let mut level = first.level;
+    for _ in 0..20 {
+        let pulse = lagged.step(level, 0., 0.25);
+        total += pulse.burn_time;
+        level = pulse.level;
+    }
+    assert!((total - 0.2).abs() < 1e-12, "{total}");
+}
+
+#[test]
+fn misalignment_and_magnitude_error() {
+    let thr = Thruster {
+        direction_body: [0., 0., 1.],
+        misalignment: [0.01, 0., 0.],
+        thrust_error: -0.05,
+        ..ideal()
+    };
+    let d = thr.actual_direction();
+    assert!((d[1] + 0.01f64.sin()).abs() < 1e-15 && (d[2] - 0.01f64.cos()).abs() < 1e-15);
+    assert!((thr.actual_thrust() - 0.95).abs() < 1e-15);
+}
+
+#[test]
+fn propellant_depletion_shifts_mass_properties() {
+    let assembly = ThrusterAssembly {
+        thrusters: vec![ideal()],
+        propellant_mass: 10.,
+        tank_position_body: [1., 0., 0.],
+    };
+    let wet = SpacecraftMassArchitecture::default();
+    let dry = assembly.mass_properties(&wet, 10.);
+    assert_eq!(dry.mass, 90.);
+    assert!((dry.r_cm_body[[0, 0]] + 1. / 9.).abs() < 1e-15);
+    let shift = 10. + 10. / 9.;
+    assert_eq!(dry.j_sc[[0, 0]], wet.j_sc[[0, 0]]);
+    assert!((dry.j_sc[[1, 1]] - (wet.j_sc[[1, 1]] - shift)).abs() < 1e-12);
+    assert!((dry.j_sc[[2, 2]] - (wet.j_sc[[2, 2]] - shift)).abs() < 1e-12);
+
+    // Thrust is cut back to what the tank can supply
+    let assembly = ThrusterAssembly {
+        propellant_mass: 1. / (220. * G0),
+        ..assembly
+    };
+    let mut signal = ThrusterSignal::initialize(&assembly);
+    signal.step(&assembly, &array![1.], 2.);
+    assert!((signal.thrust[0] - 0.5).abs() < 1e-12);
+    assert_eq!(signal.propellant_mass, 0.);
+    signal.step(&assembly, &array![1.], 2.);
+    assert_eq!(signal.thrust[0], 0.);
+}
+
+#[test]
+fn offset_thruster_forces_torques_and_mass_flow() {
+    let params = SpacecraftParamBus {
+        sc_actuators: SpacecraftActuatorArchitecture {
+            thrusters: ThrusterAssembly {
+                thrusters: vec![Thruster {
+                    position_body: [0., 0.5, 0.],
+                    direction_body: [1., 0., 0.],
+                    thrust: 2.,
+                    ..ideal()
+                }],
+                propellant_mass: 5.,
+                tank_position_body: [0., 0., 0.],
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut sc = Spacecraft::initialize(1., params).unwrap();
+    // Commanded from the flight software bus
+    let bus = ActuatorBus {
+        thr_on_time_cmd: array![0.5],
+        ..Default::default()
+    };
+    for _ in 0..10 {
+        sc.simulate_plant(&bus);
+    }
+
+    // 50% duty cycle: 1 N along +x at y = 0.5 m
+    let act = &sc.curr_sc_state.truth_actuator_bus;
+    let force: Array1<f64> = act.net_forces.column(0).to_owned();
+    let torque: Array1<f64> = act.net_torques.column(0).to_owned();
+    assert!((&force - &array![1., 0., 0.])
+        .iter()
+        .all(|x| x.abs() < 1e-15));
+    assert!((&torque - &array![0., 0., -0.5])
+        .iter()
+        .all(|x| x.abs() < 1e-15));
+
+    let used = 10. / (220. * G0);
+    assert!((act.thrusters.propellant_mass - (5. - used)).abs() < 1e-12);
+    let mass = act.mass_properties(&sc.sc_param_bus.sc_mass);
+    assert!((mass.mass - (100. - used)).abs() < 1e-12);
+
+    // Spin-up about -z from the thrust torque
+    let omega = &sc.curr_sc_state.truth_attitude.signal.omega_sc;
+    assert!((omega[[2, 0]] + 0.5 * 10. / 30.).abs() < 1e-9, "{omega}");
+}
+
+#[test]
+fn rejects_negative_time_constant() {
+    let params = SpacecraftParamBus {
+        sc_actuators: SpacecraftActuatorArchitecture {
+            thrusters: ThrusterAssembly {
+                thrusters: vec![Thruster {
+                    rise_time: -1.,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert!(matches!(
+        Spacecraft::initialize(1., params),
+        Err(ConfigurationError::Actuators(_))
+    ));
+}
+
+#[test]
+fn rejects_propellant_heavier_than_spacecraft() {
+    let params = SpacecraftParamBus {
+        sc_actuators: SpacecraftActuatorArchitecture {
+            thrusters: ThrusterAssembly {
+                propellant_mass: 100.,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert!(matches!(
+        Spacecraft::initialize(1., params),
+        Err(ConfigurationError::Actuators(
+            ActuatorError::PropellantExceedsMass { .. }
+        ))
+    ));
+}
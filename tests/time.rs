@@ -0,0 +1,111 @@
+use polaris_plant::time::types::{
+    gregorian_to_jd, jd_to_gregorian, tai_utc_at_utc, Epoch, TimeScale,
+};
+
+fn assert_clock(epoch: &Epoch, scale: TimeScale, expected: (i32, u32, u32, u32, u32, f64)) {
+    let (year, month, day, hour, minute, second) = epoch.to_gregorian(scale);
+    assert_eq!(
+        (year, month, day, hour, minute),
+        (expected.0, expected.1, expected.2, expected.3, expected.4),
+        "{scale:?}"
+    );
+    assert!((second - expected.5).abs() < 1e-6, "{scale:?}: {second}");
+}
+
+#[test]
+fn vallado_example_3_7() {
+    // 14 May 2004 16:43:00 UTC, DUT1 = -0.463326 s, TAI - UTC = 32 s
+    let epoch = Epoch::from_gregorian(TimeScale::UTC, 2004, 5, 14, 16, 43, 0.).with_dut1(-0.463326);
+
+    assert_clock(&epoch, TimeScale::UTC, (2004, 5, 14, 16, 43, 0.));
+    assert_clock(&epoch, TimeScale::UT1, (2004, 5, 14, 16, 42, 59.536674));
+    assert_clock(&epoch, TimeScale::TAI, (2004, 5, 14, 16, 43, 32.));
+    assert_clock(&epoch, TimeScale::GPS, (2004, 5, 14, 16, 43, 13.));
+    assert_clock(&epoch, TimeScale::TT, (2004, 5, 14, 16, 44, 4.184));
+
+    assert!((epoch.jd(TimeScale::UT1) - 2453140.19652241).abs() < 1e-8);
+    assert!((epoch.jd(TimeScale::TT) - 2453140.19727065).abs() < 1e-8);
+    assert!((epoch.centuries_since_j2000(TimeScale::UT1) - 0.043674100545).abs() < 1e-12);
+    assert!((epoch.centuries_since_j2000(TimeScale::TT) - 0.043674121031).abs() < 1e-12);
+}
+
+#[test]
+fn julian_and_modified_julian_dates() {
+    // J2000.0 is 2000-01-01 12:00:00 TT
+    let j2000 = Epoch::default();
+    assert_eq!(j2000.jd(TimeScale::TT), 2451545.0);
+    assert_eq!(j2000.mjd(TimeScale::TT), 51544.5);
+    assert_clock(&j2000, TimeScale::UTC, (2000, 1, 1, 11, 58, 55.816));
+    assert_eq!(Epoch::from_mjd(TimeScale::TT, 51544.5), j2000);
+
+    // GPS time started at 1980-01-06 00:00:00 UTC, 7300.5 days before J2000
+    let gps0 = Epoch::from_gregorian(TimeScale::UTC, 1980, 1, 6, 0, 0, 0.);
+    assert_eq!(gps0.seconds_since_j2000(TimeScale::GPS), -630763200.);
+
+    // Meeus Example 7.a: 1957 October 4.81 (Sputnik 1)
+    assert!((gregorian_to_jd(1957, 10, 4, 0.81) - 2436116.31).abs() < 1e-9);
+}
+
+#[test]
+fn gregorian_round_trips() {
+    for (year, month, day) in [
+        (1972, 1, 1),
+        (1999, 12, 31),
+        (2000, 2, 29),
+        (2100, 2, 28),
+        (2100, 3, 1),
+        (2024, 2, 29),
+        (1900, 3, 1),
+    ] {
+        let jd = gregorian_to_jd(year, month, day, 0.25);
+        let (y, m, d, f) = jd_to_gregorian(jd);
+        assert_eq!((y, m, d), (year, month, day));
+        assert!((f - 0.25).abs() < 1e-9);
+    }
+    // Century years are leap years only every 400 years
+    assert_eq!(
+        gregorian_to_jd(1900, 3, 1, 0.) - gregorian_to_jd(1900, 2, 28, 0.),
+        1.
+    );
+    assert_eq!(
+        gregorian_to_jd(2000, 3, 1, 0.) - gregorian_to_jd(2000, 2, 28, 0.),
+        2.
+    );
+
+    for scale in [
+        TimeScale::UTC,
+        TimeScale::TAI,
+        TimeScale::TT,
+        TimeScale::GPS,
+    ] {
+        let epoch = Epoch::from_gregorian(scale, 2019, 7, 4, 23, 59, 59.25);
+        assert_clock(&epoch, scale, (2019, 7, 4, 23, 59, 59.25));
+    }
+}
+
+#[test]
+fn leap_second_at_end_of_2016() {
+    let before = Epoch::from_gregorian(TimeScale::UTC, 2016, 12, 31, 23, 59, 59.);
+    let leap = Epoch::from_gregorian(TimeScale::UTC, 2016, 12, 31, 23, 59, 60.5);
+    let after = Epoch::from_gregorian(TimeScale::UTC, 2017, 1, 1, 0, 0, 0.);
+
+    // TAI - UTC steps from 36 s to 37 s
+    let day_2017 = (gregorian_to_jd(2017, 1, 1, 0.) - 2451545.) * 86400.;
+    assert_eq!(tai_utc_at_utc(day_2017 - 1.), 36.);
+    assert_eq!(tai_utc_at_utc(day_2017), 37.);
+    assert_clock(&before, TimeScale::TAI, (2017, 1, 1, 0, 0, 35.));
+    assert_clock(&leap, TimeScale::TAI, (2017, 1, 1, 0, 0, 36.5));
+    assert_clock(&after, TimeScale::TAI, (2017, 1, 1, 0, 0, 37.));
+
+    // The last UTC minute of 2016 held 61 seconds
+    let elapsed =
+        after.seconds_since_j2000(TimeScale::TAI) - before.seconds_since_j2000(TimeScale::TAI);
+    assert_eq!(elapsed, 2.);
+    assert_clock(&leap, TimeScale::UTC, (2016, 12, 31, 23, 59, 60.5));
+    assert_clock(&before.advance(2.), TimeScale::UTC, (2017, 1, 1, 0, 0, 0.));
+    assert_clock(
+        &before.advance(0.5),
+        TimeScale::UTC,
+        (2016, 12, 31, 23, 59, 59.5),
+    );
+}
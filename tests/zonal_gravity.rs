@@ -0,0 +1,114 @@
+use std::f64::consts::PI;
+
+use altai_rs::meta::types::{Generic1D, Generic2D};
+use ndarray::{array, concatenate, s, Axis};
+use polaris_plant::ephemeris::consts::{J2, MU, RE};
+use polaris_plant::ephemeris::elements::ClassicalElements;
+use polaris_plant::ephemeris::kinedynamics::{orbital_perturbed, zonal_gravity};
+use polaris_plant::ephemeris::types::{ForceModel, ForceModelError, GravityModel};
+use polaris_plant::ode::{DormandPrince45, Integrator};
+use polaris_plant::sc_types::{ConfigurationError, SpacecraftParamBus};
+use polaris_plant::Spacecraft;
+
+fn unforced_inputs() -> Generic2D {
+    concatenate![
+        Axis(1),
+        array![[0.], [0.], [0.]],
+        array![[1.], [0.], [0.]],
+        Generic2D::eye(3)
+    ]
+}
+
+fn raan(state: &Generic1D) -> f64 {
+    let r = state.slice(s![0..3]);
+    let v = state.slice(s![3..6]);
+    let hx = r[1] * v[2] - r[2] * v[1];
+    let hy = r[2] * v[0] - r[0] * v[2];
+    hx.atan2(-hy)
+}
+
+#[test]
+fn j2_matches_closed_form() {
+    let rsc: Generic1D = array![4000e3, -3000e3, 5000e3];
+    let rmag = rsc.dot(&rsc).sqrt();
+    let u2 = (rsc[2] / rmag).powi(2);
+    let fac = -1.5 * J2 * MU * RE.powi(2) / rmag.powi(5);
+    let expected = array![
+        fac * rsc[0] * (1. - 5. * u2),
+        fac * rsc[1] * (1. - 5. * u2),
+        fac * rsc[2] * (3. - 5. * u2)
+    ];
+
+    let asc = zonal_gravity(&rsc.view(), 2);
+    for (a, e) in asc.iter().zip(expected.iter()) {
+        assert!((a - e).abs() < 1e-12 * e.abs().max(1e-9), "{a} vs {e}");
+    }
+}
+
+#[test]
+fn j2_secular_raan_drift() {
+    let coe = ClassicalElements {
+        a: RE + 700e3,
+        e: 0.,
+        i: 98f64.to_radians(),
+        raan: 0.5,
+        argp: 0.,
+        nu: 0.,
+    };
+    let (r0, v0) = coe.to_cartesian(MU);
+    let state0 = concatenate![Axis(0), r0.column(0), v0.column(0)];
+
+    let forces = ForceModel {
+        gravity: GravityModel::Zonal { degree: 2 },
+        ..Default::default()
+    };
+    let inpts = unforced_inputs();
+    let period = 2. * PI * (coe.a.powi(3) / MU).sqrt();
+    let mut dp = DormandPrince45::new(period, 1e-6, 1e-12);
+
+    let norbits = 15;
+    let mut state = state0.to_owned();
+    for k in 0..norbits {
+        state = dp.integrate(
+            &|t, x, inpt| orbital_perturbed(t, x, inpt, &forces),
+            k as f64 * period,
+            &state,
+            &inpts,
+        );
+    }
+
+    // Vallado Eq. 9-41
+    let n = (MU / coe.a.powi(3)).sqrt();
+    let raan_rate = -1.5 * n * J2 * (RE / coe.a).powi(2) * coe.i.cos();
+    let expected = raan_rate * norbits as f64 * period;
+    let actual = (raan(&state) - raan(&state0) + PI).rem_euclid(2. * PI) - PI;
+
+    assert!(
+        (actual - expected).abs() < 0.02 * expected.abs(),
+        "RAAN drift {actual} rad vs analytic {expected} rad"
+    );
+}
+
+#[test]
+fn higher_zonals_are_small_corrections() {
+    let rsc: Generic1D = array![RE + 500e3, 1000e3, 2000e3];
+    let a2 = zonal_gravity(&rsc.view(), 2);
+    let a6 = zonal_gravity(&rsc.view(), 6);
+    let diff = &a6 - &a2;
+    assert!(diff.dot(&diff).sqrt() < 1e-2 * a2.dot(&a2).sqrt());
+}
+
+#[test]
+fn rejects_degree_outside_j2_to_j6() {
+    for degree in [0, 1, 7] {
+        let mut params = SpacecraftParamBus::default();
+        params.sc_ephemeris.gravity = GravityModel::Zonal { degree };
+        assert!(matches!(
+            Spacecraft::initialize(0.1, params),
+            Err(ConfigurationError::ForceModel(ForceModelError::ZonalDegree(d))) if d == degree
+        ));
+    }
+    let mut params = SpacecraftParamBus::default();
+    params.sc_ephemeris.gravity = GravityModel::Zonal { degree: 6 };
+    assert!(Spacecraft::initialize(0.1, params).is_ok());
+}